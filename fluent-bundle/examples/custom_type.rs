@@ -9,6 +9,9 @@
 // Lastly, we'll also create a new formatter which will be memoizable.
 //
 // The type and its options are modeled after ECMA402 Intl.DateTimeFormat.
+//
+// Note that `fluent-bundle` ships its own `FluentDateTime` type and `DATETIME`
+// builtin; this example only shows how a custom type can be wired up.
 use intl_memoizer::Memoizable;
 use unic_langid::LanguageIdentifier;
 
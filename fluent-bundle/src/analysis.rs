@@ -0,0 +1,516 @@
+//! Static analysis of the references between the entries of resources.
+//!
+//! A [`FluentBundle`](crate::bundle::FluentBundle) only discovers a missing
+//! reference, or a cycle of references, when formatting a message which runs
+//! into it. A [`ReferenceGraph`] finds them ahead of time, for all of the
+//! messages and terms of a set of resources.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_bundle::analysis::{Node, ReferenceGraph};
+//! use fluent_bundle::FluentResource;
+//!
+//! let ftl = r#"
+//! -brand = Firefox
+//! about = About { -brand }
+//! help = { -brnad } Help
+//! loop = { loop.title }
+//!     .title = { loop }
+//! "#;
+//! let resource = FluentResource::try_new(ftl.to_string())
+//!     .expect("Failed to parse an FTL string.");
+//!
+//! let graph = ReferenceGraph::new([&resource]);
+//!
+//! assert_eq!(
+//!     graph.dangling(),
+//!     vec![(&Node::message("help"), &Node::term("brnad"))]
+//! );
+//! assert_eq!(
+//!     graph.cycles(),
+//!     vec![vec![&Node::message("loop"), &Node::attribute(Node::message("loop"), "title")]]
+//! );
+//! ```
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use fluent_syntax::ast;
+use fluent_syntax::walk::{self, walk_pattern, Position};
+
+use crate::resolver::errors::ReferenceKind;
+use crate::resolver::ResolverError;
+use crate::{FluentError, FluentResource};
+
+/// A pattern which can be referenced: the value or an attribute of a message
+/// or a term.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Node {
+    /// Whether the node belongs to a term.
+    pub term: bool,
+    /// The id of the message or the term, without the leading `-` of terms.
+    pub id: String,
+    /// The name of the attribute, or `None` for the value.
+    pub attribute: Option<String>,
+}
+
+impl Node {
+    /// The value of the message `id`.
+    pub fn message(id: &str) -> Self {
+        Self {
+            term: false,
+            id: id.to_owned(),
+            attribute: None,
+        }
+    }
+
+    /// The value of the term `id`, without the leading `-`.
+    pub fn term(id: &str) -> Self {
+        Self {
+            term: true,
+            id: id.to_owned(),
+            attribute: None,
+        }
+    }
+
+    /// The attribute `name` of the message or the term of `entry`.
+    pub fn attribute(entry: Self, name: &str) -> Self {
+        Self {
+            attribute: Some(name.to_owned()),
+            ..entry
+        }
+    }
+
+    /// Returns the node of the value of the message or term of this node.
+    pub fn entry(&self) -> Self {
+        Self {
+            attribute: None,
+            ..self.clone()
+        }
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.term {
+            f.write_str("-")?;
+        }
+        f.write_str(&self.id)?;
+        if let Some(attribute) = &self.attribute {
+            write!(f, ".{}", attribute)?;
+        }
+        Ok(())
+    }
+}
+
+/// How a pattern uses a variable, as a hint of the values it expects.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VariableUsage {
+    /// Whether the variable is formatted in a placeable.
+    pub placeable: bool,
+    /// Whether the variable is the selector of a select expression, in which
+    /// case it's usually a number, or matches the keys of the variants.
+    pub selector: bool,
+    /// The keys of the variants of the select expressions which the variable
+    /// is the selector of, with number keys as written in the source.
+    pub selector_keys: BTreeSet<String>,
+    /// The functions which the variable is passed to as an argument.
+    pub functions: BTreeSet<String>,
+}
+
+impl VariableUsage {
+    fn merge(&mut self, other: &Self) {
+        self.placeable |= other.placeable;
+        self.selector |= other.selector;
+        self.selector_keys
+            .extend(other.selector_keys.iter().cloned());
+        self.functions.extend(other.functions.iter().cloned());
+    }
+}
+
+/// Returns the variables used by `patterns`, and by the messages they
+/// reference, which `get_message` looks up.
+///
+/// Terms only see the arguments they're called with, so neither their
+/// variables nor those of the messages they reference are included.
+pub(crate) fn variables<'m>(
+    patterns: impl IntoIterator<Item = &'m ast::Pattern<&'m str>>,
+    get_message: impl Fn(&str) -> Option<&'m ast::Message<&'m str>>,
+) -> BTreeMap<String, VariableUsage> {
+    let mut variables = BTreeMap::<String, VariableUsage>::new();
+    let mut visited = BTreeSet::new();
+    let mut pending: Vec<_> = patterns.into_iter().collect();
+    while let Some(pattern) = pending.pop() {
+        let uses = Uses::from_pattern(pattern);
+        for (name, usage) in &uses.variables {
+            variables.entry(name.clone()).or_default().merge(usage);
+        }
+        for node in uses.references {
+            if node.term || visited.contains(&node) {
+                continue;
+            }
+            let message = match get_message(&node.id) {
+                Some(message) => message,
+                None => continue,
+            };
+            let pattern = match &node.attribute {
+                Some(name) => message
+                    .attributes
+                    .iter()
+                    .find(|attribute| attribute.id.name == name)
+                    .map(|attribute| &attribute.value),
+                None => message.value.as_ref(),
+            };
+            pending.extend(pattern);
+            visited.insert(node);
+        }
+    }
+    variables
+}
+
+/// The references between the values and attributes of the messages and terms
+/// of a set of resources.
+///
+/// As in a [`FluentBundle`](crate::bundle::FluentBundle), the first definition
+/// of an id wins over the following ones.
+#[derive(Clone, Debug, Default)]
+pub struct ReferenceGraph {
+    /// The defined nodes, and what their patterns use.
+    references: BTreeMap<Node, Uses>,
+    /// The ids of the defined messages and terms.
+    entries: BTreeSet<Node>,
+}
+
+/// What a pattern uses, including in its select expressions and the arguments
+/// of its calls.
+#[derive(Clone, Debug, Default)]
+struct Uses {
+    references: BTreeSet<Node>,
+    functions: BTreeSet<String>,
+    variables: BTreeMap<String, VariableUsage>,
+}
+
+impl ReferenceGraph {
+    /// Builds the graph of the entries of `resources`.
+    pub fn new<'r>(resources: impl IntoIterator<Item = &'r FluentResource>) -> Self {
+        Self::from_entries(resources.into_iter().flat_map(FluentResource::entries))
+    }
+
+    pub(crate) fn from_entries<'e>(
+        entries: impl IntoIterator<Item = &'e ast::Entry<&'e str>>,
+    ) -> Self {
+        let mut graph = Self::default();
+        for entry in entries {
+            graph.add_entry(entry);
+        }
+        graph
+    }
+
+    fn add_entry(&mut self, entry: &ast::Entry<&str>) {
+        let (node, value, attributes) = match entry {
+            ast::Entry::Message(message) => (
+                Node::message(message.id.name),
+                message.value.as_ref(),
+                &message.attributes,
+            ),
+            ast::Entry::Term(term) => (
+                Node::term(term.id.name),
+                Some(&term.value),
+                &term.attributes,
+            ),
+            _ => return,
+        };
+        if !self.entries.insert(node.clone()) {
+            return;
+        }
+
+        if let Some(value) = value {
+            self.references
+                .insert(node.clone(), Uses::from_pattern(value));
+        }
+        for attribute in attributes {
+            self.references.insert(
+                Node::attribute(node.clone(), attribute.id.name),
+                Uses::from_pattern(&attribute.value),
+            );
+        }
+    }
+
+    /// Returns the defined values and attributes.
+    pub fn nodes(&self) -> impl Iterator<Item = &Node> {
+        self.references.keys()
+    }
+
+    /// Returns whether `node` is defined.
+    pub fn contains(&self, node: &Node) -> bool {
+        self.references.contains_key(node)
+    }
+
+    /// Returns the nodes referenced by the pattern of `node`.
+    pub fn references(&self, node: &Node) -> impl Iterator<Item = &Node> {
+        self.references
+            .get(node)
+            .into_iter()
+            .flat_map(|uses| &uses.references)
+    }
+
+    /// Returns the ids of the functions called in the pattern of `node`.
+    pub fn functions(&self, node: &Node) -> impl Iterator<Item = &str> {
+        self.references
+            .get(node)
+            .into_iter()
+            .flat_map(|uses| uses.functions.iter().map(String::as_str))
+    }
+
+    /// Returns the nodes whose patterns reference `node`.
+    pub fn referrers<'g>(&'g self, node: &'g Node) -> impl Iterator<Item = &'g Node> {
+        self.references
+            .iter()
+            .filter(move |(_, uses)| uses.references.contains(node))
+            .map(|(referrer, _)| referrer)
+    }
+
+    /// Returns the references to values and attributes which aren't defined,
+    /// as pairs of the referencing node and the referenced one.
+    pub fn dangling(&self) -> Vec<(&Node, &Node)> {
+        self.references
+            .iter()
+            .flat_map(|(node, uses)| uses.references.iter().map(move |target| (node, target)))
+            .filter(|(_, target)| !self.contains(target))
+            .collect()
+    }
+
+    /// Returns the cycles of references.
+    ///
+    /// Each cycle lists the nodes which reference each other, including the
+    /// nodes which reference themselves. The references in every variant of a
+    /// select expression count, so a cycle which only goes through variants that
+    /// are never selected is listed too, even though the nodes can be formatted.
+    pub fn cycles(&self) -> Vec<Vec<&Node>> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            index: BTreeMap::new(),
+            low_link: BTreeMap::new(),
+            stack: vec![],
+            on_stack: BTreeSet::new(),
+            components: vec![],
+        };
+        for node in self.nodes() {
+            if !tarjan.index.contains_key(node) {
+                tarjan.visit(node);
+            }
+        }
+
+        let mut cycles: Vec<_> = tarjan
+            .components
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || self.references(component[0]).any(|n| n == component[0])
+            })
+            .map(|mut component| {
+                component.sort();
+                component
+            })
+            .collect();
+        cycles.sort();
+        cycles
+    }
+
+    /// Returns the messages and terms which aren't referenced by any other
+    /// message or term.
+    ///
+    /// Messages are usually formatted by the application, so it's mostly the
+    /// unreferenced terms which are unused.
+    pub fn unreferenced(&self) -> Vec<&Node> {
+        let referenced: BTreeSet<Node> = self
+            .references
+            .iter()
+            .flat_map(|(node, uses)| {
+                uses.references
+                    .iter()
+                    .filter(move |target| target.entry() != node.entry())
+                    .map(Node::entry)
+            })
+            .collect();
+        self.entries
+            .iter()
+            .filter(|entry| !referenced.contains(entry))
+            .collect()
+    }
+
+    /// Returns the errors which formatting the patterns would run into
+    /// regardless of the arguments, given the functions which are available.
+    pub(crate) fn errors(&self, has_function: impl Fn(&str) -> bool) -> Vec<FluentError> {
+        let cyclic: BTreeSet<&Node> = self.cycles().into_iter().flatten().collect();
+        let mut errors = vec![];
+        for (node, uses) in &self.references {
+            let mut add = |error| {
+                errors.push(FluentError::InvalidEntry {
+                    id: node.to_string(),
+                    error,
+                })
+            };
+            for target in uses.references.iter().filter(|t| !self.contains(t)) {
+                if target.attribute.is_none() && self.entries.contains(target) {
+                    add(ResolverError::NoValue(target.id.clone()));
+                } else {
+                    add(ResolverError::Reference(target.into()));
+                }
+            }
+            for function in uses.functions.iter().filter(|f| !has_function(f)) {
+                add(ResolverError::Reference(ReferenceKind::Function {
+                    id: function.clone(),
+                }));
+            }
+            if cyclic.contains(node) {
+                add(ResolverError::Cyclic);
+            }
+        }
+        errors
+    }
+}
+
+/// Returns the node referenced by `exp`, if it's a message or a term reference.
+pub(crate) fn node_of(exp: &ast::InlineExpression<&str>) -> Option<Node> {
+    let (node, attribute) = match exp {
+        ast::InlineExpression::MessageReference { id, attribute } => {
+            (Node::message(id.name), attribute)
+        }
+        ast::InlineExpression::TermReference { id, attribute, .. } => {
+            (Node::term(id.name), attribute)
+        }
+        _ => return None,
+    };
+    Some(match attribute {
+        Some(attribute) => Node::attribute(node, attribute.name),
+        None => node,
+    })
+}
+
+impl From<&Node> for ReferenceKind {
+    fn from(node: &Node) -> Self {
+        let (id, attribute) = (node.id.clone(), node.attribute.clone());
+        if node.term {
+            Self::Term { id, attribute }
+        } else {
+            Self::Message { id, attribute }
+        }
+    }
+}
+
+/// Tarjan's algorithm for the strongly connected components of the graph.
+///
+/// The nodes are visited with an explicit stack rather than by recursion, so that
+/// long chains of references don't overflow the call stack.
+struct Tarjan<'g> {
+    graph: &'g ReferenceGraph,
+    index: BTreeMap<&'g Node, usize>,
+    low_link: BTreeMap<&'g Node, usize>,
+    stack: Vec<&'g Node>,
+    on_stack: BTreeSet<&'g Node>,
+    components: Vec<Vec<&'g Node>>,
+}
+
+impl<'g> Tarjan<'g> {
+    fn visit(&mut self, root: &'g Node) {
+        self.push(root);
+        let mut work = vec![(root, self.targets(root))];
+        while let Some((node, targets)) = work.last_mut() {
+            let node = *node;
+            if let Some(target) = targets.next() {
+                if !self.index.contains_key(target) {
+                    self.push(target);
+                    work.push((target, self.targets(target)));
+                } else if self.on_stack.contains(target) {
+                    let low_link = self.low_link[node].min(self.index[target]);
+                    self.low_link.insert(node, low_link);
+                }
+                continue;
+            }
+
+            work.pop();
+            if let Some((parent, _)) = work.last() {
+                let low_link = self.low_link[parent].min(self.low_link[node]);
+                self.low_link.insert(parent, low_link);
+            }
+            if self.low_link[node] == self.index[node] {
+                let position = self
+                    .stack
+                    .iter()
+                    .rposition(|n| *n == node)
+                    .expect("The node is on the stack");
+                let component = self.stack.split_off(position);
+                for n in &component {
+                    self.on_stack.remove(n);
+                }
+                self.components.push(component);
+            }
+        }
+    }
+
+    fn push(&mut self, node: &'g Node) {
+        let index = self.index.len();
+        self.index.insert(node, index);
+        self.low_link.insert(node, index);
+        self.stack.push(node);
+        self.on_stack.insert(node);
+    }
+
+    /// Returns the nodes referenced by `node` which are defined.
+    fn targets(&self, node: &'g Node) -> std::vec::IntoIter<&'g Node> {
+        let graph = self.graph;
+        graph
+            .references(node)
+            .filter_map(|target| graph.references.get_key_value(target))
+            .map(|(target, _)| target)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+impl Uses {
+    fn from_pattern(pattern: &ast::Pattern<&str>) -> Self {
+        let mut uses = Self::default();
+        walk_pattern(pattern, &mut |node| {
+            if let walk::Node::Inline(expression, position) = node {
+                uses.add_inline_expression(expression, position);
+            }
+        });
+        uses
+    }
+
+    fn add_inline_expression(
+        &mut self,
+        expression: &ast::InlineExpression<&str>,
+        position: Position<&str>,
+    ) {
+        if let Some(node) = node_of(expression) {
+            self.references.insert(node);
+        }
+        match expression {
+            ast::InlineExpression::FunctionReference { id, .. } => {
+                self.functions.insert(id.name.to_owned());
+            }
+            ast::InlineExpression::VariableReference { id } => {
+                let usage = self.variables.entry(id.name.to_owned()).or_default();
+                match position {
+                    Position::Placeable => usage.placeable = true,
+                    Position::Selector(variants) => {
+                        usage.selector = true;
+                        usage.selector_keys.extend(variants.iter().map(
+                            |variant| match variant.key {
+                                ast::VariantKey::Identifier { name } => name.to_owned(),
+                                ast::VariantKey::NumberLiteral { value } => value.to_owned(),
+                            },
+                        ));
+                    }
+                    Position::FunctionArgument(function) => {
+                        usage.functions.insert(function.name.to_owned());
+                    }
+                    Position::TermArgument(_) => {}
+                }
+            }
+            _ => {}
+        }
+    }
+}
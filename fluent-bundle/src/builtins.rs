@@ -1,3 +1,4 @@
+use crate::types::{parse_offset, FluentDateTime};
 use crate::{FluentArgs, FluentValue};
 
 #[allow(non_snake_case)]
@@ -11,3 +12,34 @@ pub fn NUMBER<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentV
 
     FluentValue::Number(n)
 }
+
+#[allow(non_snake_case)]
+pub fn DATETIME<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentValue<'a> {
+    let mut dt = match positional.first() {
+        Some(FluentValue::DateTime(dt)) => dt.clone(),
+        Some(FluentValue::Number(n)) if n.value.abs() <= FluentDateTime::MAX_EPOCH as f64 => {
+            FluentDateTime::new(n.value as i64, Default::default())
+        }
+        Some(FluentValue::String(s)) => match s.parse::<FluentDateTime>() {
+            Ok(dt) => dt,
+            Err(_) => return FluentValue::Error,
+        },
+        _ => return FluentValue::Error,
+    };
+
+    if dt.epoch.abs() > FluentDateTime::MAX_EPOCH {
+        return FluentValue::Error;
+    }
+    dt.options.merge(named);
+    // Only UTC and fixed offsets are supported, see `call_function`.
+    if dt
+        .options
+        .time_zone
+        .as_deref()
+        .map_or(false, |zone| parse_offset(zone).is_none())
+    {
+        return FluentValue::Error;
+    }
+
+    FluentValue::DateTime(dt)
+}
@@ -8,6 +8,7 @@ use rustc_hash::FxHashMap;
 use std::borrow::Borrow;
 use std::borrow::Cow;
 use std::collections::hash_map::Entry as HashEntry;
+use std::collections::BTreeMap;
 use std::default::Default;
 use std::fmt;
 
@@ -15,13 +16,18 @@ use fluent_syntax::ast;
 use intl_memoizer::IntlLangMemoizer;
 use unic_langid::LanguageIdentifier;
 
+use crate::analysis::{self, ReferenceGraph, VariableUsage};
 use crate::args::FluentArgs;
 use crate::entry::Entry;
 use crate::entry::GetEntry;
 use crate::errors::{EntryKind, FluentError};
 use crate::memoizer::MemoizerKind;
 use crate::message::FluentMessage;
-use crate::resolver::{ResolveValue, Scope, WriteValue};
+use crate::parts::FluentPart;
+use crate::resolver::errors::ReferenceKind;
+use crate::resolver::{
+    self, Isolation, ResolutionTrace, ResolveValue, ResolverError, ResolverLimits, Scope, TraceStep,
+};
 use crate::resource::FluentResource;
 use crate::types::FluentValue;
 
@@ -138,7 +144,9 @@ pub struct FluentBundle<R, M> {
     pub(crate) resources: Vec<R>,
     pub(crate) entries: FxHashMap<String, Entry>,
     pub(crate) intls: M,
-    pub(crate) use_isolating: bool,
+    pub(crate) isolation: Isolation,
+    pub(crate) strict: bool,
+    pub(crate) limits: ResolverLimits,
     pub(crate) transform: Option<fn(&str) -> Cow<str>>,
     pub(crate) formatter: Option<fn(&FluentValue, &M) -> Option<String>>,
 }
@@ -328,8 +336,90 @@ impl<R, M> FluentBundle<R, M> {
     ///
     /// In some cases, such as testing, the user may want
     /// to disable the isolating.
+    ///
+    /// Enabling it sets [`Isolation::FirstStrong`], and disabling it
+    /// [`Isolation::None`]. See [`set_isolation`](FluentBundle::set_isolation)
+    /// for the other ways of isolating placeables.
     pub fn set_use_isolating(&mut self, value: bool) {
-        self.use_isolating = value;
+        self.isolation = if value {
+            Isolation::FirstStrong
+        } else {
+            Isolation::None
+        };
+    }
+
+    /// Sets how placeables are isolated from the surrounding text, see
+    /// [`Isolation`].
+    ///
+    /// Different surfaces call for different isolations: a terminal may not render the
+    /// Unicode isolation marks, while HTML can use the `<bdi>` element instead.
+    pub fn set_isolation(&mut self, isolation: Isolation) {
+        self.isolation = isolation;
+    }
+
+    /// Sets whether formatting checks the arguments strictly against the pattern.
+    ///
+    /// In strict mode, [`format_pattern`](FluentBundle::format_pattern) and
+    /// [`write_pattern`](FluentBundle::write_pattern) also report:
+    ///   - The arguments which the pattern never uses, as
+    ///     [`ResolverError::UnusedArgument`].
+    ///   - The missing arguments which the pattern may use, even in variants which
+    ///     weren't selected this time.
+    ///   - The string arguments which select between plural or number variants, as
+    ///     [`ResolverError::ExpectedNumber`].
+    ///
+    /// This is meant to catch arguments drifting away from the messages in tests, and
+    /// is disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::resolver::ResolverError;
+    /// use fluent_bundle::{FluentArgs, FluentBundle, FluentError, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("
+    /// emails = { $count ->
+    ///         [one] One new email
+    ///        *[other] New emails
+    ///     }
+    /// ");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let langid_en = langid!("en-US");
+    /// let mut bundle = FluentBundle::new(vec![langid_en]);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    /// bundle.set_strict(true);
+    ///
+    /// let mut args = FluentArgs::new();
+    /// args.set("count", "1");
+    /// args.set("name", "Jane");
+    ///
+    /// let msg = bundle.get_message("emails").expect("Message doesn't exist.");
+    /// let pattern = msg.value().expect("Message has no value.");
+    /// let mut errors = vec![];
+    /// bundle.format_pattern(pattern, Some(&args), &mut errors);
+    ///
+    /// assert_eq!(
+    ///     errors,
+    ///     vec![
+    ///         FluentError::from(ResolverError::ExpectedNumber("count".to_string())),
+    ///         FluentError::from(ResolverError::UnusedArgument("name".to_string())),
+    ///     ]
+    /// );
+    /// ```
+    pub fn set_strict(&mut self, value: bool) {
+        self.strict = value;
+    }
+
+    /// Sets the limits on resolving a single pattern, see [`ResolverLimits`].
+    ///
+    /// The defaults are meant for trusted translations. Formatting translations from
+    /// untrusted sources calls for tighter limits, and a `max_output_bytes` in
+    /// particular.
+    pub fn set_limits(&mut self, limits: ResolverLimits) {
+        self.limits = limits;
     }
 
     /// This method allows to specify a function that will
@@ -406,6 +496,128 @@ impl<R, M> FluentBundle<R, M> {
         self.get_entry_message(id).map(Into::into)
     }
 
+    /// Returns the variables which formatting `pattern` uses, including in the
+    /// messages it references, so that the arguments passed to
+    /// [`format_pattern`](FluentBundle::format_pattern) can be validated.
+    ///
+    /// Terms are called with their own arguments, so the variables they use
+    /// aren't included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("
+    /// user = { $first-name } { $last-name }
+    /// welcome = Welcome, { user }!
+    /// ");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let langid_en = langid!("en-US");
+    /// let mut bundle = FluentBundle::new(vec![langid_en]);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// let msg = bundle.get_message("welcome")
+    ///     .expect("Failed to retrieve a message.");
+    /// let pattern = msg.value().expect("Message has no value.");
+    /// let variables = bundle.pattern_variables(pattern);
+    ///
+    /// assert_eq!(
+    ///     variables.keys().collect::<Vec<_>>(),
+    ///     vec!["first-name", "last-name"]
+    /// );
+    /// ```
+    pub fn pattern_variables<'bundle>(
+        &'bundle self,
+        pattern: &'bundle ast::Pattern<&'bundle str>,
+    ) -> BTreeMap<String, VariableUsage>
+    where
+        R: Borrow<FluentResource>,
+    {
+        analysis::variables([pattern], |id| self.get_entry_message(id))
+    }
+
+    /// Returns the variables which formatting the value and the attributes of
+    /// the message `id` uses, including in the messages they reference, or
+    /// `None` if there's no such message.
+    ///
+    /// See [`pattern_variables`](FluentBundle::pattern_variables) for the
+    /// variables of a single value or attribute.
+    pub fn message_variables(&self, id: &str) -> Option<BTreeMap<String, VariableUsage>>
+    where
+        R: Borrow<FluentResource>,
+    {
+        let message = self.get_entry_message(id)?;
+        let attributes = message.attributes.iter().map(|attr| &attr.value);
+        Some(analysis::variables(
+            message.value.iter().chain(attributes),
+            |id| self.get_entry_message(id),
+        ))
+    }
+
+    /// Checks all of the messages and terms of the bundle for the errors which
+    /// formatting them would run into regardless of the arguments: references
+    /// to unknown messages, terms, attributes and functions, references to
+    /// messages without a value and cyclic references.
+    ///
+    /// Each error is a [`FluentError::InvalidEntry`] with the id of the value
+    /// or attribute it was found in, ordered by id. Functions have to be added
+    /// before checking, or calls to them are reported as unknown.
+    ///
+    /// The check is conservative: the references in every variant of a select
+    /// expression count, so a message which only references itself through a
+    /// variant that is never selected is still reported as
+    /// [`ResolverError::Cyclic`](crate::resolver::ResolverError::Cyclic).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::resolver::errors::{ReferenceKind, ResolverError};
+    /// use fluent_bundle::{FluentBundle, FluentError, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("
+    /// emails = { NUMBER($count) } new emails in { -brand-name }
+    /// ");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let langid_en = langid!("en-US");
+    /// let mut bundle = FluentBundle::new(vec![langid_en]);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    /// bundle.add_builtins()
+    ///     .expect("Failed to add the builtin functions.");
+    ///
+    /// assert_eq!(
+    ///     bundle.check(),
+    ///     vec![FluentError::InvalidEntry {
+    ///         id: "emails".to_string(),
+    ///         error: ResolverError::Reference(ReferenceKind::Term {
+    ///             id: "brand-name".to_string(),
+    ///             attribute: None,
+    ///         }),
+    ///     }]
+    /// );
+    /// ```
+    pub fn check(&self) -> Vec<FluentError>
+    where
+        R: Borrow<FluentResource>,
+    {
+        let entries = self.entries.values().filter_map(|entry| match entry {
+            Entry::Message((resource_idx, entry_idx)) | Entry::Term((resource_idx, entry_idx)) => {
+                self.resources
+                    .get(*resource_idx)?
+                    .borrow()
+                    .get_entry(*entry_idx)
+            }
+            Entry::Function(_) => None,
+        });
+        ReferenceGraph::from_entries(entries).errors(|id| self.get_entry_function(id).is_some())
+    }
+
     /// Writes a formatted pattern which comes from a `FluentMessage`.
     ///
     /// # Example
@@ -449,8 +661,13 @@ impl<R, M> FluentBundle<R, M> {
         W: fmt::Write,
         M: MemoizerKind,
     {
+        let start = errors.len();
         let mut scope = Scope::new(self, args, Some(errors));
-        pattern.write(w, &mut scope)
+        let result = scope.write_root(w, pattern);
+        if self.strict {
+            self.check_arguments(pattern, args, errors, start);
+        }
+        result
     }
 
     /// Formats a pattern which comes from a `FluentMessage`.
@@ -492,9 +709,197 @@ impl<R, M> FluentBundle<R, M> {
         R: Borrow<FluentResource>,
         M: MemoizerKind,
     {
+        self.resolve_pattern(pattern, args, errors, false).0
+    }
+
+    /// Formats a pattern which comes from a `FluentMessage`, as by
+    /// [`format_pattern`](FluentBundle::format_pattern), and returns the steps of its
+    /// resolution too.
+    ///
+    /// The trace tells which messages and terms were entered, which variants were
+    /// selected and why, and which functions were called with which arguments. This is
+    /// meant for debugging translations, as it's slower than formatting alone.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("
+    /// -brand-name = Firefox
+    /// emails = { $count ->
+    ///         [one] One new email in { -brand-name }
+    ///        *[other] { $count } new emails in { -brand-name }
+    ///     }
+    /// ");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    ///
+    /// let langid_en = langid!("en-US");
+    /// let mut bundle = FluentBundle::new(vec![langid_en]);
+    /// bundle.set_use_isolating(false);
+    ///
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// let msg = bundle.get_message("emails")
+    ///     .expect("Failed to retrieve a FluentMessage.");
+    ///
+    /// let pattern = msg.value()
+    ///     .expect("Missing Value.");
+    /// let mut args = FluentArgs::new();
+    /// args.set("count", 1);
+    /// let mut errors = vec![];
+    ///
+    /// let (value, trace) = bundle.format_pattern_with_trace(&pattern, Some(&args), &mut errors);
+    ///
+    /// assert_eq!(value, "One new email in Firefox");
+    /// assert_eq!(
+    ///     trace.to_string(),
+    ///     "select $count = \"1\" (one) -> [one]\nenter -brand-name\n"
+    /// );
+    /// ```
+    pub fn format_pattern_with_trace<'bundle>(
+        &'bundle self,
+        pattern: &'bundle ast::Pattern<&'bundle str>,
+        args: Option<&FluentArgs>,
+        errors: &mut Vec<FluentError>,
+    ) -> (Cow<'bundle, str>, ResolutionTrace)
+    where
+        R: Borrow<FluentResource>,
+        M: MemoizerKind,
+    {
+        let (value, steps) = self.resolve_pattern(pattern, args, errors, true);
+        let trace = ResolutionTrace {
+            steps: steps.unwrap_or_default(),
+        };
+        (value, trace)
+    }
+
+    fn resolve_pattern<'bundle>(
+        &'bundle self,
+        pattern: &'bundle ast::Pattern<&'bundle str>,
+        args: Option<&FluentArgs>,
+        errors: &mut Vec<FluentError>,
+        trace: bool,
+    ) -> (Cow<'bundle, str>, Option<Vec<TraceStep>>)
+    where
+        R: Borrow<FluentResource>,
+        M: MemoizerKind,
+    {
+        let start = errors.len();
         let mut scope = Scope::new(self, args, Some(errors));
+        if trace {
+            scope.trace = Some(vec![]);
+        }
         let value = pattern.resolve(&mut scope);
-        value.into_string(&scope)
+        let value = value.into_string(&scope);
+        let steps = scope.trace.take();
+        if self.strict {
+            self.check_arguments(pattern, args, errors, start);
+        }
+        (value, steps)
+    }
+
+    /// Formats a pattern which comes from a `FluentMessage` to a list of parts, which
+    /// tell the text apart from the values of the arguments, functions and references.
+    ///
+    /// This lets rich text renderers style or link the values, see [`FluentPart`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentArgs, FluentBundle, FluentPart, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("
+    /// -brand-name = Firefox
+    /// update = { -brand-name } was updated by { $user }.
+    /// ");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    ///
+    /// let langid_en = langid!("en-US");
+    /// let mut bundle = FluentBundle::new(vec![langid_en]);
+    ///
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// let msg = bundle.get_message("update")
+    ///     .expect("Failed to retrieve a FluentMessage.");
+    ///
+    /// let pattern = msg.value()
+    ///     .expect("Missing Value.");
+    /// let mut args = FluentArgs::new();
+    /// args.set("user", "Jane");
+    /// let mut errors = vec![];
+    ///
+    /// let parts = bundle.format_to_parts(&pattern, Some(&args), &mut errors);
+    ///
+    /// assert_eq!(
+    ///     parts,
+    ///     vec![
+    ///         FluentPart::Term {
+    ///             id: "brand-name",
+    ///             attribute: None,
+    ///             value: "Firefox".to_string(),
+    ///         },
+    ///         FluentPart::Text(" was updated by ".into()),
+    ///         FluentPart::Variable {
+    ///             name: "user",
+    ///             value: "Jane".to_string(),
+    ///         },
+    ///         FluentPart::Text(".".into()),
+    ///     ]
+    /// );
+    /// ```
+    pub fn format_to_parts<'bundle>(
+        &'bundle self,
+        pattern: &'bundle ast::Pattern<&'bundle str>,
+        args: Option<&FluentArgs>,
+        errors: &mut Vec<FluentError>,
+    ) -> Vec<FluentPart<'bundle>>
+    where
+        R: Borrow<FluentResource>,
+        M: MemoizerKind,
+    {
+        let start = errors.len();
+        let mut scope = Scope::new(self, args, Some(errors));
+        let parts = resolver::resolve_parts(pattern, &mut scope);
+        if self.strict {
+            self.check_arguments(pattern, args, errors, start);
+        }
+        parts
+    }
+
+    /// Reports the arguments which `pattern` never uses, and the ones it may use which
+    /// are missing, unless formatting already reported them after `start`.
+    fn check_arguments(
+        &self,
+        pattern: &ast::Pattern<&str>,
+        args: Option<&FluentArgs>,
+        errors: &mut Vec<FluentError>,
+        start: usize,
+    ) where
+        R: Borrow<FluentResource>,
+    {
+        let variables = self.pattern_variables(pattern);
+        for name in variables.keys() {
+            if args.and_then(|args| args.get(name.as_str())).is_none() {
+                let error = FluentError::from(ResolverError::Reference(ReferenceKind::Variable {
+                    id: name.clone(),
+                }));
+                if !errors[start..].contains(&error) {
+                    errors.push(error);
+                }
+            }
+        }
+        for (name, _) in args.into_iter().flat_map(FluentArgs::iter) {
+            if !variables.contains_key(name) {
+                errors.push(ResolverError::UnusedArgument(name.to_string()).into());
+            }
+        }
     }
 
     /// Makes the provided rust function available to messages with the name `id`. See
@@ -598,7 +1003,7 @@ impl<R, M> FluentBundle<R, M> {
     /// [FTL syntax guide]: https://projectfluent.org/fluent/guide/functions.html
     pub fn add_builtins(&mut self) -> Result<(), FluentError> {
         self.add_function("NUMBER", crate::builtins::NUMBER)?;
-        // TODO: DATETIME()
+        self.add_function("DATETIME", crate::builtins::DATETIME)?;
 
         Ok(())
     }
@@ -638,7 +1043,9 @@ impl<R> FluentBundle<R, IntlLangMemoizer> {
             resources: vec![],
             entries: FxHashMap::default(),
             intls: IntlLangMemoizer::new(first_locale),
-            use_isolating: true,
+            isolation: Isolation::default(),
+            strict: false,
+            limits: ResolverLimits::default(),
             transform: None,
             formatter: None,
         }
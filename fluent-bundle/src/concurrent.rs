@@ -3,6 +3,7 @@ use rustc_hash::FxHashMap;
 use unic_langid::LanguageIdentifier;
 
 use crate::memoizer::MemoizerKind;
+use crate::resolver::{Isolation, ResolverLimits};
 use crate::types::FluentType;
 
 /// Specialized [`FluentBundle`](crate::bundle::FluentBundle) over
@@ -36,7 +37,9 @@ impl<R> FluentBundle<R> {
             resources: vec![],
             entries: FxHashMap::default(),
             intls: IntlLangMemoizer::new(first_locale),
-            use_isolating: true,
+            isolation: Isolation::default(),
+            strict: false,
+            limits: ResolverLimits::default(),
             transform: None,
             formatter: None,
         }
@@ -24,6 +24,7 @@ impl std::fmt::Display for EntryKind {
 /// It contains three main types of errors that may come up
 /// during runtime use of the fluent-bundle crate.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum FluentError {
     /// An error which occurs when
     /// [`FluentBundle::add_resource`](crate::bundle::FluentBundle::add_resource)
@@ -57,6 +58,19 @@ pub enum FluentError {
     },
     ParserError(ParserError),
     ResolverError(ResolverError),
+    /// An error which occurs when a resource cannot be loaded, for instance when a
+    /// resource manager fails to read the file of a resource with the given `id`.
+    ResourceLoad {
+        id: String,
+        reason: String,
+    },
+    /// An error which [`FluentBundle::check`](crate::bundle::FluentBundle::check)
+    /// finds in the pattern with the given `id`, such as `key`, `key.attr`,
+    /// `-term` or `-term.attr`.
+    InvalidEntry {
+        id: String,
+        error: ResolverError,
+    },
 }
 
 impl std::fmt::Display for FluentError {
@@ -67,6 +81,10 @@ impl std::fmt::Display for FluentError {
             }
             Self::ParserError(err) => write!(f, "Parser error: {}", err),
             Self::ResolverError(err) => write!(f, "Resolver error: {}", err),
+            Self::ResourceLoad { id, reason } => {
+                write!(f, "Failed to load resource \"{}\": {}", id, reason)
+            }
+            Self::InvalidEntry { id, error } => write!(f, "Invalid entry \"{}\": {}", id, error),
         }
     }
 }
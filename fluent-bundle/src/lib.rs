@@ -98,6 +98,7 @@
 //! At the moment it is expected that users will use
 //! the `fluent-bundle` crate directly, while the ecosystem
 //! matures and higher level APIs are being developed.
+pub mod analysis;
 mod args;
 pub mod builtins;
 pub mod bundle;
@@ -107,6 +108,7 @@ mod errors;
 #[doc(hidden)]
 pub mod memoizer;
 mod message;
+mod parts;
 #[doc(hidden)]
 pub mod resolver;
 mod resource;
@@ -123,6 +125,7 @@ pub use args::FluentArgs;
 pub type FluentBundle<R> = bundle::FluentBundle<R, intl_memoizer::IntlLangMemoizer>;
 pub use errors::FluentError;
 pub use message::{FluentAttribute, FluentMessage};
+pub use parts::FluentPart;
 pub use resource::FluentResource;
 #[doc(inline)]
 pub use types::FluentValue;
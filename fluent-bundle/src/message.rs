@@ -1,5 +1,9 @@
+use std::collections::BTreeMap;
+
 use fluent_syntax::ast;
 
+use crate::analysis::{self, VariableUsage};
+
 /// [`FluentAttribute`] is a component of a compound [`FluentMessage`].
 ///
 /// It represents a key-value pair providing a translation of a component
@@ -265,6 +269,46 @@ impl<'m> FluentMessage<'m> {
             .find(|attr| attr.id.name == key)
             .map(Into::into)
     }
+
+    /// Returns the variables used directly in the value and the attributes of
+    /// the message.
+    ///
+    /// Variables used by the messages it references aren't included, see
+    /// [`FluentBundle::message_variables`](crate::bundle::FluentBundle::message_variables)
+    /// for those.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource};
+    ///
+    /// let source = r#"
+    /// emails = { $count ->
+    ///         [one] One new email for { $name }
+    ///        *[other] { NUMBER($count) } new emails for { $name }
+    ///     }
+    /// "#;
+    ///
+    /// let resource = FluentResource::try_new(source.to_string())
+    ///     .expect("Failed to parse the resource.");
+    ///
+    /// let mut bundle = FluentBundle::default();
+    /// bundle.add_resource(resource)
+    ///     .expect("Failed to add a resource.");
+    ///
+    /// let msg = bundle.get_message("emails")
+    ///     .expect("Failed to retrieve a message.");
+    /// let variables = msg.variables();
+    ///
+    /// assert_eq!(variables.keys().collect::<Vec<_>>(), vec!["count", "name"]);
+    /// assert!(variables["count"].selector);
+    /// assert!(variables["count"].functions.contains("NUMBER"));
+    /// assert!(variables["name"].placeable);
+    /// ```
+    pub fn variables(&self) -> BTreeMap<String, VariableUsage> {
+        let attributes = self.node.attributes.iter().map(|attr| &attr.value);
+        analysis::variables(self.node.value.iter().chain(attributes), |_| None)
+    }
 }
 
 impl<'m> From<&'m ast::Message<&'m str>> for FluentMessage<'m> {
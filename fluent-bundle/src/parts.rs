@@ -0,0 +1,73 @@
+use std::borrow::Cow;
+
+/// A segment of a formatted pattern, as returned by
+/// [`FluentBundle::format_to_parts`](crate::bundle::FluentBundle::format_to_parts).
+///
+/// Each placeable of the pattern becomes a part which tells where its value comes from,
+/// except for select expressions and literals: the former are replaced by the parts of
+/// the selected variant, and the latter are merged with the surrounding text.
+///
+/// The values of the parts are formatted as by
+/// [`FluentBundle::format_pattern`](crate::bundle::FluentBundle::format_pattern), but
+/// without isolation, which is left to the renderer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FluentPart<'bundle> {
+    /// Literal text from the pattern.
+    Text(Cow<'bundle, str>),
+    /// A placeable formatting the argument `name`.
+    Variable { name: &'bundle str, value: String },
+    /// A placeable calling the function `id`.
+    Function { id: &'bundle str, value: String },
+    /// A placeable referencing the message `id`, or its `attribute`.
+    Message {
+        id: &'bundle str,
+        attribute: Option<&'bundle str>,
+        value: String,
+    },
+    /// A placeable referencing the term `id`, or its `attribute`.
+    Term {
+        id: &'bundle str,
+        attribute: Option<&'bundle str>,
+        value: String,
+    },
+}
+
+impl FluentPart<'_> {
+    /// Returns the formatted value of the part.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentArgs, FluentBundle, FluentPart, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("intro = Welcome, { $name }.");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// let mut args = FluentArgs::new();
+    /// args.set("name", "John");
+    ///
+    /// let msg = bundle.get_message("intro").expect("Message doesn't exist.");
+    /// let pattern = msg.value().expect("Message has no value.");
+    /// let mut errors = vec![];
+    /// let parts = bundle.format_to_parts(pattern, Some(&args), &mut errors);
+    ///
+    /// assert_eq!(
+    ///     parts.iter().map(FluentPart::value).collect::<String>(),
+    ///     "Welcome, John."
+    /// );
+    /// ```
+    pub fn value(&self) -> &str {
+        match self {
+            Self::Text(value) => value,
+            Self::Variable { value, .. }
+            | Self::Function { value, .. }
+            | Self::Message { value, .. }
+            | Self::Term { value, .. } => value,
+        }
+    }
+}
@@ -39,7 +39,7 @@ where
                 id: id.name.to_string(),
                 attribute: attribute.as_ref().map(|i| i.name.to_string()),
             },
-            InlineExpression::VariableReference { id, .. } => Self::Variable {
+            InlineExpression::VariableReference { id } => Self::Variable {
                 id: id.name.to_string(),
             },
             _ => unreachable!(),
@@ -50,12 +50,24 @@ where
 /// Errors generated during the process of resolving a fluent message into a string.
 /// This process takes place in the `write` method of the `WriteValue` trait.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum ResolverError {
     Reference(ReferenceKind),
     NoValue(String),
     MissingDefault,
     Cyclic,
     TooManyPlaceables,
+    /// A message or term reference nested deeper than the `max_depth` limit.
+    TooDeeplyNested,
+    /// A formatted pattern longer than the `max_output_bytes` limit.
+    OutputTooLong,
+    /// An argument which the pattern doesn't use, reported in strict mode.
+    UnusedArgument(String),
+    /// A string argument selecting between plural or number variants, reported in
+    /// strict mode.
+    ExpectedNumber(String),
+    /// A time zone passed to `DATETIME` other than UTC or a fixed offset.
+    UnsupportedTimeZone(String),
 }
 
 impl std::fmt::Display for ResolverError {
@@ -85,6 +97,11 @@ impl std::fmt::Display for ResolverError {
             Self::MissingDefault => f.write_str("No default"),
             Self::Cyclic => f.write_str("Cyclical dependency detected"),
             Self::TooManyPlaceables => f.write_str("Too many placeables"),
+            Self::TooDeeplyNested => f.write_str("Too deeply nested references"),
+            Self::OutputTooLong => f.write_str("Output too long"),
+            Self::UnusedArgument(id) => write!(f, "Unused argument: ${}", id),
+            Self::ExpectedNumber(id) => write!(f, "Expected a number: ${}", id),
+            Self::UnsupportedTimeZone(zone) => write!(f, "Unsupported time zone: {}", zone),
         }
     }
 }
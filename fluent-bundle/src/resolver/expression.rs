@@ -5,11 +5,12 @@ use std::borrow::Borrow;
 use std::fmt;
 
 use fluent_syntax::ast;
+use intl_pluralrules::PluralCategory;
 
 use crate::memoizer::MemoizerKind;
-use crate::resolver::{ResolveValue, ResolverError};
+use crate::resolver::{ResolveValue, ResolverError, TraceEvent};
 use crate::resource::FluentResource;
-use crate::types::FluentValue;
+use crate::types::{plural_category, FluentValue};
 
 impl<'bundle> WriteValue<'bundle> for ast::Expression<&'bundle str> {
     fn write<'ast, 'args, 'errors, W, R, M>(
@@ -25,31 +26,10 @@ impl<'bundle> WriteValue<'bundle> for ast::Expression<&'bundle str> {
         match self {
             Self::Inline(exp) => exp.write(w, scope),
             Self::Select { selector, variants } => {
-                let selector = selector.resolve(scope);
-                match selector {
-                    FluentValue::String(_) | FluentValue::Number(_) => {
-                        for variant in variants {
-                            let key = match variant.key {
-                                ast::VariantKey::Identifier { name } => name.into(),
-                                ast::VariantKey::NumberLiteral { value } => {
-                                    FluentValue::try_number(value)
-                                }
-                            };
-                            if key.matches(&selector, scope) {
-                                return variant.value.write(w, scope);
-                            }
-                        }
-                    }
-                    _ => {}
+                match select_variant(selector, variants, scope) {
+                    Some(pattern) => pattern.write(w, scope),
+                    None => Ok(()),
                 }
-
-                for variant in variants {
-                    if variant.default {
-                        return variant.value.write(w, scope);
-                    }
-                }
-                scope.add_error(ResolverError::MissingDefault);
-                Ok(())
             }
         }
     }
@@ -64,3 +44,99 @@ impl<'bundle> WriteValue<'bundle> for ast::Expression<&'bundle str> {
         }
     }
 }
+
+/// Returns the pattern of the variant which `selector` selects, or of the default
+/// variant, reporting a missing default.
+pub(super) fn select_variant<'bundle, 'ast, 'args, 'errors, R, M>(
+    selector_expression: &'ast ast::InlineExpression<&'bundle str>,
+    variants: &'ast [ast::Variant<&'bundle str>],
+    scope: &mut Scope<'bundle, 'ast, 'args, 'errors, R, M>,
+) -> Option<&'ast ast::Pattern<&'bundle str>>
+where
+    R: Borrow<FluentResource>,
+    M: MemoizerKind,
+{
+    let selector = selector_expression.resolve(scope);
+    if scope.bundle.strict && scope.local_args.is_none() {
+        if let (ast::InlineExpression::VariableReference { id }, FluentValue::String(_)) =
+            (selector_expression, &selector)
+        {
+            if variants.iter().any(is_number_variant) {
+                scope.add_error(ResolverError::ExpectedNumber(id.name.to_string()));
+            }
+        }
+    }
+    match selector {
+        FluentValue::String(_) | FluentValue::Number(_) => {
+            for variant in variants {
+                let key = match variant.key {
+                    ast::VariantKey::Identifier { name } => name.into(),
+                    ast::VariantKey::NumberLiteral { value } => FluentValue::try_number(value),
+                };
+                if key.matches(&selector, scope) {
+                    trace_select(selector_expression, &selector, variant, false, scope);
+                    return Some(&variant.value);
+                }
+            }
+        }
+        _ => {}
+    }
+
+    for variant in variants {
+        if variant.default {
+            trace_select(selector_expression, &selector, variant, true, scope);
+            return Some(&variant.value);
+        }
+    }
+    scope.add_error(ResolverError::MissingDefault);
+    None
+}
+
+/// Records the selection of `variant` when the resolution is traced.
+fn trace_select<R, M>(
+    selector_expression: &ast::InlineExpression<&str>,
+    selector: &FluentValue,
+    variant: &ast::Variant<&str>,
+    default: bool,
+    scope: &mut Scope<R, M>,
+) where
+    R: Borrow<FluentResource>,
+    M: MemoizerKind,
+{
+    scope.record(|scope| {
+        let mut expression = String::new();
+        let _ = selector_expression.write_error(&mut expression);
+        let category = match selector {
+            FluentValue::Number(number) => plural_category(number, scope),
+            _ => None,
+        };
+        TraceEvent::Select {
+            selector: expression,
+            value: selector.as_string(scope).into_owned(),
+            category: category.map(|category| match category {
+                PluralCategory::ZERO => "zero",
+                PluralCategory::ONE => "one",
+                PluralCategory::TWO => "two",
+                PluralCategory::FEW => "few",
+                PluralCategory::MANY => "many",
+                PluralCategory::OTHER => "other",
+            }),
+            key: match variant.key {
+                ast::VariantKey::Identifier { name } => name.to_string(),
+                ast::VariantKey::NumberLiteral { value } => value.to_string(),
+            },
+            default,
+        }
+    });
+}
+
+/// Returns whether the variant is meant for numbers, with a number or a plural
+/// category as its key.
+fn is_number_variant(variant: &ast::Variant<&str>) -> bool {
+    match variant.key {
+        ast::VariantKey::Identifier { name } => {
+            matches!(name, "zero" | "one" | "two" | "few" | "many")
+        }
+        ast::VariantKey::NumberLiteral { .. } => true,
+    }
+}
@@ -83,13 +83,7 @@ impl<'bundle> WriteValue<'bundle> for ast::InlineExpression<&'bundle str> {
                 result
             }
             Self::FunctionReference { id, arguments } => {
-                let (resolved_positional_args, resolved_named_args) =
-                    scope.get_arguments(Some(arguments));
-
-                let func = scope.bundle.get_entry_function(id.name);
-
-                if let Some(func) = func {
-                    let result = func(resolved_positional_args.as_slice(), &resolved_named_args);
+                if let Some(result) = scope.call_function(id.name, arguments) {
                     if let FluentValue::Error = result {
                         self.write_error(w)
                     } else {
@@ -173,19 +167,9 @@ impl<'bundle> ResolveValue<'bundle> for ast::InlineExpression<&'bundle str> {
                 }
                 FluentValue::Error
             }
-            Self::FunctionReference { id, arguments } => {
-                let (resolved_positional_args, resolved_named_args) =
-                    scope.get_arguments(Some(arguments));
-
-                let func = scope.bundle.get_entry_function(id.name);
-
-                if let Some(func) = func {
-                    let result = func(resolved_positional_args.as_slice(), &resolved_named_args);
-                    result
-                } else {
-                    FluentValue::Error
-                }
-            }
+            Self::FunctionReference { id, arguments } => scope
+                .call_function(id.name, arguments)
+                .unwrap_or(FluentValue::Error),
             _ => {
                 let mut result = String::new();
                 self.write(&mut result, scope).expect("Failed to write");
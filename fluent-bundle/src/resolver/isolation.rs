@@ -0,0 +1,103 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use unicode_bidi::{get_base_direction, Direction};
+
+/// How the placeables of a pattern are isolated from the surrounding text.
+///
+/// Isolating placeables ensures that a placeable written in a different direction than
+/// the translation, such as a right-to-left user name in a left-to-right message, doesn't
+/// affect the layout of the text around it.
+///
+/// Placeables referencing messages and terms, and string literals, are never isolated,
+/// nor are the placeables of patterns without any other text.
+///
+/// # Example
+///
+/// ```
+/// use fluent_bundle::resolver::Isolation;
+/// use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+/// use unic_langid::langid;
+///
+/// let ftl_string = String::from("intro = Welcome, { $name }.");
+/// let resource = FluentResource::try_new(ftl_string)
+///     .expect("Failed to parse an FTL string.");
+/// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+/// bundle.add_resource(&resource)
+///     .expect("Failed to add FTL resources to the bundle.");
+/// bundle.set_isolation(Isolation::Html);
+///
+/// let mut args = FluentArgs::new();
+/// args.set("name", "John");
+///
+/// let msg = bundle.get_message("intro").expect("Message doesn't exist.");
+/// let pattern = msg.value().expect("Message has no value.");
+/// let mut errors = vec![];
+///
+/// assert_eq!(
+///     bundle.format_pattern(pattern, Some(&args), &mut errors),
+///     "Welcome, <bdi>John</bdi>."
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub enum Isolation {
+    /// Placeables are written as they are.
+    None,
+    /// Placeables are wrapped in the First Strong Isolate (U+2068) and Pop Directional
+    /// Isolate (U+2069) marks, leaving the direction to the renderer.
+    #[default]
+    FirstStrong,
+    /// Placeables are wrapped in the Left-to-Right Isolate (U+2066) or Right-to-Left
+    /// Isolate (U+2067) mark, depending on the direction of their first strong
+    /// character, and the Pop Directional Isolate (U+2069) mark. Placeables without any
+    /// strong character fall back to the First Strong Isolate (U+2068) mark.
+    Directional,
+    /// Placeables are wrapped in the HTML `<bdi>` element, with their `&`, `<`, `>` and
+    /// `"` characters escaped. The text of the pattern is written as it is, so that
+    /// translations may contain markup.
+    Html,
+    /// Placeables are replaced by the result of a function, which receives the
+    /// formatted placeable.
+    Custom(fn(&str) -> Cow<str>),
+}
+
+impl Isolation {
+    /// Writes a placeable which was formatted as `value`, isolated.
+    pub(crate) fn write<W: fmt::Write>(&self, w: &mut W, value: &str) -> fmt::Result {
+        match self {
+            Self::None => w.write_str(value),
+            Self::FirstStrong => write!(w, "\u{2068}{}\u{2069}", value),
+            Self::Directional => {
+                let isolate = match get_base_direction(value) {
+                    Direction::Ltr => '\u{2066}',
+                    Direction::Rtl => '\u{2067}',
+                    Direction::Mixed => '\u{2068}',
+                };
+                write!(w, "{}{}\u{2069}", isolate, value)
+            }
+            Self::Html => {
+                w.write_str("<bdi>")?;
+                write_escaped(w, value)?;
+                w.write_str("</bdi>")
+            }
+            Self::Custom(isolate) => w.write_str(&isolate(value)),
+        }
+    }
+}
+
+/// Writes `value` with the characters which are special in HTML text and attribute values
+/// escaped.
+fn write_escaped<W: fmt::Write>(w: &mut W, value: &str) -> fmt::Result {
+    let mut rest = value;
+    while let Some(idx) = rest.find(['&', '<', '>', '"']) {
+        w.write_str(&rest[..idx])?;
+        w.write_str(match rest.as_bytes()[idx] {
+            b'&' => "&amp;",
+            b'<' => "&lt;",
+            b'>' => "&gt;",
+            _ => "&quot;",
+        })?;
+        rest = &rest[idx + 1..];
+    }
+    w.write_str(rest)
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+
+/// Limits on the resolution of a single pattern, which guard against translations
+/// expanding into huge or deeply nested output, such as the Billion Laughs and
+/// Quadratic Blowup attacks.
+///
+/// Each limit is reported with its own [`ResolverError`](super::ResolverError) when
+/// exceeded.
+///
+/// # Example
+///
+/// ```
+/// use fluent_bundle::resolver::ResolverLimits;
+/// use fluent_bundle::FluentBundle;
+/// use fluent_bundle::FluentResource;
+/// use unic_langid::langid;
+///
+/// let mut bundle: FluentBundle<FluentResource> = FluentBundle::new(vec![langid!("en-US")]);
+/// bundle.set_limits(ResolverLimits {
+///     max_output_bytes: Some(4096),
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResolverLimits {
+    /// The maximum number of placeables resolved for a pattern, including the ones of
+    /// the messages and terms it references. Exceeding it stops the resolution with
+    /// [`ResolverError::TooManyPlaceables`](super::ResolverError::TooManyPlaceables).
+    ///
+    /// Defaults to 100.
+    pub max_placeables: usize,
+    /// The maximum depth of nested message and term references. A reference exceeding it
+    /// is reported as
+    /// [`ResolverError::TooDeeplyNested`](super::ResolverError::TooDeeplyNested), and
+    /// formatted as its id.
+    ///
+    /// Defaults to 100.
+    pub max_depth: usize,
+    /// The maximum length of the formatted pattern, in bytes. Exceeding it stops the
+    /// resolution with
+    /// [`ResolverError::OutputTooLong`](super::ResolverError::OutputTooLong), leaving
+    /// the output written so far.
+    ///
+    /// Defaults to `None`, for no limit.
+    pub max_output_bytes: Option<usize>,
+}
+
+impl Default for ResolverLimits {
+    fn default() -> Self {
+        Self {
+            max_placeables: 100,
+            max_depth: 100,
+            max_output_bytes: None,
+        }
+    }
+}
+
+/// Forwards to a writer until `remaining` bytes are used up, then fails every write.
+pub(super) struct LimitedWriter<'w, W> {
+    inner: &'w mut W,
+    remaining: usize,
+    pub(super) exceeded: bool,
+}
+
+impl<'w, W> LimitedWriter<'w, W> {
+    pub(super) fn new(inner: &'w mut W, limit: Option<usize>) -> Self {
+        Self {
+            inner,
+            remaining: limit.unwrap_or(usize::MAX),
+            exceeded: false,
+        }
+    }
+}
+
+impl<W: fmt::Write> fmt::Write for LimitedWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.exceeded || s.len() > self.remaining {
+            self.exceeded = true;
+            return Err(fmt::Error);
+        }
+        self.remaining -= s.len();
+        self.inner.write_str(s)
+    }
+}
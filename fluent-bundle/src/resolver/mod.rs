@@ -6,11 +6,19 @@
 pub mod errors;
 mod expression;
 mod inline_expression;
+mod isolation;
+mod limits;
+mod parts;
 mod pattern;
 mod scope;
+mod trace;
 
 pub use errors::ResolverError;
+pub use isolation::Isolation;
+pub use limits::ResolverLimits;
+pub(crate) use parts::resolve_parts;
 pub use scope::Scope;
+pub use trace::{ResolutionTrace, TraceEvent, TraceStep};
 
 use std::borrow::Borrow;
 use std::fmt;
@@ -0,0 +1,143 @@
+use super::expression::select_variant;
+use super::limits::LimitedWriter;
+use super::scope::Scope;
+use super::ResolverError;
+
+use std::borrow::{Borrow, Cow};
+
+use fluent_syntax::ast;
+
+use crate::memoizer::MemoizerKind;
+use crate::parts::FluentPart;
+use crate::resource::FluentResource;
+
+/// Resolves `pattern` to a list of parts, stopping once they exceed the
+/// `max_output_bytes` limit of the bundle.
+pub(crate) fn resolve_parts<'bundle, 'ast, 'args, 'errors, R, M>(
+    pattern: &'ast ast::Pattern<&'bundle str>,
+    scope: &mut Scope<'bundle, 'ast, 'args, 'errors, R, M>,
+) -> Vec<FluentPart<'bundle>>
+where
+    R: Borrow<FluentResource>,
+    M: MemoizerKind,
+{
+    let mut parts = vec![];
+    let mut remaining = scope.bundle.limits.max_output_bytes.unwrap_or(usize::MAX);
+    write_parts(pattern, &mut parts, &mut remaining, scope);
+    parts
+}
+
+fn write_parts<'bundle, 'ast, 'args, 'errors, R, M>(
+    pattern: &'ast ast::Pattern<&'bundle str>,
+    parts: &mut Vec<FluentPart<'bundle>>,
+    remaining: &mut usize,
+    scope: &mut Scope<'bundle, 'ast, 'args, 'errors, R, M>,
+) where
+    R: Borrow<FluentResource>,
+    M: MemoizerKind,
+{
+    for elem in &pattern.elements {
+        if scope.dirty {
+            return;
+        }
+
+        match elem {
+            ast::PatternElement::TextElement { value } => {
+                let value = match scope.bundle.transform {
+                    Some(transform) => transform(value),
+                    None => Cow::Borrowed(*value),
+                };
+                if value.len() > *remaining {
+                    scope.dirty = true;
+                    scope.add_error(ResolverError::OutputTooLong);
+                    return;
+                }
+                *remaining -= value.len();
+                push_text(parts, value);
+            }
+            ast::PatternElement::Placeable { expression } => {
+                scope.placeables += 1;
+                if scope.placeables > scope.bundle.limits.max_placeables {
+                    scope.dirty = true;
+                    scope.add_error(ResolverError::TooManyPlaceables);
+                    return;
+                }
+                write_expression_parts(pattern, expression, parts, remaining, scope);
+            }
+        }
+    }
+}
+
+fn write_expression_parts<'bundle, 'ast, 'args, 'errors, R, M>(
+    pattern: &'ast ast::Pattern<&'bundle str>,
+    expression: &'ast ast::Expression<&'bundle str>,
+    parts: &mut Vec<FluentPart<'bundle>>,
+    remaining: &mut usize,
+    scope: &mut Scope<'bundle, 'ast, 'args, 'errors, R, M>,
+) where
+    R: Borrow<FluentResource>,
+    M: MemoizerKind,
+{
+    let exp = match expression {
+        ast::Expression::Select { selector, variants } => {
+            scope.track_root(pattern);
+            if let Some(variant) = select_variant(selector, variants, scope) {
+                write_parts(variant, parts, remaining, scope);
+            }
+            return;
+        }
+        ast::Expression::Inline(ast::InlineExpression::Placeable { expression }) => {
+            return write_expression_parts(pattern, expression, parts, remaining, scope);
+        }
+        ast::Expression::Inline(exp) => exp,
+    };
+
+    // The value is written within the output limit, keeping what fits of it, as
+    // `format_pattern` does.
+    let mut value = String::new();
+    let mut limited = LimitedWriter::new(&mut value, Some(*remaining));
+    let result = scope.maybe_track(&mut limited, pattern, expression);
+    if limited.exceeded {
+        scope.dirty = true;
+        scope.add_error(ResolverError::OutputTooLong);
+    } else {
+        result.expect("Failed to write to a string.");
+    }
+    *remaining -= value.len();
+    if value.is_empty() && scope.dirty {
+        return;
+    }
+    let part = match exp {
+        ast::InlineExpression::VariableReference { id } => FluentPart::Variable {
+            name: id.name,
+            value,
+        },
+        ast::InlineExpression::FunctionReference { id, .. } => {
+            FluentPart::Function { id: id.name, value }
+        }
+        ast::InlineExpression::MessageReference { id, attribute } => FluentPart::Message {
+            id: id.name,
+            attribute: attribute.as_ref().map(|attribute| attribute.name),
+            value,
+        },
+        ast::InlineExpression::TermReference { id, attribute, .. } => FluentPart::Term {
+            id: id.name,
+            attribute: attribute.as_ref().map(|attribute| attribute.name),
+            value,
+        },
+        _ => return push_text(parts, value.into()),
+    };
+    parts.push(part);
+}
+
+/// Appends text to the parts, merging it with the previous part if it's text too.
+fn push_text<'bundle>(parts: &mut Vec<FluentPart<'bundle>>, value: Cow<'bundle, str>) {
+    if value.is_empty() {
+        return;
+    }
+    if let Some(FluentPart::Text(text)) = parts.last_mut() {
+        text.to_mut().push_str(&value);
+    } else {
+        parts.push(FluentPart::Text(value));
+    }
+}
@@ -1,5 +1,6 @@
+use super::limits::LimitedWriter;
 use super::scope::Scope;
-use super::{ResolverError, WriteValue};
+use super::{Isolation, ResolverError, WriteValue};
 
 use std::borrow::Borrow;
 use std::fmt;
@@ -11,8 +12,6 @@ use crate::resolver::ResolveValue;
 use crate::resource::FluentResource;
 use crate::types::FluentValue;
 
-const MAX_PLACEABLES: u8 = 100;
-
 impl<'bundle> WriteValue<'bundle> for ast::Pattern<&'bundle str> {
     fn write<'ast, 'args, 'errors, W, R, M>(
         &'ast self,
@@ -41,13 +40,13 @@ impl<'bundle> WriteValue<'bundle> for ast::Pattern<&'bundle str> {
                 }
                 ast::PatternElement::Placeable { ref expression } => {
                     scope.placeables += 1;
-                    if scope.placeables > MAX_PLACEABLES {
+                    if scope.placeables > scope.bundle.limits.max_placeables {
                         scope.dirty = true;
                         scope.add_error(ResolverError::TooManyPlaceables);
                         return Ok(());
                     }
 
-                    let needs_isolation = scope.bundle.use_isolating
+                    let needs_isolation = !matches!(scope.bundle.isolation, Isolation::None)
                         && len > 1
                         && !matches!(
                             expression,
@@ -59,12 +58,26 @@ impl<'bundle> WriteValue<'bundle> for ast::Pattern<&'bundle str> {
                                     ast::InlineExpression::StringLiteral { .. },
                                 )
                         );
-                    if needs_isolation {
+                    if !needs_isolation {
+                        scope.maybe_track(w, self, expression)?;
+                    } else if let Isolation::FirstStrong = scope.bundle.isolation {
                         w.write_char('\u{2068}')?;
-                    }
-                    scope.maybe_track(w, self, expression)?;
-                    if needs_isolation {
+                        scope.maybe_track(w, self, expression)?;
                         w.write_char('\u{2069}')?;
+                    } else {
+                        // The other isolations depend on the formatted placeable, which
+                        // is buffered within the output limit.
+                        let mut value = String::new();
+                        let mut limited =
+                            LimitedWriter::new(&mut value, scope.bundle.limits.max_output_bytes);
+                        let result = scope.maybe_track(&mut limited, self, expression);
+                        if limited.exceeded {
+                            scope.dirty = true;
+                            scope.add_error(ResolverError::OutputTooLong);
+                            return Ok(());
+                        }
+                        result?;
+                        scope.bundle.isolation.write(w, &value)?;
                     }
                 }
             }
@@ -93,15 +106,20 @@ impl<'bundle> ResolveValue<'bundle> for ast::Pattern<&'bundle str> {
 
         if len == 1 {
             if let ast::PatternElement::TextElement { value } = self.elements[0] {
-                return scope
+                let value = scope
                     .bundle
                     .transform
-                    .map_or_else(|| value.into(), |transform| transform(value).into());
+                    .map_or_else(|| value.into(), |transform| transform(value));
+                let max = scope.bundle.limits.max_output_bytes;
+                if max.map_or(true, |max| value.len() <= max) {
+                    return value.into();
+                }
             }
         }
 
         let mut result = String::new();
-        self.write(&mut result, scope)
+        scope
+            .write_root(&mut result, self)
             .expect("Failed to write to a string.");
         result.into()
     }
@@ -1,7 +1,11 @@
+use crate::analysis;
 use crate::bundle::FluentBundle;
+use crate::entry::GetEntry;
 use crate::memoizer::MemoizerKind;
+use crate::resolver::limits::LimitedWriter;
+use crate::resolver::trace::{TraceEvent, TraceStep};
 use crate::resolver::{ResolveValue, ResolverError, WriteValue};
-use crate::types::FluentValue;
+use crate::types::{parse_offset, FluentValue};
 use crate::{FluentArgs, FluentError, FluentResource};
 use fluent_syntax::ast;
 use std::borrow::Borrow;
@@ -17,13 +21,15 @@ pub struct Scope<'bundle, 'ast, 'args, 'errors, R, M> {
     pub(super) local_args: Option<FluentArgs<'bundle>>,
     /// The running count of resolved placeables. Used to detect the Billion
     /// Laughs and Quadratic Blowup attacks.
-    pub(super) placeables: u8,
+    pub(super) placeables: usize,
     /// Tracks hashes to prevent infinite recursion.
     traveled: smallvec::SmallVec<[&'ast ast::Pattern<&'bundle str>; 2]>,
     /// Track errors accumulated during resolving.
     pub errors: Option<&'errors mut Vec<FluentError>>,
     /// Makes the resolver bail.
     pub dirty: bool,
+    /// The steps of the resolution, when it's traced.
+    pub(crate) trace: Option<Vec<TraceStep>>,
 }
 
 impl<'bundle, 'ast, 'args, 'errors, R, M> Scope<'bundle, 'ast, 'args, 'errors, R, M> {
@@ -40,6 +46,7 @@ impl<'bundle, 'ast, 'args, 'errors, R, M> Scope<'bundle, 'ast, 'args, 'errors, R
             traveled: Default::default(),
             errors,
             dirty: false,
+            trace: None,
         }
     }
 
@@ -49,6 +56,41 @@ impl<'bundle, 'ast, 'args, 'errors, R, M> Scope<'bundle, 'ast, 'args, 'errors, R
         }
     }
 
+    /// Records a step of the resolution when it's traced, at the depth of the current
+    /// pattern.
+    pub(super) fn record(&mut self, event: impl FnOnce(&Self) -> TraceEvent) {
+        if self.trace.is_some() {
+            let depth = self.traveled.len().saturating_sub(1);
+            let event = event(self);
+            if let Some(trace) = self.trace.as_mut() {
+                trace.push(TraceStep { depth, event });
+            }
+        }
+    }
+
+    /// Writes `pattern` as the root of the resolution, stopping once the output exceeds
+    /// the `max_output_bytes` limit of the bundle.
+    pub(crate) fn write_root<W>(
+        &mut self,
+        w: &mut W,
+        pattern: &'ast ast::Pattern<&'bundle str>,
+    ) -> fmt::Result
+    where
+        R: Borrow<FluentResource>,
+        W: fmt::Write,
+        M: MemoizerKind,
+    {
+        let mut w = LimitedWriter::new(w, self.bundle.limits.max_output_bytes);
+        let result = pattern.write(&mut w, self);
+        if w.exceeded {
+            self.dirty = true;
+            self.add_error(ResolverError::OutputTooLong);
+            Ok(())
+        } else {
+            result
+        }
+    }
+
     /// This method allows us to lazily add Pattern on the stack, only if the
     /// `Pattern::resolve` has been called on an empty stack.
     ///
@@ -65,9 +107,7 @@ impl<'bundle, 'ast, 'args, 'errors, R, M> Scope<'bundle, 'ast, 'args, 'errors, R
         W: fmt::Write,
         M: MemoizerKind,
     {
-        if self.traveled.is_empty() {
-            self.traveled.push(pattern);
-        }
+        self.track_root(pattern);
         exp.write(w, self)?;
         if self.dirty {
             w.write_char('{')?;
@@ -78,6 +118,14 @@ impl<'bundle, 'ast, 'args, 'errors, R, M> Scope<'bundle, 'ast, 'args, 'errors, R
         }
     }
 
+    /// Adds `pattern` on the stack if it's empty, see
+    /// [`maybe_track`](Scope::maybe_track).
+    pub(super) fn track_root(&mut self, pattern: &'ast ast::Pattern<&'bundle str>) {
+        if self.traveled.is_empty() {
+            self.traveled.push(pattern);
+        }
+    }
+
     pub fn track<W>(
         &mut self,
         w: &mut W,
@@ -94,7 +142,17 @@ impl<'bundle, 'ast, 'args, 'errors, R, M> Scope<'bundle, 'ast, 'args, 'errors, R
             w.write_char('{')?;
             exp.write_error(w)?;
             w.write_char('}')
+        } else if self.traveled.len() > self.bundle.limits.max_depth {
+            self.add_error(ResolverError::TooDeeplyNested);
+            w.write_char('{')?;
+            exp.write_error(w)?;
+            w.write_char('}')
         } else {
+            if self.trace.is_some() {
+                if let Some(node) = analysis::node_of(exp) {
+                    self.record(|_| TraceEvent::Enter(node));
+                }
+            }
             self.traveled.push(pattern);
             let result = pattern.write(w, self);
             self.traveled.pop();
@@ -116,6 +174,43 @@ impl<'bundle, 'ast, 'args, 'errors, R, M> Scope<'bundle, 'ast, 'args, 'errors, R
         w.write_char('}')
     }
 
+    /// Calls the function `id` with `arguments`, returning `None` if there's no such
+    /// function.
+    pub(super) fn call_function(
+        &mut self,
+        id: &str,
+        arguments: &'ast ast::CallArguments<&'bundle str>,
+    ) -> Option<FluentValue<'bundle>>
+    where
+        R: Borrow<FluentResource>,
+        M: MemoizerKind,
+    {
+        let (positional, named) = self.get_arguments(Some(arguments));
+        let func = self.bundle.get_entry_function(id)?;
+        let result = func(positional.as_slice(), &named);
+        if let (FluentValue::Error, "DATETIME") = (&result, id) {
+            // The builtin can't report why it rejected a time zone, so that's done here.
+            if let Some(FluentValue::String(zone)) = named.get("timeZone") {
+                if parse_offset(zone).is_none() {
+                    self.add_error(ResolverError::UnsupportedTimeZone(zone.to_string()));
+                }
+            }
+        }
+        self.record(|scope| TraceEvent::Function {
+            id: id.to_string(),
+            positional: positional
+                .iter()
+                .map(|value| value.as_string(scope).into_owned())
+                .collect(),
+            named: named
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.as_string(scope).into_owned()))
+                .collect(),
+            result: result.as_string(scope).into_owned(),
+        });
+        Some(result)
+    }
+
     pub fn get_arguments(
         &mut self,
         arguments: Option<&'ast ast::CallArguments<&'bundle str>>,
@@ -0,0 +1,99 @@
+use std::fmt;
+
+use crate::analysis::Node;
+
+/// The steps of the resolution of a pattern, as recorded by
+/// [`FluentBundle::format_pattern_with_trace`](crate::bundle::FluentBundle::format_pattern_with_trace).
+///
+/// Its [`Display`](fmt::Display) implementation prints a step per line, indented by
+/// their depth.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResolutionTrace {
+    pub steps: Vec<TraceStep>,
+}
+
+/// A step of the resolution of a pattern.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceStep {
+    /// The number of messages and terms entered to reach this step.
+    pub depth: usize,
+    pub event: TraceEvent,
+}
+
+/// What happened in a [`TraceStep`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TraceEvent {
+    /// A message or a term, or one of their attributes, was entered.
+    Enter(Node),
+    /// A select expression selected a variant.
+    Select {
+        /// The selector expression, such as `$count`.
+        selector: String,
+        /// The formatted value of the selector.
+        value: String,
+        /// The plural category of the value, if it's a number.
+        category: Option<&'static str>,
+        /// The key of the selected variant.
+        key: String,
+        /// Whether the default variant was selected because no other variant matched.
+        default: bool,
+    },
+    /// A function was called.
+    Function {
+        id: String,
+        /// The formatted positional arguments.
+        positional: Vec<String>,
+        /// The formatted named arguments.
+        named: Vec<(String, String)>,
+        /// The formatted result.
+        result: String,
+    },
+}
+
+impl fmt::Display for ResolutionTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            writeln!(f, "{:indent$}{}", "", step.event, indent = step.depth * 2)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Enter(node) => write!(f, "enter {}", node),
+            Self::Select {
+                selector,
+                value,
+                category,
+                key,
+                default,
+            } => {
+                write!(f, "select {} = {:?}", selector, value)?;
+                if let Some(category) = category {
+                    write!(f, " ({})", category)?;
+                }
+                let default = if *default { " (default)" } else { "" };
+                write!(f, " -> [{}]{}", key, default)
+            }
+            Self::Function {
+                id,
+                positional,
+                named,
+                result,
+            } => {
+                let arguments = positional
+                    .iter()
+                    .map(|value| format!("{:?}", value))
+                    .chain(
+                        named
+                            .iter()
+                            .map(|(name, value)| format!("{}: {:?}", name, value)),
+                    )
+                    .collect::<Vec<_>>();
+                write!(f, "call {}({}) = {:?}", id, arguments.join(", "), result)
+            }
+        }
+    }
+}
@@ -0,0 +1,947 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use intl_memoizer::Memoizable;
+use unic_langid::LanguageIdentifier;
+
+use crate::args::FluentArgs;
+use crate::types::FluentValue;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FluentDateTimeStyle {
+    Full,
+    Long,
+    Medium,
+    Short,
+}
+
+impl FromStr for FluentDateTimeStyle {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "full" => Ok(Self::Full),
+            "long" => Ok(Self::Long),
+            "medium" => Ok(Self::Medium),
+            "short" => Ok(Self::Short),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The style of a textual field, such as the weekday.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FluentDateTimeTextStyle {
+    Narrow,
+    Short,
+    Long,
+}
+
+impl FromStr for FluentDateTimeTextStyle {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "narrow" => Ok(Self::Narrow),
+            "short" => Ok(Self::Short),
+            "long" => Ok(Self::Long),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The style of a numeric field, such as the day or the hour.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FluentDateTimeNumericStyle {
+    Numeric,
+    TwoDigit,
+}
+
+impl FromStr for FluentDateTimeNumericStyle {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "numeric" => Ok(Self::Numeric),
+            "2-digit" => Ok(Self::TwoDigit),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FluentDateTimeMonthStyle {
+    Numeric,
+    TwoDigit,
+    Narrow,
+    Short,
+    Long,
+}
+
+impl FromStr for FluentDateTimeMonthStyle {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "numeric" => Ok(Self::Numeric),
+            "2-digit" => Ok(Self::TwoDigit),
+            "narrow" => Ok(Self::Narrow),
+            "short" => Ok(Self::Short),
+            "long" => Ok(Self::Long),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Options of a [`FluentDateTime`], modeled after ECMA-402 `Intl.DateTimeFormat`.
+///
+/// `dateStyle` and `timeStyle` take precedence over the individual fields.
+/// When neither a style nor any field is set, the date is displayed numerically.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct FluentDateTimeOptions {
+    pub date_style: Option<FluentDateTimeStyle>,
+    pub time_style: Option<FluentDateTimeStyle>,
+    pub weekday: Option<FluentDateTimeTextStyle>,
+    pub year: Option<FluentDateTimeNumericStyle>,
+    pub month: Option<FluentDateTimeMonthStyle>,
+    pub day: Option<FluentDateTimeNumericStyle>,
+    pub hour: Option<FluentDateTimeNumericStyle>,
+    pub minute: Option<FluentDateTimeNumericStyle>,
+    pub second: Option<FluentDateTimeNumericStyle>,
+    pub hour12: Option<bool>,
+    /// Either `UTC` or a fixed offset such as `+02:00` or `UTC-5`. Named time zones
+    /// such as `Europe/Paris` aren't supported: the `DATETIME` builtin rejects them,
+    /// and dates built with one are formatted in UTC.
+    pub time_zone: Option<String>,
+}
+
+impl FluentDateTimeOptions {
+    pub fn merge(&mut self, opts: &FluentArgs) {
+        fn set<T: FromStr>(field: &mut Option<T>, value: &str) {
+            if let Ok(value) = value.parse() {
+                *field = Some(value);
+            }
+        }
+
+        for (key, value) in opts.iter() {
+            let FluentValue::String(value) = value else {
+                continue;
+            };
+            match key {
+                "dateStyle" => set(&mut self.date_style, value),
+                "timeStyle" => set(&mut self.time_style, value),
+                "weekday" => set(&mut self.weekday, value),
+                "year" => set(&mut self.year, value),
+                "month" => set(&mut self.month, value),
+                "day" => set(&mut self.day, value),
+                "hour" => set(&mut self.hour, value),
+                "minute" => set(&mut self.minute, value),
+                "second" => set(&mut self.second, value),
+                "hour12" => set(&mut self.hour12, value),
+                "timeZone" => self.time_zone = Some(value.to_string()),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// A point in time, stored as milliseconds since the Unix epoch, together with
+/// the options used to format it.
+///
+/// # Example
+///
+/// ```
+/// use fluent_bundle::types::{FluentDateTime, FluentDateTimeOptions, FluentDateTimeStyle};
+///
+/// let dt: FluentDateTime = "2022-10-27T15:04:05Z".parse().unwrap();
+/// assert_eq!(dt.epoch, 1_666_883_045_000);
+///
+/// let dt = FluentDateTime::new(
+///     dt.epoch,
+///     FluentDateTimeOptions {
+///         date_style: Some(FluentDateTimeStyle::Long),
+///         ..Default::default()
+///     },
+/// );
+/// assert_eq!(dt.as_string(), "October 27, 2022");
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct FluentDateTime {
+    pub epoch: i64,
+    pub options: FluentDateTimeOptions,
+}
+
+impl FluentDateTime {
+    /// The largest supported distance from the Unix epoch, in milliseconds, as in
+    /// ECMAScript: 100,000,000 days. Dates further away are formatted as the nearest
+    /// supported date, and rejected by the `DATETIME` builtin.
+    pub const MAX_EPOCH: i64 = 8_640_000_000_000_000;
+
+    pub const fn new(epoch: i64, options: FluentDateTimeOptions) -> Self {
+        Self { epoch, options }
+    }
+
+    /// Formats the date with the `en-US` conventions.
+    ///
+    /// When formatting through a [`FluentBundle`](crate::bundle::FluentBundle),
+    /// dates are formatted for the bundle's locale with [`DateTimeFormat`] instead.
+    pub fn as_string(&self) -> Cow<'static, str> {
+        DateTimeFormat::new(&LanguageIdentifier::default())
+            .format(self)
+            .into()
+    }
+}
+
+/// The error returned when parsing an ISO 8601 date fails.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseDateTimeError;
+
+impl fmt::Display for ParseDateTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid ISO 8601 date")
+    }
+}
+
+impl std::error::Error for ParseDateTimeError {}
+
+impl FromStr for FluentDateTime {
+    type Err = ParseDateTimeError;
+
+    /// Parses an ISO 8601 date such as `2022-10-27`, `2022-10-27T15:04`
+    /// or `2022-10-27T15:04:05.123+02:00`. Dates without an offset are UTC.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        fn number(input: &str, len: usize) -> Result<i64, ParseDateTimeError> {
+            if input.len() == len && input.bytes().all(|b| b.is_ascii_digit()) {
+                input.parse().map_err(|_| ParseDateTimeError)
+            } else {
+                Err(ParseDateTimeError)
+            }
+        }
+
+        let (date, time) = match input.find(['T', ' ']) {
+            Some(pos) => (&input[..pos], Some(&input[pos + 1..])),
+            None => (input, None),
+        };
+        let mut parts = date.splitn(3, '-');
+        let (Some(year), Some(month), Some(day)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(ParseDateTimeError);
+        };
+        let (year, month, day) = (number(year, 4)?, number(month, 2)?, number(day, 2)?);
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+            return Err(ParseDateTimeError);
+        }
+        let mut epoch = days_from_civil(year, month, day) * MS_PER_DAY;
+
+        if let Some(time) = time {
+            let (time, offset) = match time.find(['Z', '+', '-']) {
+                Some(pos) => (&time[..pos], &time[pos..]),
+                None => (time, "Z"),
+            };
+            let offset = parse_offset(offset).ok_or(ParseDateTimeError)?;
+            let (time, millis) = match time.split_once('.') {
+                Some((time, frac)) if !frac.is_empty() && frac.len() <= 3 => {
+                    let millis = number(frac, frac.len())?;
+                    (time, millis * 10_i64.pow(3 - frac.len() as u32))
+                }
+                Some(_) => return Err(ParseDateTimeError),
+                None => (time, 0),
+            };
+            let mut fields = time.split(':');
+            let hour = number(fields.next().unwrap_or_default(), 2)?;
+            let minute = number(fields.next().ok_or(ParseDateTimeError)?, 2)?;
+            let second = fields.next().map_or(Ok(0), |s| number(s, 2))?;
+            if fields.next().is_some() || hour > 23 || minute > 59 || second > 59 {
+                return Err(ParseDateTimeError);
+            }
+            epoch += ((hour * 60 + minute - offset) * 60 + second) * 1000 + millis;
+        }
+        Ok(Self::new(epoch, FluentDateTimeOptions::default()))
+    }
+}
+
+impl From<SystemTime> for FluentDateTime {
+    fn from(input: SystemTime) -> Self {
+        let epoch = match input.duration_since(UNIX_EPOCH) {
+            Ok(duration) => i64::try_from(duration.as_millis()).unwrap_or(i64::MAX),
+            Err(err) => -i64::try_from(err.duration().as_millis()).unwrap_or(i64::MAX),
+        };
+        let epoch = epoch.clamp(-FluentDateTime::MAX_EPOCH, FluentDateTime::MAX_EPOCH);
+        Self::new(epoch, FluentDateTimeOptions::default())
+    }
+}
+
+impl From<FluentDateTime> for FluentValue<'_> {
+    fn from(input: FluentDateTime) -> Self {
+        FluentValue::DateTime(input)
+    }
+}
+
+const MS_PER_DAY: i64 = 86_400_000;
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// The inverse of [`days_from_civil`], returning `(year, month, day)`.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+/// Parses `Z`, `UTC`, `GMT`, `+02:00`, `-0530`, `UTC+2` and similar into
+/// an offset in minutes, or `None` for unsupported time zones.
+pub(crate) fn parse_offset(input: &str) -> Option<i64> {
+    let input = ["Etc/UTC", "Etc/GMT", "UTC", "GMT", "Z"]
+        .iter()
+        .find_map(|prefix| input.strip_prefix(prefix))
+        .unwrap_or(input);
+    if input.is_empty() {
+        return Some(0);
+    }
+    let (sign, rest) = match input.as_bytes()[0] {
+        b'+' => (1, &input[1..]),
+        b'-' => (-1, &input[1..]),
+        _ => return None,
+    };
+    let digits = rest.replace(':', "");
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = match digits.len() {
+        1 | 2 => (digits.parse::<i64>().ok()?, 0),
+        3 | 4 => {
+            let split = digits.len() - 2;
+            (
+                digits[..split].parse::<i64>().ok()?,
+                digits[split..].parse::<i64>().ok()?,
+            )
+        }
+        _ => return None,
+    };
+    (hours <= 23 && minutes <= 59).then_some(sign * (hours * 60 + minutes))
+}
+
+/// Names and patterns of a locale.
+///
+/// Patterns use the CLDR pattern letters: `y`, `M`, `d`, `E`, `h`, `H`,
+/// `m`, `s`, `a` and `z`, with literal text between single quotes.
+struct DateTimeSymbols {
+    months: [&'static str; 12],
+    months_short: [&'static str; 12],
+    /// Starting on Sunday.
+    weekdays: [&'static str; 7],
+    weekdays_short: [&'static str; 7],
+    day_periods: [&'static str; 2],
+    hour12: bool,
+    /// `full`, `long`, `medium` and `short` date patterns.
+    date_styles: [&'static str; 4],
+    /// Year-month-day, month-day and year-month patterns for numeric months.
+    numeric_dates: [&'static str; 3],
+    /// Year-month-day, month-day and year-month patterns for textual months.
+    text_dates: [&'static str; 3],
+    weekday_separator: &'static str,
+    /// Joins a `full` or `long` date with a time.
+    long_join: &'static str,
+    /// Joins any other date with a time.
+    short_join: &'static str,
+}
+
+const EN_MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const EN_MONTHS_SHORT: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+const EN_WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+const EN_WEEKDAYS_SHORT: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+const EN: DateTimeSymbols = DateTimeSymbols {
+    months: EN_MONTHS,
+    months_short: EN_MONTHS_SHORT,
+    weekdays: EN_WEEKDAYS,
+    weekdays_short: EN_WEEKDAYS_SHORT,
+    day_periods: ["AM", "PM"],
+    hour12: true,
+    date_styles: ["EEEE, MMMM d, y", "MMMM d, y", "MMM d, y", "M/d/yy"],
+    numeric_dates: ["M/d/y", "M/d", "M/y"],
+    text_dates: ["MMMM d, y", "MMMM d", "MMMM y"],
+    weekday_separator: ", ",
+    long_join: "{date} 'at' {time}",
+    short_join: "{date}, {time}",
+};
+
+const EN_GB: DateTimeSymbols = DateTimeSymbols {
+    hour12: false,
+    date_styles: ["EEEE d MMMM y", "d MMMM y", "d MMM y", "dd/MM/y"],
+    numeric_dates: ["dd/MM/y", "dd/MM", "MM/y"],
+    text_dates: ["d MMMM y", "d MMMM", "MMMM y"],
+    weekday_separator: " ",
+    ..EN
+};
+
+const DE: DateTimeSymbols = DateTimeSymbols {
+    months: [
+        "Januar",
+        "Februar",
+        "März",
+        "April",
+        "Mai",
+        "Juni",
+        "Juli",
+        "August",
+        "September",
+        "Oktober",
+        "November",
+        "Dezember",
+    ],
+    months_short: [
+        "Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.", "Nov.",
+        "Dez.",
+    ],
+    weekdays: [
+        "Sonntag",
+        "Montag",
+        "Dienstag",
+        "Mittwoch",
+        "Donnerstag",
+        "Freitag",
+        "Samstag",
+    ],
+    weekdays_short: ["So.", "Mo.", "Di.", "Mi.", "Do.", "Fr.", "Sa."],
+    day_periods: ["AM", "PM"],
+    hour12: false,
+    date_styles: ["EEEE, d. MMMM y", "d. MMMM y", "dd.MM.y", "dd.MM.yy"],
+    numeric_dates: ["d.M.y", "d.M.", "M/y"],
+    text_dates: ["d. MMMM y", "d. MMMM", "MMMM y"],
+    weekday_separator: ", ",
+    long_join: "{date} 'um' {time}",
+    short_join: "{date}, {time}",
+};
+
+const FR: DateTimeSymbols = DateTimeSymbols {
+    months: [
+        "janvier",
+        "février",
+        "mars",
+        "avril",
+        "mai",
+        "juin",
+        "juillet",
+        "août",
+        "septembre",
+        "octobre",
+        "novembre",
+        "décembre",
+    ],
+    months_short: [
+        "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.",
+        "déc.",
+    ],
+    weekdays: [
+        "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+    ],
+    weekdays_short: ["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."],
+    day_periods: ["AM", "PM"],
+    hour12: false,
+    date_styles: ["EEEE d MMMM y", "d MMMM y", "d MMM y", "dd/MM/y"],
+    numeric_dates: ["dd/MM/y", "dd/MM", "MM/y"],
+    text_dates: ["d MMMM y", "d MMMM", "MMMM y"],
+    weekday_separator: " ",
+    long_join: "{date} 'à' {time}",
+    short_join: "{date} {time}",
+};
+
+const ES: DateTimeSymbols = DateTimeSymbols {
+    months: [
+        "enero",
+        "febrero",
+        "marzo",
+        "abril",
+        "mayo",
+        "junio",
+        "julio",
+        "agosto",
+        "septiembre",
+        "octubre",
+        "noviembre",
+        "diciembre",
+    ],
+    months_short: [
+        "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+    ],
+    weekdays: [
+        "domingo",
+        "lunes",
+        "martes",
+        "miércoles",
+        "jueves",
+        "viernes",
+        "sábado",
+    ],
+    weekdays_short: ["dom", "lun", "mar", "mié", "jue", "vie", "sáb"],
+    day_periods: ["a. m.", "p. m."],
+    hour12: false,
+    date_styles: [
+        "EEEE, d 'de' MMMM 'de' y",
+        "d 'de' MMMM 'de' y",
+        "d MMM y",
+        "d/M/yy",
+    ],
+    numeric_dates: ["d/M/y", "d/M", "M/y"],
+    text_dates: ["d 'de' MMMM 'de' y", "d 'de' MMMM", "MMMM 'de' y"],
+    weekday_separator: ", ",
+    long_join: "{date}, {time}",
+    short_join: "{date}, {time}",
+};
+
+const IT: DateTimeSymbols = DateTimeSymbols {
+    months: [
+        "gennaio",
+        "febbraio",
+        "marzo",
+        "aprile",
+        "maggio",
+        "giugno",
+        "luglio",
+        "agosto",
+        "settembre",
+        "ottobre",
+        "novembre",
+        "dicembre",
+    ],
+    months_short: [
+        "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+    ],
+    weekdays: [
+        "domenica",
+        "lunedì",
+        "martedì",
+        "mercoledì",
+        "giovedì",
+        "venerdì",
+        "sabato",
+    ],
+    weekdays_short: ["dom", "lun", "mar", "mer", "gio", "ven", "sab"],
+    day_periods: ["AM", "PM"],
+    hour12: false,
+    date_styles: ["EEEE d MMMM y", "d MMMM y", "d MMM y", "dd/MM/yy"],
+    numeric_dates: ["d/M/y", "d/M", "M/y"],
+    text_dates: ["d MMMM y", "d MMMM", "MMMM y"],
+    weekday_separator: " ",
+    long_join: "{date} {time}",
+    short_join: "{date}, {time}",
+};
+
+fn find_symbols(lang: &LanguageIdentifier) -> &'static DateTimeSymbols {
+    match lang.language.as_str() {
+        "en" => match lang.region.as_ref().map(|r| r.as_str()) {
+            Some("AU" | "GB" | "IE" | "NZ") => &EN_GB,
+            _ => &EN,
+        },
+        "de" => &DE,
+        "es" => &ES,
+        "fr" => &FR,
+        "it" => &IT,
+        _ => &EN,
+    }
+}
+
+/// The calendar fields of a [`FluentDateTime`] in its time zone.
+struct Fields {
+    year: i64,
+    month: usize,
+    day: i64,
+    weekday: usize,
+    hour: i64,
+    minute: i64,
+    second: i64,
+    offset: i64,
+}
+
+impl Fields {
+    fn new(dt: &FluentDateTime) -> Self {
+        let offset = dt
+            .options
+            .time_zone
+            .as_deref()
+            .and_then(parse_offset)
+            .unwrap_or(0);
+        let epoch = dt
+            .epoch
+            .clamp(-FluentDateTime::MAX_EPOCH, FluentDateTime::MAX_EPOCH);
+        let local = epoch + offset * 60_000;
+        let days = local.div_euclid(MS_PER_DAY);
+        let seconds = local.rem_euclid(MS_PER_DAY) / 1000;
+        let (year, month, day) = civil_from_days(days);
+        Self {
+            year,
+            month: month as usize - 1,
+            day,
+            weekday: (days + 4).rem_euclid(7) as usize,
+            hour: seconds / 3600,
+            minute: seconds / 60 % 60,
+            second: seconds % 60,
+            offset,
+        }
+    }
+}
+
+/// A locale-aware formatter for [`FluentDateTime`] values.
+///
+/// Month and weekday names and patterns are available for `en`, `de`, `es`, `fr`
+/// and `it`; other locales fall back to `en-US`. Time zones are limited to UTC and
+/// fixed offsets; dates in other time zones are formatted in UTC.
+///
+/// `DateTimeFormat` is [`Memoizable`], so [`FluentBundle`](crate::bundle::FluentBundle)
+/// constructs it once per locale.
+pub struct DateTimeFormat {
+    symbols: &'static DateTimeSymbols,
+}
+
+impl DateTimeFormat {
+    pub fn new(lang: &LanguageIdentifier) -> Self {
+        Self {
+            symbols: find_symbols(lang),
+        }
+    }
+
+    pub fn format(&self, dt: &FluentDateTime) -> String {
+        let pattern = self.pattern(&dt.options);
+        self.apply(&pattern, &Fields::new(dt))
+    }
+
+    /// Builds the pattern for a set of options.
+    fn pattern(&self, options: &FluentDateTimeOptions) -> String {
+        use FluentDateTimeMonthStyle as Month;
+        use FluentDateTimeNumericStyle as Numeric;
+
+        let symbols = self.symbols;
+        let hour12 = options.hour12.unwrap_or(symbols.hour12);
+
+        if options.date_style.is_some() || options.time_style.is_some() {
+            let style_idx = |style| match style {
+                FluentDateTimeStyle::Full => 0,
+                FluentDateTimeStyle::Long => 1,
+                FluentDateTimeStyle::Medium => 2,
+                FluentDateTimeStyle::Short => 3,
+            };
+            let date = options
+                .date_style
+                .map(|style| symbols.date_styles[style_idx(style)].to_string());
+            let time = options.time_style.map(|style| {
+                let seconds = style_idx(style) <= 2;
+                let mut time = time_pattern(hour12, Numeric::Numeric, true, seconds);
+                match style {
+                    FluentDateTimeStyle::Full => time.push_str(" zzzz"),
+                    FluentDateTimeStyle::Long => time.push_str(" z"),
+                    _ => {}
+                }
+                time
+            });
+            return match (date, time) {
+                (Some(date), Some(time)) => {
+                    let join = match options.date_style {
+                        Some(FluentDateTimeStyle::Full | FluentDateTimeStyle::Long) => {
+                            symbols.long_join
+                        }
+                        _ => symbols.short_join,
+                    };
+                    join.replace("{date}", &date).replace("{time}", &time)
+                }
+                (date, time) => date.or(time).unwrap_or_default(),
+            };
+        }
+
+        let mut options = options.clone();
+        if options.weekday.is_none()
+            && options.year.is_none()
+            && options.month.is_none()
+            && options.day.is_none()
+            && options.hour.is_none()
+            && options.minute.is_none()
+            && options.second.is_none()
+        {
+            options.year = Some(Numeric::Numeric);
+            options.month = Some(Month::Numeric);
+            options.day = Some(Numeric::Numeric);
+        }
+
+        let text = matches!(
+            options.month,
+            Some(Month::Narrow | Month::Short | Month::Long)
+        );
+        let dates = if text {
+            symbols.text_dates
+        } else {
+            symbols.numeric_dates
+        };
+        let mut date = match (options.year, options.month, options.day) {
+            (Some(_), Some(_), Some(_)) | (Some(_), None, Some(_)) => dates[0].to_string(),
+            (None, Some(_), Some(_)) => dates[1].to_string(),
+            (Some(_), Some(_), None) => dates[2].to_string(),
+            (Some(_), None, None) => "y".to_string(),
+            (None, Some(_), None) => if text { "MMMM" } else { "M" }.to_string(),
+            (None, None, Some(_)) => "d".to_string(),
+            (None, None, None) => String::new(),
+        };
+        date = adjust_widths(&date, &options);
+        if let Some(weekday) = options.weekday {
+            let token = match weekday {
+                FluentDateTimeTextStyle::Narrow => "EEEEE",
+                FluentDateTimeTextStyle::Short => "EEE",
+                FluentDateTimeTextStyle::Long => "EEEE",
+            };
+            date = if date.is_empty() {
+                token.to_string()
+            } else {
+                format!("{}'{}'{}", token, symbols.weekday_separator, date)
+            };
+        }
+
+        let time = match (options.hour, options.minute, options.second) {
+            (Some(hour), minute, second) => {
+                time_pattern(hour12, hour, minute.is_some(), second.is_some())
+            }
+            (None, Some(_), Some(_)) => "mm:ss".to_string(),
+            (None, Some(_), None) => "mm".to_string(),
+            (None, None, Some(_)) => "ss".to_string(),
+            (None, None, None) => String::new(),
+        };
+
+        match (date.is_empty(), time.is_empty()) {
+            (false, false) => symbols
+                .short_join
+                .replace("{date}", &date)
+                .replace("{time}", &time),
+            (true, _) => time,
+            (_, true) => date,
+        }
+    }
+
+    /// Formats the fields according to a pattern.
+    fn apply(&self, pattern: &str, fields: &Fields) -> String {
+        let symbols = self.symbols;
+        let mut result = String::new();
+        let mut chars = pattern.chars().peekable();
+        while let Some(ch) = chars.next() {
+            if ch == '\'' {
+                if chars.peek() == Some(&'\'') {
+                    chars.next();
+                    result.push('\'');
+                    continue;
+                }
+                for ch in chars.by_ref() {
+                    if ch == '\'' {
+                        break;
+                    }
+                    result.push(ch);
+                }
+                continue;
+            }
+            if !ch.is_ascii_alphabetic() {
+                result.push(ch);
+                continue;
+            }
+            let mut count = 1;
+            while chars.peek() == Some(&ch) {
+                chars.next();
+                count += 1;
+            }
+            let padded = |value: i64| {
+                if count >= 2 {
+                    format!("{:02}", value)
+                } else {
+                    value.to_string()
+                }
+            };
+            let hour12 = match fields.hour % 12 {
+                0 => 12,
+                hour => hour,
+            };
+            match ch {
+                'y' if count == 2 => {
+                    result.push_str(&format!("{:02}", fields.year.rem_euclid(100)))
+                }
+                'y' => result.push_str(&fields.year.to_string()),
+                'M' => match count {
+                    1 | 2 => result.push_str(&padded(fields.month as i64 + 1)),
+                    3 => result.push_str(symbols.months_short[fields.month]),
+                    4 => result.push_str(symbols.months[fields.month]),
+                    _ => result.extend(narrow(symbols.months[fields.month])),
+                },
+                'd' => result.push_str(&padded(fields.day)),
+                'E' => match count {
+                    1..=3 => result.push_str(symbols.weekdays_short[fields.weekday]),
+                    4 => result.push_str(symbols.weekdays[fields.weekday]),
+                    _ => result.extend(narrow(symbols.weekdays[fields.weekday])),
+                },
+                'h' => result.push_str(&padded(hour12)),
+                'H' => result.push_str(&padded(fields.hour)),
+                'm' => result.push_str(&padded(fields.minute)),
+                's' => result.push_str(&padded(fields.second)),
+                'a' => result.push_str(symbols.day_periods[(fields.hour >= 12) as usize]),
+                'z' => result.push_str(&zone_name(fields.offset, count >= 4)),
+                _ => {}
+            }
+        }
+        result
+    }
+}
+
+fn narrow(name: &str) -> Option<char> {
+    name.chars()
+        .next()
+        .map(|ch| ch.to_uppercase().next().unwrap_or(ch))
+}
+
+fn time_pattern(
+    hour12: bool,
+    hour: FluentDateTimeNumericStyle,
+    minute: bool,
+    second: bool,
+) -> String {
+    let mut pattern = String::from(match (hour12, hour) {
+        (true, FluentDateTimeNumericStyle::Numeric) => "h",
+        (true, FluentDateTimeNumericStyle::TwoDigit) => "hh",
+        (false, _) => "HH",
+    });
+    if minute {
+        pattern.push_str(":mm");
+    }
+    if second {
+        pattern.push_str(":ss");
+    }
+    if hour12 {
+        pattern.push_str(" a");
+    }
+    pattern
+}
+
+/// Adapts the numeric fields of a locale pattern to the requested widths.
+fn adjust_widths(pattern: &str, options: &FluentDateTimeOptions) -> String {
+    let mut result = String::new();
+    let mut chars = pattern.chars().peekable();
+    let mut quoted = false;
+    while let Some(ch) = chars.next() {
+        if ch == '\'' {
+            quoted = !quoted;
+        }
+        if quoted || !matches!(ch, 'y' | 'M' | 'd') {
+            result.push(ch);
+            continue;
+        }
+        let mut count = 1;
+        while chars.peek() == Some(&ch) {
+            chars.next();
+            count += 1;
+        }
+        let two_digit = |style| style == Some(FluentDateTimeNumericStyle::TwoDigit);
+        let count = match ch {
+            'y' if two_digit(options.year) => 2,
+            'y' => 1,
+            'd' if two_digit(options.day) => 2,
+            'M' => match options.month {
+                Some(FluentDateTimeMonthStyle::TwoDigit) => 2,
+                Some(FluentDateTimeMonthStyle::Short) => 3,
+                Some(FluentDateTimeMonthStyle::Long) => 4,
+                Some(FluentDateTimeMonthStyle::Narrow) => 5,
+                _ => count,
+            },
+            _ => count,
+        };
+        result.extend(std::iter::repeat(ch).take(count));
+    }
+    result
+}
+
+fn zone_name(offset: i64, long: bool) -> String {
+    if offset == 0 {
+        return "UTC".to_string();
+    }
+    let sign = if offset < 0 { '-' } else { '+' };
+    let (hours, minutes) = (offset.abs() / 60, offset.abs() % 60);
+    match (long, minutes) {
+        (true, _) => format!("GMT{}{:02}:{:02}", sign, hours, minutes),
+        (false, 0) => format!("GMT{}{}", sign, hours),
+        (false, _) => format!("GMT{}{}:{:02}", sign, hours, minutes),
+    }
+}
+
+impl Memoizable for DateTimeFormat {
+    type Args = ();
+    type Error = &'static str;
+    fn construct(lang: LanguageIdentifier, _args: Self::Args) -> Result<Self, Self::Error> {
+        Ok(Self::new(&lang))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn civil_roundtrip() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(days_from_civil(2000, 2, 29), 11_016);
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+
+    #[test]
+    fn offsets() {
+        assert_eq!(parse_offset("UTC"), Some(0));
+        assert_eq!(parse_offset("+02:00"), Some(120));
+        assert_eq!(parse_offset("-0530"), Some(-330));
+        assert_eq!(parse_offset("GMT+2"), Some(120));
+        assert_eq!(parse_offset("Europe/Berlin"), None);
+    }
+}
@@ -0,0 +1,325 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// An exact decimal number of arbitrary precision.
+///
+/// [`FluentNumber`](super::FluentNumber) stores its value as an `f64`, which cannot
+/// represent large integers or most decimal fractions exactly. When a number
+/// carries a `FluentDecimal`, formatting and plural selection operate on its digits
+/// instead, so that `12345678901234567890` or `0.10` are rendered as written.
+///
+/// Rounding also operates on the digits rather than on a float, so that
+/// `1.005` rounded to two fraction digits gives `1.01`, like in ECMA-402.
+///
+/// # Example
+///
+/// ```
+/// use fluent_bundle::types::FluentDecimal;
+///
+/// let price = FluentDecimal::new(1999, 2);
+/// assert_eq!(price.to_string(), "19.99");
+///
+/// let big: FluentDecimal = "-12345678901234567890.50".parse().unwrap();
+/// assert_eq!(big.to_string(), "-12345678901234567890.50");
+/// assert_eq!(big.scale(), 2);
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct FluentDecimal {
+    pub(crate) negative: bool,
+    /// Integer digits, without leading zeros, `[0]` for zero.
+    pub(crate) int: Vec<u8>,
+    pub(crate) frac: Vec<u8>,
+}
+
+impl FluentDecimal {
+    /// The maximum scale of the decimals created with [`new`](Self::new), like the
+    /// maximum fraction digits of `Intl.NumberFormat`.
+    pub const MAX_SCALE: u32 = 100;
+
+    /// Creates the decimal `mantissa * 10^-scale`, rounded to
+    /// [`MAX_SCALE`](Self::MAX_SCALE) fraction digits.
+    pub fn new(mantissa: i128, scale: u32) -> Self {
+        let digits = mantissa.unsigned_abs().to_string();
+        let max = Self::MAX_SCALE as usize;
+        // Only the digit following the maximum scale matters for rounding.
+        let excess = (scale as usize).saturating_sub(max + 1);
+        let digits = &digits[..digits.len().saturating_sub(excess)];
+        let scale = scale as usize - excess;
+        let (int, frac) = if digits.len() > scale {
+            digits.split_at(digits.len() - scale)
+        } else {
+            ("", digits)
+        };
+        let to_digits = |s: &str| s.bytes().map(|b| b - b'0').collect::<Vec<u8>>();
+        let mut frac_digits = vec![0; scale - frac.len()];
+        frac_digits.extend(to_digits(frac));
+        let mut result = Self {
+            negative: mantissa < 0,
+            int: to_digits(int),
+            frac: frac_digits,
+        };
+        result.normalize_int();
+        if scale > max {
+            result.round(max as isize);
+        }
+        result
+    }
+
+    /// The number of fraction digits, including trailing zeros.
+    pub fn scale(&self) -> usize {
+        self.frac.len()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// The closest `f64` to this decimal.
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    /// The integer part, truncated towards zero.
+    pub(crate) fn trunc(&self) -> Self {
+        Self {
+            negative: self.negative,
+            int: self.int.clone(),
+            frac: vec![],
+        }
+    }
+
+    /// Parses a plain decimal string such as `-12.340`. Exponents are not supported.
+    pub(crate) fn parse(input: &str) -> Option<Self> {
+        let (negative, unsigned) = match input.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, input.strip_prefix('+').unwrap_or(input)),
+        };
+        let (int, frac) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        if int.is_empty() && frac.is_empty() {
+            return None;
+        }
+        let to_digits = |s: &str| {
+            s.bytes()
+                .map(|b| b.is_ascii_digit().then(|| b - b'0'))
+                .collect::<Option<Vec<u8>>>()
+        };
+        let mut result = Self {
+            negative,
+            int: to_digits(int)?,
+            frac: to_digits(frac)?,
+        };
+        result.normalize_int();
+        Some(result)
+    }
+
+    /// Returns `None` for NaN and infinite values.
+    pub(crate) fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+        // `Display` for `f64` prints the shortest representation that
+        // round-trips, and never uses an exponent.
+        Self::parse(&value.to_string())
+    }
+
+    fn normalize_int(&mut self) {
+        let zeros = self.int.iter().take_while(|d| **d == 0).count();
+        self.int.drain(..zeros);
+        if self.int.is_empty() {
+            self.int.push(0);
+        }
+    }
+
+    /// Multiplies the number by `10^n`.
+    pub(crate) fn shift(&mut self, n: usize) {
+        for _ in 0..n {
+            let digit = if self.frac.is_empty() {
+                0
+            } else {
+                self.frac.remove(0)
+            };
+            self.int.push(digit);
+        }
+        self.normalize_int();
+    }
+
+    /// The position of the most significant digit, counted as the number of
+    /// integer digits it implies: `123.4` gives 3, `0.05` gives -1, zero gives 1.
+    pub(crate) fn magnitude(&self) -> isize {
+        if self.int != [0] {
+            return self.int.len() as isize;
+        }
+        match self.frac.iter().position(|d| *d != 0) {
+            Some(pos) => -(pos as isize),
+            None => 1,
+        }
+    }
+
+    /// Rounds half away from zero, keeping `frac_digits` fraction digits.
+    /// A negative value rounds to tens, hundreds and so on.
+    pub(crate) fn round(&mut self, frac_digits: isize) {
+        let point = self.int.len();
+        let mut digits: Vec<u8> = self.int.iter().chain(&self.frac).copied().collect();
+        let keep = point as isize + frac_digits;
+        if keep >= digits.len() as isize {
+            return;
+        }
+        let mut point = point;
+        if keep < 0 {
+            digits.clear();
+        } else {
+            let keep = keep as usize;
+            let round_up = digits[keep] >= 5;
+            digits.truncate(keep);
+            if round_up {
+                let mut carry = true;
+                for digit in digits.iter_mut().rev() {
+                    if *digit == 9 {
+                        *digit = 0;
+                    } else {
+                        *digit += 1;
+                        carry = false;
+                        break;
+                    }
+                }
+                if carry {
+                    digits.insert(0, 1);
+                    point += 1;
+                }
+            }
+        }
+        if digits.len() < point {
+            digits.resize(point, 0);
+        }
+        self.frac = digits.split_off(point);
+        self.int = digits;
+        self.normalize_int();
+    }
+
+    /// Removes trailing zeros from the fraction, keeping at least `min` digits.
+    pub(crate) fn trim_frac(&mut self, min: usize) {
+        while self.frac.len() > min && self.frac.last() == Some(&0) {
+            self.frac.pop();
+        }
+    }
+
+    pub(crate) fn pad_frac(&mut self, min: usize) {
+        if self.frac.len() < min {
+            self.frac.resize(min, 0);
+        }
+    }
+
+    pub(crate) fn pad_int(&mut self, min: usize) {
+        if self.int.len() < min {
+            let mut int = vec![0; min - self.int.len()];
+            int.append(&mut self.int);
+            self.int = int;
+        }
+    }
+}
+
+impl FromStr for FluentDecimal {
+    type Err = ParseDecimalError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::parse(input).ok_or(ParseDecimalError)
+    }
+}
+
+/// The error returned when parsing a [`FluentDecimal`] fails.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseDecimalError;
+
+impl fmt::Display for ParseDecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid decimal number")
+    }
+}
+
+impl std::error::Error for ParseDecimalError {}
+
+macro_rules! from_int {
+    ($($num:ty)+) => {
+        $(
+            impl From<$num> for FluentDecimal {
+                fn from(n: $num) -> Self {
+                    let mut result = Self {
+                        negative: false,
+                        int: vec![],
+                        frac: vec![],
+                    };
+                    let digits = n.to_string();
+                    let digits = match digits.strip_prefix('-') {
+                        Some(rest) => {
+                            result.negative = true;
+                            rest
+                        }
+                        None => digits.as_str(),
+                    };
+                    result.int = digits.bytes().map(|b| b - b'0').collect();
+                    result
+                }
+            }
+        )+
+    };
+}
+
+from_int!(i8 i16 i32 i64 i128 isize);
+from_int!(u8 u16 u32 u64 u128 usize);
+
+impl fmt::Display for FluentDecimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = String::new();
+        if self.negative {
+            out.push('-');
+        }
+        out.extend(self.int.iter().map(|d| (b'0' + d) as char));
+        if !self.frac.is_empty() {
+            out.push('.');
+            out.extend(self.frac.iter().map(|d| (b'0' + d) as char));
+        }
+        f.write_str(&out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FluentDecimal;
+
+    fn round(input: &str, frac_digits: isize) -> String {
+        let mut digits = FluentDecimal::parse(input).unwrap();
+        digits.round(frac_digits);
+        digits.to_string()
+    }
+
+    #[test]
+    fn decimal_digits_round() {
+        assert_eq!(round("1.005", 2), "1.01");
+        assert_eq!(round("1.004", 2), "1.00");
+        assert_eq!(round("9.99", 1), "10.0");
+        assert_eq!(round("-0.5", 0), "-1");
+        assert_eq!(round("1234", -2), "1200");
+        assert_eq!(round("0.04", -1), "0");
+        assert_eq!(round("12.5", 3), "12.5");
+    }
+
+    #[test]
+    fn decimal_new() {
+        assert_eq!(FluentDecimal::new(1999, 2).to_string(), "19.99");
+        assert_eq!(FluentDecimal::new(-5, 3).to_string(), "-0.005");
+        assert_eq!(FluentDecimal::new(120, 0).to_string(), "120");
+        assert_eq!(
+            FluentDecimal::from(u128::MAX).to_string(),
+            u128::MAX.to_string()
+        );
+    }
+
+    #[test]
+    fn decimal_digits_magnitude() {
+        let magnitude = |input| FluentDecimal::parse(input).unwrap().magnitude();
+        assert_eq!(magnitude("123.4"), 3);
+        assert_eq!(magnitude("0.05"), -1);
+        assert_eq!(magnitude("0.5"), 0);
+        assert_eq!(magnitude("0"), 1);
+    }
+}
@@ -8,13 +8,19 @@
 //! for the internationalization APIs to mature, at which point all number
 //! formatting operations will be moved out of Fluent.
 //!
-//! For now, [`FluentValue`] can be a string, a number, or a custom [`FluentType`]
+//! For now, [`FluentValue`] can be a string, a number, a date, or a custom [`FluentType`]
 //! which allows users of the library to implement their own types of values,
-//! such as dates, or more complex structures needed for their bindings.
+//! such as more complex structures needed for their bindings.
+mod datetime;
+mod decimal;
 mod number;
+mod number_format;
 mod plural;
 
+pub use datetime::*;
+pub use decimal::{FluentDecimal, ParseDecimalError};
 pub use number::*;
+pub use number_format::NumberFormat;
 use plural::PluralRules;
 
 use std::any::Any;
@@ -28,6 +34,25 @@ use crate::memoizer::MemoizerKind;
 use crate::resolver::Scope;
 use crate::resource::FluentResource;
 
+/// Returns the plural category of `number` in the locale of the bundle.
+pub(crate) fn plural_category<R, M>(
+    number: &FluentNumber,
+    scope: &Scope<R, M>,
+) -> Option<PluralCategory>
+where
+    M: MemoizerKind,
+{
+    let r#type = match number.options.r#type {
+        FluentNumberType::Cardinal => PluralRuleType::CARDINAL,
+        FluentNumberType::Ordinal => PluralRuleType::ORDINAL,
+    };
+    scope
+        .bundle
+        .intls
+        .with_try_get_threadsafe::<PluralRules, _, _>((r#type,), |pr| pr.0.select(number).ok())
+        .unwrap()
+}
+
 /// Custom types can implement the [`FluentType`] trait in order to generate a string
 /// value for use in the message generation process.
 pub trait FluentType: fmt::Debug + AnyEq + 'static {
@@ -77,6 +102,7 @@ impl<T: Any + PartialEq> AnyEq for T {
 pub enum FluentValue<'source> {
     String(Cow<'source, str>),
     Number(FluentNumber),
+    DateTime(FluentDateTime),
     Custom(Box<dyn FluentType + Send>),
     None,
     Error,
@@ -87,6 +113,7 @@ impl PartialEq for FluentValue<'_> {
         match (self, other) {
             (FluentValue::String(s), FluentValue::String(s2)) => s == s2,
             (FluentValue::Number(s), FluentValue::Number(s2)) => s == s2,
+            (FluentValue::DateTime(s), FluentValue::DateTime(s2)) => s == s2,
             (FluentValue::Custom(s), FluentValue::Custom(s2)) => s == s2,
             _ => false,
         }
@@ -98,6 +125,7 @@ impl Clone for FluentValue<'_> {
         match self {
             FluentValue::String(s) => FluentValue::String(s.clone()),
             FluentValue::Number(s) => FluentValue::Number(s.clone()),
+            FluentValue::DateTime(s) => FluentValue::DateTime(s.clone()),
             FluentValue::Custom(s) => {
                 let new_value: Box<dyn FluentType + Send> = s.duplicate();
                 FluentValue::Custom(new_value)
@@ -185,6 +213,7 @@ impl<'source> FluentValue<'source> {
         match (self, other) {
             (FluentValue::String(a), FluentValue::String(b)) => a == b,
             (FluentValue::Number(a), FluentValue::Number(b)) => a == b,
+            (FluentValue::DateTime(a), FluentValue::DateTime(b)) => a == b,
             (FluentValue::String(a), FluentValue::Number(b)) => {
                 let cat = match a.as_ref() {
                     "zero" => PluralCategory::ZERO,
@@ -197,17 +226,7 @@ impl<'source> FluentValue<'source> {
                 };
                 // This string matches a plural rule keyword. Check if the number
                 // matches the plural rule category.
-                let r#type = match b.options.r#type {
-                    FluentNumberType::Cardinal => PluralRuleType::CARDINAL,
-                    FluentNumberType::Ordinal => PluralRuleType::ORDINAL,
-                };
-                scope
-                    .bundle
-                    .intls
-                    .with_try_get_threadsafe::<PluralRules, _, _>((r#type,), |pr| {
-                        pr.0.select(b) == Ok(cat)
-                    })
-                    .unwrap()
+                plural_category(b, scope) == Some(cat)
             }
             _ => false,
         }
@@ -227,7 +246,8 @@ impl<'source> FluentValue<'source> {
         }
         match self {
             FluentValue::String(s) => w.write_str(s),
-            FluentValue::Number(n) => w.write_str(&n.as_string()),
+            FluentValue::Number(n) => w.write_str(&format_number(n, scope)),
+            FluentValue::DateTime(d) => w.write_str(&format_datetime(d, scope)),
             FluentValue::Custom(s) => w.write_str(&scope.bundle.intls.stringify_value(&**s)),
             FluentValue::Error => Ok(()),
             FluentValue::None => Ok(()),
@@ -249,7 +269,8 @@ impl<'source> FluentValue<'source> {
         }
         match self {
             FluentValue::String(s) => s.clone(),
-            FluentValue::Number(n) => n.as_string(),
+            FluentValue::Number(n) => format_number(n, scope).into(),
+            FluentValue::DateTime(d) => format_datetime(d, scope).into(),
             FluentValue::Custom(s) => scope.bundle.intls.stringify_value(&**s),
             FluentValue::Error => "".into(),
             FluentValue::None => "".into(),
@@ -271,7 +292,8 @@ impl<'source> FluentValue<'source> {
         }
         match self {
             FluentValue::String(s) => s,
-            FluentValue::Number(n) => n.as_string(),
+            FluentValue::Number(n) => format_number(&n, scope).into(),
+            FluentValue::DateTime(d) => format_datetime(&d, scope).into(),
             FluentValue::Custom(s) => scope.bundle.intls.stringify_value(s.as_ref()),
             FluentValue::Error => "".into(),
             FluentValue::None => "".into(),
@@ -282,6 +304,7 @@ impl<'source> FluentValue<'source> {
         match self {
             FluentValue::String(str) => FluentValue::String(Cow::from(str.to_string())),
             FluentValue::Number(s) => FluentValue::Number(s.clone()),
+            FluentValue::DateTime(s) => FluentValue::DateTime(s.clone()),
             FluentValue::Custom(s) => FluentValue::Custom(s.duplicate()),
             FluentValue::Error => FluentValue::Error,
             FluentValue::None => FluentValue::None,
@@ -289,6 +312,30 @@ impl<'source> FluentValue<'source> {
     }
 }
 
+/// Formats a number for the bundle's locale.
+fn format_number<R, M>(number: &FluentNumber, scope: &Scope<R, M>) -> String
+where
+    M: MemoizerKind,
+{
+    scope
+        .bundle
+        .intls
+        .with_try_get_threadsafe::<NumberFormat, _, _>((), |nf| nf.format(number))
+        .unwrap_or_else(|_| number.as_string().into_owned())
+}
+
+/// Formats a date for the bundle's locale.
+fn format_datetime<R, M>(dt: &FluentDateTime, scope: &Scope<R, M>) -> String
+where
+    M: MemoizerKind,
+{
+    scope
+        .bundle
+        .intls
+        .with_try_get_threadsafe::<DateTimeFormat, _, _>((), |dtf| dtf.format(dt))
+        .unwrap_or_else(|_| dt.as_string().into_owned())
+}
+
 impl From<String> for FluentValue<'_> {
     fn from(s: String) -> Self {
         FluentValue::String(s.into())
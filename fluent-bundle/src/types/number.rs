@@ -6,7 +6,7 @@ use std::str::FromStr;
 use intl_pluralrules::operands::PluralOperands;
 
 use crate::args::FluentArgs;
-use crate::types::FluentValue;
+use crate::types::{FluentDecimal, FluentValue, NumberFormat};
 
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
 pub enum FluentNumberType {
@@ -134,29 +134,86 @@ impl FluentNumberOptions {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct FluentNumber {
     pub value: f64,
     pub options: FluentNumberOptions,
+    /// The exact value, when it cannot be represented by `value`. It takes
+    /// precedence over `value` in formatting and plural selection.
+    decimal: Option<FluentDecimal>,
 }
 
 impl FluentNumber {
     pub const fn new(value: f64, options: FluentNumberOptions) -> Self {
-        Self { value, options }
+        Self {
+            value,
+            options,
+            decimal: None,
+        }
+    }
+
+    /// Creates a number which is formatted and selected exactly as `decimal`.
+    /// Unless set in `options`, the minimum fraction digits default to the
+    /// scale of `decimal`, so that trailing zeros are preserved.
+    ///
+    /// ```
+    /// use fluent_bundle::types::{FluentDecimal, FluentNumber, FluentNumberOptions};
+    ///
+    /// let decimal: FluentDecimal = "12345678901234567890.25".parse().unwrap();
+    /// let num = FluentNumber::from_decimal(decimal, FluentNumberOptions::default());
+    /// assert_eq!(num.as_string(), "12345678901234567890.25");
+    /// ```
+    pub fn from_decimal(decimal: FluentDecimal, options: FluentNumberOptions) -> Self {
+        let value = decimal.to_f64();
+        let options = FluentNumberOptions {
+            minimum_fraction_digits: options.minimum_fraction_digits.or(Some(decimal.scale())),
+            ..options
+        };
+        Self {
+            value,
+            options,
+            decimal: Some(decimal),
+        }
     }
 
+    /// Formats the number without any locale data: `.` is used as the decimal
+    /// separator and digits are never grouped. All other options are applied.
+    ///
+    /// When formatting through a [`FluentBundle`](crate::bundle::FluentBundle),
+    /// numbers are formatted for the bundle's locale with [`NumberFormat`] instead.
     pub fn as_string(&self) -> Cow<'static, str> {
-        let mut val = self.value.to_string();
-        if let Some(minfd) = self.options.minimum_fraction_digits {
-            if let Some(pos) = val.find('.') {
-                let frac_num = val.len() - pos - 1;
-                let missing = minfd.saturating_sub(frac_num);
-                val = format!("{}{}", val, "0".repeat(missing));
-            } else {
-                val = format!("{}.{}", val, "0".repeat(minfd));
-            }
+        NumberFormat::neutral().format(self).into()
+    }
+
+    /// The exact value of the number, when it cannot be represented by `value`. It
+    /// takes precedence over `value` in formatting and plural selection.
+    pub fn decimal(&self) -> Option<&FluentDecimal> {
+        self.decimal.as_ref()
+    }
+
+    pub(crate) fn to_digits(&self) -> Option<FluentDecimal> {
+        match &self.decimal {
+            Some(decimal) => Some(decimal.clone()),
+            None => FluentDecimal::from_f64(self.value),
         }
-        val.into()
+    }
+}
+
+impl PartialEq for FluentNumber {
+    fn eq(&self, other: &Self) -> bool {
+        if self.options != other.options {
+            return false;
+        }
+        if self.decimal.is_none() && other.decimal.is_none() {
+            return self.value == other.value;
+        }
+        let normalized = |num: &Self| {
+            num.to_digits().map(|mut digits| {
+                digits.trim_frac(0);
+                digits
+            })
+        };
+        normalized(self) == normalized(other)
     }
 }
 
@@ -170,7 +227,19 @@ impl FromStr for FluentNumber {
                 minimum_fraction_digits: mfd,
                 ..Default::default()
             };
-            Self::new(n, opts)
+            let mut number = Self::new(n, opts);
+            // Keep the digits when the float doesn't represent them exactly.
+            if let Some(mut decimal) = FluentDecimal::parse(input) {
+                decimal.trim_frac(0);
+                let mut approx = number.to_digits();
+                if let Some(approx) = approx.as_mut() {
+                    approx.trim_frac(0);
+                }
+                if approx.as_ref() != Some(&decimal) {
+                    number.decimal = Some(decimal);
+                }
+            }
+            number
         })
     }
 }
@@ -183,30 +252,14 @@ impl From<FluentNumber> for FluentValue<'_> {
 
 macro_rules! from_num {
     ($num:ty) => {
-        impl From<$num> for FluentNumber {
-            fn from(n: $num) -> Self {
-                Self {
-                    value: n as f64,
-                    options: FluentNumberOptions::default(),
-                }
-            }
-        }
         impl From<&$num> for FluentNumber {
             fn from(n: &$num) -> Self {
-                Self {
-                    value: *n as f64,
-                    options: FluentNumberOptions::default(),
-                }
-            }
-        }
-        impl From<FluentNumber> for $num {
-            fn from(input: FluentNumber) -> Self {
-                input.value as $num
+                (*n).into()
             }
         }
         impl From<&FluentNumber> for $num {
             fn from(input: &FluentNumber) -> Self {
-                input.value as $num
+                input.clone().into()
             }
         }
         impl From<$num> for FluentValue<'_> {
@@ -220,31 +273,79 @@ macro_rules! from_num {
             }
         }
     };
+}
+
+/// Integers which don't fit into an `f64` are kept as an exact [`FluentDecimal`].
+macro_rules! from_int {
+    ($($num:ty)+) => {
+        $(
+            impl From<$num> for FluentNumber {
+                fn from(n: $num) -> Self {
+                    // Integers up to 2^53 are exactly representable.
+                    let value = n as f64;
+                    Self {
+                        value,
+                        options: FluentNumberOptions::default(),
+                        decimal: (value.abs() >= 9_007_199_254_740_992.0).then(|| n.into()),
+                    }
+                }
+            }
+            impl From<FluentNumber> for $num {
+                fn from(input: FluentNumber) -> Self {
+                    input
+                        .decimal
+                        .and_then(|decimal| decimal.trunc().to_string().parse().ok())
+                        .unwrap_or(input.value as $num)
+                }
+            }
+            from_num!($num);
+        )+
+    };
+}
+
+macro_rules! from_float {
     ($($num:ty)+) => {
-        $(from_num!($num);)+
+        $(
+            impl From<$num> for FluentNumber {
+                fn from(n: $num) -> Self {
+                    Self::new(n as f64, FluentNumberOptions::default())
+                }
+            }
+            impl From<FluentNumber> for $num {
+                fn from(input: FluentNumber) -> Self {
+                    input.value as $num
+                }
+            }
+            from_num!($num);
+        )+
     };
 }
 
 impl From<&FluentNumber> for PluralOperands {
     fn from(input: &FluentNumber) -> Self {
-        let mut operands: Self = input
-            .value
-            .try_into()
-            .expect("Failed to generate operands out of FluentNumber");
-        if let Some(mfd) = input.options.minimum_fraction_digits {
-            if mfd > operands.v {
-                operands.f *= 10_u64.pow(mfd as u32 - operands.v as u32);
-                operands.v = mfd;
+        // Select on the same rounded and padded digits that formatting displays.
+        // Percent values are selected on the number itself rather than on
+        // its scaled representation.
+        let style = match input.options.style {
+            FluentNumberStyle::Percent => FluentNumberStyle::Decimal,
+            style => style,
+        };
+        if let Some(mut digits) = input.to_digits() {
+            digits.apply_options(input, style);
+            if let Ok(operands) = Self::try_from(digits.to_string().as_str()) {
+                return operands;
             }
         }
-        // XXX: Add support for other options.
-        operands
+        input
+            .value
+            .try_into()
+            .expect("Failed to generate operands out of FluentNumber")
     }
 }
 
-from_num!(i8 i16 i32 i64 i128 isize);
-from_num!(u8 u16 u32 u64 u128 usize);
-from_num!(f32 f64);
+from_int!(i8 i16 i32 i64 i128 isize);
+from_int!(u8 u16 u32 u64 u128 usize);
+from_float!(f32 f64);
 
 #[cfg(test)]
 mod tests {
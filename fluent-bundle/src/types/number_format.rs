@@ -0,0 +1,461 @@
+use std::fmt;
+
+use intl_memoizer::Memoizable;
+use intl_pluralrules::operands::PluralOperands;
+use intl_pluralrules::{PluralCategory, PluralRuleType, PluralRules};
+use unic_langid::LanguageIdentifier;
+
+use super::decimal::FluentDecimal;
+use super::number::{FluentNumber, FluentNumberCurrencyDisplayStyle, FluentNumberStyle};
+
+/// Separators and patterns used to render numbers in a given locale.
+///
+/// In `percent` and `currency` patterns, `#` stands for the formatted
+/// digits and `¤` for the currency symbol, code or name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct NumberSymbols {
+    decimal: &'static str,
+    group: &'static str,
+    minus: &'static str,
+    /// Size of the group closest to the decimal separator.
+    primary_grouping: usize,
+    /// Size of every other group.
+    secondary_grouping: usize,
+    /// Grouping is only used when the integer part has at least
+    /// `primary_grouping + min_grouping` digits.
+    min_grouping: usize,
+    percent: &'static str,
+    currency: &'static str,
+}
+
+/// The maximum significant and integer digits, as in `Intl.NumberFormat`.
+const MAX_SIGNIFICANT_DIGITS: usize = 21;
+
+const NBSP: &str = "\u{a0}";
+const NNBSP: &str = "\u{202f}";
+
+const EN: NumberSymbols = NumberSymbols {
+    decimal: ".",
+    group: ",",
+    minus: "-",
+    primary_grouping: 3,
+    secondary_grouping: 3,
+    min_grouping: 1,
+    percent: "#%",
+    currency: "¤#",
+};
+
+const INDIAN: NumberSymbols = NumberSymbols {
+    secondary_grouping: 2,
+    ..EN
+};
+
+const DE: NumberSymbols = NumberSymbols {
+    decimal: ",",
+    group: ".",
+    percent: "#\u{a0}%",
+    currency: "#\u{a0}¤",
+    ..EN
+};
+
+const FR: NumberSymbols = NumberSymbols {
+    decimal: ",",
+    group: NNBSP,
+    percent: "#\u{202f}%",
+    currency: "#\u{a0}¤",
+    ..EN
+};
+
+const ES: NumberSymbols = NumberSymbols {
+    min_grouping: 2,
+    ..DE
+};
+
+const IT: NumberSymbols = NumberSymbols {
+    percent: "#%",
+    ..DE
+};
+
+const NL: NumberSymbols = NumberSymbols {
+    percent: "#%",
+    currency: "¤\u{a0}#",
+    ..DE
+};
+
+const RU: NumberSymbols = NumberSymbols { group: NBSP, ..DE };
+
+const PL: NumberSymbols = NumberSymbols {
+    min_grouping: 2,
+    percent: "#%",
+    ..RU
+};
+
+const SV: NumberSymbols = NumberSymbols {
+    minus: "\u{2212}",
+    ..RU
+};
+
+const TR: NumberSymbols = NumberSymbols {
+    percent: "%#",
+    currency: "¤#",
+    ..DE
+};
+
+/// Locale data, looked up by `language-REGION` first and by `language` second.
+/// Locales missing from the table use the `en` conventions.
+const LOCALES: &[(&str, NumberSymbols)] = &[
+    ("cs", RU),
+    ("da", NumberSymbols { group: ".", ..RU }),
+    ("de", DE),
+    ("de-AT", NumberSymbols { group: NBSP, ..DE }),
+    (
+        "de-CH",
+        NumberSymbols {
+            decimal: ".",
+            group: "\u{2019}",
+            currency: "¤\u{a0}#",
+            ..DE
+        },
+    ),
+    ("en", EN),
+    ("en-IN", INDIAN),
+    ("es", ES),
+    ("es-MX", EN),
+    ("es-US", EN),
+    ("fi", SV),
+    ("fr", FR),
+    ("hi", INDIAN),
+    ("it", IT),
+    ("ja", EN),
+    ("ko", EN),
+    ("nb", SV),
+    ("nl", NL),
+    ("pl", PL),
+    (
+        "pt",
+        NumberSymbols {
+            percent: "#%",
+            currency: "¤\u{a0}#",
+            ..DE
+        },
+    ),
+    (
+        "pt-PT",
+        NumberSymbols {
+            group: NBSP,
+            min_grouping: 2,
+            percent: "#%",
+            ..DE
+        },
+    ),
+    ("ru", RU),
+    ("sv", SV),
+    ("tr", TR),
+    ("uk", RU),
+    ("zh", EN),
+];
+
+fn find_symbols(lang: &LanguageIdentifier) -> NumberSymbols {
+    let language = lang.language.as_str();
+    let find = |key: &str| {
+        LOCALES
+            .iter()
+            .find(|(id, _)| *id == key)
+            .map(|(_, symbols)| *symbols)
+    };
+    lang.region
+        .and_then(|region| find(&format!("{}-{}", language, region.as_str())))
+        .or_else(|| find(language))
+        .unwrap_or(EN)
+}
+
+/// Returns `(symbol, fraction digits)` for an ISO 4217 currency code.
+fn currency_data(code: &str) -> (Option<&'static str>, usize) {
+    match code {
+        "AUD" => (Some("A$"), 2),
+        "BRL" => (Some("R$"), 2),
+        "CAD" => (Some("CA$"), 2),
+        "CNY" => (Some("CN¥"), 2),
+        "EUR" => (Some("€"), 2),
+        "GBP" => (Some("£"), 2),
+        "ILS" => (Some("₪"), 2),
+        "INR" => (Some("₹"), 2),
+        "JPY" => (Some("¥"), 0),
+        "KRW" => (Some("₩"), 0),
+        "MXN" => (Some("MX$"), 2),
+        "USD" => (Some("$"), 2),
+        "VND" => (Some("₫"), 0),
+        "CLP" | "ISK" | "UGX" => (None, 0),
+        "BHD" | "JOD" | "KWD" | "OMR" | "TND" => (None, 3),
+        _ => (None, 2),
+    }
+}
+
+/// English display names, for the `one` plural category and the others.
+fn currency_name(code: &str, category: PluralCategory) -> Option<&'static str> {
+    let (one, other) = match code {
+        "AUD" => ("Australian dollar", "Australian dollars"),
+        "BRL" => ("Brazilian real", "Brazilian reals"),
+        "CAD" => ("Canadian dollar", "Canadian dollars"),
+        "CHF" => ("Swiss franc", "Swiss francs"),
+        "CNY" => ("Chinese yuan", "Chinese yuan"),
+        "EUR" => ("euro", "euros"),
+        "GBP" => ("British pound", "British pounds"),
+        "INR" => ("Indian rupee", "Indian rupees"),
+        "JPY" => ("Japanese yen", "Japanese yen"),
+        "KRW" => ("South Korean won", "South Korean won"),
+        "MXN" => ("Mexican peso", "Mexican pesos"),
+        "PLN" => ("Polish zloty", "Polish zlotys"),
+        "USD" => ("US dollar", "US dollars"),
+        _ => return None,
+    };
+    Some(match category {
+        PluralCategory::ONE => one,
+        _ => other,
+    })
+}
+
+/// The plural rules selecting currency names, for the locales which have names.
+#[derive(Clone)]
+struct CurrencyNames {
+    lang: LanguageIdentifier,
+    plurals: PluralRules,
+}
+
+impl CurrencyNames {
+    fn new(lang: &LanguageIdentifier) -> Option<Self> {
+        // Names are only available in English.
+        if lang.language.as_str() != "en" {
+            return None;
+        }
+        let lang = LanguageIdentifier::from_parts(lang.language, None, None, &[]);
+        let plurals = PluralRules::create(lang.clone(), PluralRuleType::CARDINAL).ok()?;
+        Some(Self { lang, plurals })
+    }
+
+    fn get(&self, code: &str, digits: &FluentDecimal) -> Option<&'static str> {
+        let operands = PluralOperands::try_from(digits.to_string().as_str()).ok()?;
+        currency_name(code, self.plurals.select(operands).ok()?)
+    }
+}
+
+impl fmt::Debug for CurrencyNames {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CurrencyNames")
+            .field("lang", &self.lang)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for CurrencyNames {
+    fn eq(&self, other: &Self) -> bool {
+        self.lang == other.lang
+    }
+}
+
+impl Eq for CurrencyNames {}
+
+impl FluentDecimal {
+    /// Applies the digit options of `number`, using the defaults of `style`. The options
+    /// are clamped to the ranges of `Intl.NumberFormat`.
+    pub(crate) fn apply_options(&mut self, number: &FluentNumber, style: FluentNumberStyle) {
+        let options = &number.options;
+        if options.minimum_significant_digits.is_some()
+            || options.maximum_significant_digits.is_some()
+        {
+            let min_sd = options
+                .minimum_significant_digits
+                .unwrap_or(1)
+                .clamp(1, MAX_SIGNIFICANT_DIGITS);
+            let max_sd = options
+                .maximum_significant_digits
+                .unwrap_or(MAX_SIGNIFICANT_DIGITS)
+                .clamp(min_sd, MAX_SIGNIFICANT_DIGITS);
+            self.round(max_sd as isize - self.magnitude());
+            self.trim_frac(0);
+            let min_frac = min_sd as isize - self.magnitude();
+            self.pad_frac(min_frac.max(0) as usize);
+        } else {
+            let (default_min, default_max) = match style {
+                FluentNumberStyle::Decimal => (0, 3),
+                FluentNumberStyle::Percent => (0, 0),
+                FluentNumberStyle::Currency => {
+                    let digits = options
+                        .currency
+                        .as_deref()
+                        .map_or(2, |code| currency_data(code).1);
+                    (digits, digits)
+                }
+            };
+            let max_scale = FluentDecimal::MAX_SCALE as usize;
+            let min_fd = options
+                .minimum_fraction_digits
+                .unwrap_or_else(|| match options.maximum_fraction_digits {
+                    Some(max) => default_min.min(max),
+                    None => default_min,
+                })
+                .min(max_scale);
+            let max_fd = options
+                .maximum_fraction_digits
+                .unwrap_or(default_max)
+                .clamp(min_fd, max_scale);
+            self.round(max_fd as isize);
+            self.trim_frac(min_fd);
+            self.pad_frac(min_fd);
+        }
+        if let Some(min_id) = options.minimum_integer_digits {
+            self.pad_int(min_id.min(MAX_SIGNIFICANT_DIGITS));
+        }
+    }
+
+    fn write_int(&self, out: &mut String, symbols: Option<&NumberSymbols>) {
+        let len = self.int.len();
+        let symbols = symbols
+            .filter(|s| s.primary_grouping > 0 && len >= s.primary_grouping + s.min_grouping);
+        for (idx, digit) in self.int.iter().enumerate() {
+            if let Some(symbols) = symbols {
+                let remaining = len - idx;
+                if idx > 0 && remaining >= symbols.primary_grouping {
+                    let beyond = remaining - symbols.primary_grouping;
+                    if beyond % symbols.secondary_grouping == 0 {
+                        out.push_str(symbols.group);
+                    }
+                }
+            }
+            out.push((b'0' + digit) as char);
+        }
+    }
+}
+
+/// A locale-aware formatter for [`FluentNumber`] values.
+///
+/// It honours the [`FluentNumberOptions`](super::FluentNumberOptions) of each number:
+/// digit limits, grouping, and the percent and currency styles. The locale
+/// data covers the decimal and grouping separators, grouping sizes and the
+/// placement of the percent sign and currency for a set of common locales;
+/// other locales fall back to `en` conventions. Currency names are only
+/// available in English, where the plural rules select their form; other
+/// locales, and [`FluentNumber::as_string`], display the currency code instead.
+///
+/// `NumberFormat` is [`Memoizable`], so [`FluentBundle`](crate::bundle::FluentBundle)
+/// constructs it once per locale.
+///
+/// # Example
+///
+/// ```
+/// use fluent_bundle::types::{FluentNumber, FluentNumberOptions, NumberFormat};
+/// use unic_langid::langid;
+///
+/// let nf = NumberFormat::new(&langid!("de-DE"));
+/// let num = FluentNumber::new(1234.5, FluentNumberOptions::default());
+/// assert_eq!(nf.format(&num), "1.234,5");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberFormat {
+    symbols: NumberSymbols,
+    currency_names: Option<CurrencyNames>,
+}
+
+impl NumberFormat {
+    pub fn new(lang: &LanguageIdentifier) -> Self {
+        Self {
+            symbols: find_symbols(lang),
+            currency_names: CurrencyNames::new(lang),
+        }
+    }
+
+    /// The locale-independent formatter used by [`FluentNumber::as_string`].
+    /// It uses `.` as the decimal separator and never groups digits.
+    pub(crate) fn neutral() -> Self {
+        Self {
+            symbols: NumberSymbols {
+                primary_grouping: 0,
+                ..EN
+            },
+            currency_names: None,
+        }
+    }
+
+    pub fn format(&self, number: &FluentNumber) -> String {
+        let options = &number.options;
+        let currency = options.currency.as_deref().map(str::to_ascii_uppercase);
+        let style = match (options.style, &currency) {
+            // A currency style without a currency has nothing to display.
+            (FluentNumberStyle::Currency, None) => FluentNumberStyle::Decimal,
+            (style, _) => style,
+        };
+
+        let Some(mut digits) = number.to_digits() else {
+            let value = number.value;
+            return if value.is_nan() {
+                "NaN".to_string()
+            } else if value.is_sign_negative() {
+                format!("{}∞", self.symbols.minus)
+            } else {
+                "∞".to_string()
+            };
+        };
+        if style == FluentNumberStyle::Percent {
+            digits.shift(2);
+        }
+        digits.apply_options(number, style);
+
+        let mut num = String::new();
+        digits.write_int(&mut num, options.use_grouping.then_some(&self.symbols));
+        if !digits.frac.is_empty() {
+            num.push_str(self.symbols.decimal);
+            num.extend(digits.frac.iter().map(|d| (b'0' + d) as char));
+        }
+
+        let mut result = String::new();
+        if digits.negative {
+            result.push_str(self.symbols.minus);
+        }
+        match (style, currency) {
+            (FluentNumberStyle::Currency, Some(code)) => {
+                let display = match options.currency_display {
+                    FluentNumberCurrencyDisplayStyle::Name => self
+                        .currency_names
+                        .as_ref()
+                        .and_then(|names| names.get(&code, &digits))
+                        .map(str::to_string),
+                    FluentNumberCurrencyDisplayStyle::Symbol => {
+                        currency_data(&code).0.map(str::to_string)
+                    }
+                    _ => None,
+                };
+                if options.currency_display == FluentNumberCurrencyDisplayStyle::Name {
+                    let name = display.unwrap_or(code);
+                    result.push_str(&num);
+                    result.push(' ');
+                    result.push_str(&name);
+                } else {
+                    let is_symbol = display.is_some();
+                    let currency = display.unwrap_or(code);
+                    let mut pattern = self.symbols.currency.to_string();
+                    // Alphabetic currencies are separated from the digits,
+                    // like "EUR 10.00" or "10.00 EUR".
+                    if !is_symbol || currency.chars().all(char::is_alphabetic) {
+                        pattern = pattern
+                            .replace("¤#", &format!("¤{}#", NBSP))
+                            .replace("#¤", &format!("#{}¤", NBSP));
+                    }
+                    result.push_str(&pattern.replace('¤', &currency).replace('#', &num));
+                }
+            }
+            (FluentNumberStyle::Percent, _) => {
+                result.push_str(&self.symbols.percent.replace('#', &num));
+            }
+            _ => result.push_str(&num),
+        }
+        result
+    }
+}
+
+impl Memoizable for NumberFormat {
+    type Args = ();
+    type Error = &'static str;
+    fn construct(lang: LanguageIdentifier, _args: Self::Args) -> Result<Self, Self::Error> {
+        Ok(Self::new(&lang))
+    }
+}
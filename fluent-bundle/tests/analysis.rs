@@ -0,0 +1,164 @@
+use fluent_bundle::analysis::{Node, ReferenceGraph};
+use fluent_bundle::FluentResource;
+
+fn resource(source: &str) -> FluentResource {
+    FluentResource::try_new(source.to_string()).expect("Failed to parse an FTL string.")
+}
+
+fn attribute(entry: Node, name: &str) -> Node {
+    Node::attribute(entry, name)
+}
+
+#[test]
+fn references_across_resources() {
+    let brand = resource("-brand = Firefox\n    .gender = masculine\n");
+    let messages = resource(
+        r#"
+about = About { -brand }
+update = { -brand.gender ->
+        [masculine] { about } updated
+       *[other] { NUMBER(about.title, minimumFractionDigits: 1) }
+    }
+"#,
+    );
+    let graph = ReferenceGraph::new([&brand, &messages]);
+
+    assert_eq!(
+        graph
+            .references(&Node::message("update"))
+            .collect::<Vec<_>>(),
+        vec![
+            &Node::message("about"),
+            &attribute(Node::message("about"), "title"),
+            &attribute(Node::term("brand"), "gender"),
+        ]
+    );
+    assert_eq!(
+        graph.referrers(&Node::term("brand")).collect::<Vec<_>>(),
+        vec![&Node::message("about")]
+    );
+    assert_eq!(
+        graph
+            .functions(&Node::message("update"))
+            .collect::<Vec<_>>(),
+        vec!["NUMBER"]
+    );
+    assert!(graph.contains(&attribute(Node::term("brand"), "gender")));
+    assert_eq!(graph.nodes().count(), 4);
+}
+
+#[test]
+fn dangling_references() {
+    let res = resource(
+        r#"
+title = { -brand-name } { menu }
+    .tooltip = { title.label }
+menu =
+    .label = Menu
+"#,
+    );
+    let graph = ReferenceGraph::new([&res]);
+
+    assert_eq!(
+        graph.dangling(),
+        vec![
+            (&Node::message("title"), &Node::message("menu")),
+            (&Node::message("title"), &Node::term("brand-name")),
+            (
+                &attribute(Node::message("title"), "tooltip"),
+                &attribute(Node::message("title"), "label")
+            ),
+        ]
+    );
+}
+
+#[test]
+fn reference_cycles() {
+    let res = resource(
+        r#"
+self = { self }
+a = { b }
+b = { -c }
+-c = { a }
+d = { a } { e }
+e = { e.attr }
+    .attr = { e }
+"#,
+    );
+    let graph = ReferenceGraph::new([&res]);
+
+    assert_eq!(
+        graph.cycles(),
+        vec![
+            vec![&Node::message("a"), &Node::message("b"), &Node::term("c")],
+            vec![&Node::message("e"), &attribute(Node::message("e"), "attr")],
+            vec![&Node::message("self")],
+        ]
+    );
+}
+
+#[test]
+fn long_reference_chains() {
+    let count = 100_000;
+    let mut source: String = (1..count)
+        .map(|idx| format!("m{} = {{ m{} }}\n", idx, idx + 1))
+        .collect();
+    source.push_str(&format!("m{} = {{ m1 }}\n", count));
+    let res = resource(&source);
+    let graph = ReferenceGraph::new([&res]);
+
+    let cycles = graph.cycles();
+    assert_eq!(cycles.len(), 1);
+    assert_eq!(cycles[0].len(), count);
+}
+
+#[test]
+fn unreferenced_entries() {
+    let res = resource(
+        r#"
+-used = Used
+-unused = Unused
+-recursive = { -recursive.attr ->
+       *[other] Recursive
+    }
+    .attr = other
+welcome = { -used }
+"#,
+    );
+    let graph = ReferenceGraph::new([&res]);
+
+    assert_eq!(
+        graph.unreferenced(),
+        vec![
+            &Node::message("welcome"),
+            &Node::term("recursive"),
+            &Node::term("unused"),
+        ]
+    );
+}
+
+#[test]
+fn first_definition_wins() {
+    let first = resource("key = { -first }\n");
+    let second = resource("key = { -second }\n");
+    let graph = ReferenceGraph::new([&first, &second]);
+
+    assert_eq!(
+        graph.references(&Node::message("key")).collect::<Vec<_>>(),
+        vec![&Node::term("first")]
+    );
+}
+
+#[test]
+fn node_display() {
+    assert_eq!(Node::message("key").to_string(), "key");
+    assert_eq!(Node::term("brand").to_string(), "-brand");
+    assert_eq!(
+        attribute(Node::term("brand"), "gender").to_string(),
+        "-brand.gender"
+    );
+    assert_eq!(
+        attribute(Node::message("key"), "title").entry(),
+        Node::message("key")
+    );
+}
@@ -1,5 +1,8 @@
-use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use fluent_bundle::resolver::ResolverError;
+use fluent_bundle::types::FluentDateTime;
+use fluent_bundle::{FluentArgs, FluentBundle, FluentError, FluentResource, FluentValue};
 use fluent_syntax::ast::Pattern;
+use unic_langid::langid;
 
 #[test]
 fn test_builtin_number() {
@@ -67,3 +70,186 @@ order = { NUMBER($num, type: "ordinal") ->
     assert_eq!(get_val(order, 15).unwrap(), "\u{2068}15\u{2069}th");
     assert_eq!(get_val(order, 123).unwrap(), "\u{2068}123\u{2069}rd");
 }
+
+#[test]
+fn test_builtin_number_locale() {
+    let ftl_string = String::from(
+        r#"
+percent = { NUMBER($num, style: "percent") }
+price = { NUMBER($num, style: "currency", currency: "EUR") }
+        "#,
+    );
+
+    let mut bundle = FluentBundle::new(vec![langid!("de-DE")]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(FluentResource::try_new(ftl_string).expect("Could not parse an FTL string."))
+        .expect("Failed to add FTL resources to the bundle.");
+    bundle
+        .add_builtins()
+        .expect("Failed to add builtin functions to the bundle.");
+
+    let format = |id: &str, num: f64| {
+        let mut args = FluentArgs::new();
+        args.set("num", num);
+        let pattern = bundle
+            .get_message(id)
+            .expect("Message doesn't exist")
+            .value()
+            .expect("Message has no value");
+        let mut errors = vec![];
+        let val = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        assert!(errors.is_empty());
+        val.into_owned()
+    };
+
+    assert_eq!(format("percent", 0.25), "25\u{a0}%");
+    assert_eq!(format("price", 1234.5), "1.234,50\u{a0}€");
+}
+
+#[test]
+fn test_builtin_datetime() {
+    let ftl_string = String::from(
+        r#"
+today = Today is { DATETIME($date, dateStyle: "long") }.
+meeting = { DATETIME($date, hour: "numeric", minute: "numeric") }
+zoned = { DATETIME($date, timeZone: "+05:00") }
+        "#,
+    );
+
+    let mut bundle = FluentBundle::new(vec![langid!("fr-FR")]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(FluentResource::try_new(ftl_string).expect("Could not parse an FTL string."))
+        .expect("Failed to add FTL resources to the bundle.");
+    bundle
+        .add_builtins()
+        .expect("Failed to add builtin functions to the bundle.");
+
+    let format = |id: &str, date: FluentValue| {
+        let mut args = FluentArgs::new();
+        args.set("date", date);
+        let pattern = bundle
+            .get_message(id)
+            .expect("Message doesn't exist")
+            .value()
+            .expect("Message has no value");
+        let mut errors = vec![];
+        let val = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        assert!(errors.is_empty());
+        val.into_owned()
+    };
+
+    let date: FluentDateTime = "2022-10-27T15:04:05Z".parse().unwrap();
+    assert_eq!(
+        format("today", date.clone().into()),
+        "Today is 27 octobre 2022."
+    );
+    assert_eq!(format("meeting", date.into()), "15:04");
+    assert_eq!(
+        format("today", "2020-01-20".into()),
+        "Today is 20 janvier 2020."
+    );
+    assert_eq!(format("today", 0.into()), "Today is 1 janvier 1970.");
+
+    // Dates out of the supported range are rejected.
+    for date in [1e300, f64::NAN, -8.64e15 - 1.0] {
+        assert_eq!(format("zoned", date.into()), "DATETIME()");
+    }
+    assert_eq!(format("zoned", 8.64e15.into()), "13/09/275760");
+    let max = FluentDateTime::new(i64::MAX, Default::default());
+    assert_eq!(format("zoned", max.into()), "DATETIME()");
+}
+
+#[test]
+fn test_builtin_datetime_time_zones() {
+    let ftl_string = String::from(
+        r#"
+utc = { DATETIME($date, timeStyle: "short", timeZone: "UTC") }
+plus-two = { DATETIME($date, timeStyle: "short", timeZone: "UTC+2") }
+minus-five = { DATETIME($date, timeStyle: "short", timeZone: "-05:30") }
+paris = { DATETIME($date, timeStyle: "short", timeZone: "Europe/Paris") }
+        "#,
+    );
+
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(FluentResource::try_new(ftl_string).expect("Could not parse an FTL string."))
+        .expect("Failed to add FTL resources to the bundle.");
+    bundle
+        .add_builtins()
+        .expect("Failed to add builtin functions to the bundle.");
+
+    let format = |id: &str| {
+        let mut args = FluentArgs::new();
+        args.set("date", "2022-10-27T15:04:05Z");
+        let pattern = bundle
+            .get_message(id)
+            .expect("Message doesn't exist")
+            .value()
+            .expect("Message has no value");
+        let mut errors = vec![];
+        let val = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        (val.into_owned(), errors)
+    };
+
+    assert_eq!(format("utc"), ("3:04 PM".to_string(), vec![]));
+    assert_eq!(format("plus-two"), ("5:04 PM".to_string(), vec![]));
+    assert_eq!(format("minus-five"), ("9:34 AM".to_string(), vec![]));
+
+    // Named time zones are rejected rather than formatted as UTC.
+    assert_eq!(
+        format("paris"),
+        (
+            "DATETIME()".to_string(),
+            vec![FluentError::ResolverError(
+                ResolverError::UnsupportedTimeZone("Europe/Paris".to_string())
+            )]
+        )
+    );
+}
+
+#[test]
+fn test_builtin_number_plural_rounding() {
+    let ftl_string = String::from(
+        r#"
+rounded = { NUMBER($num, maximumFractionDigits: 0) ->
+    [one] { NUMBER($num, maximumFractionDigits: 0) } file
+   *[other] { NUMBER($num, maximumFractionDigits: 0) } files
+}
+padded = { NUMBER($num, minimumFractionDigits: 1) ->
+    [one] { NUMBER($num, minimumFractionDigits: 1) } mile
+   *[other] { NUMBER($num, minimumFractionDigits: 1) } miles
+}
+        "#,
+    );
+
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(FluentResource::try_new(ftl_string).expect("Could not parse an FTL string."))
+        .expect("Failed to add FTL resources to the bundle.");
+    bundle
+        .add_builtins()
+        .expect("Failed to add builtin functions to the bundle.");
+
+    let format = |id: &str, num: f64| {
+        let mut args = FluentArgs::new();
+        args.set("num", num);
+        let pattern = bundle
+            .get_message(id)
+            .expect("Message doesn't exist")
+            .value()
+            .expect("Message has no value");
+        let mut errors = vec![];
+        let val = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        assert!(errors.is_empty());
+        val.into_owned()
+    };
+
+    assert_eq!(format("rounded", 1.2), "1 file");
+    assert_eq!(format("rounded", 0.6), "1 file");
+    assert_eq!(format("rounded", 1.6), "2 files");
+    assert_eq!(format("padded", 1.0), "1.0 miles");
+}
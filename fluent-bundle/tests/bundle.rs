@@ -1,4 +1,9 @@
-use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+use fluent_bundle::analysis::Node;
+use fluent_bundle::resolver::errors::{ReferenceKind, ResolverError};
+use fluent_bundle::resolver::{Isolation, ResolverLimits, TraceEvent, TraceStep};
+use fluent_bundle::{
+    FluentArgs, FluentBundle, FluentError, FluentPart, FluentResource, FluentValue,
+};
 use std::borrow::Cow;
 use unic_langid::langid;
 
@@ -84,3 +89,602 @@ fn arguments_outlive_formatted_pattern() {
 
     assert_eq!(formatted_pattern, "Variable");
 }
+
+fn invalid(id: &str, error: ResolverError) -> FluentError {
+    FluentError::InvalidEntry {
+        id: id.to_string(),
+        error,
+    }
+}
+
+#[test]
+fn check_reports_errors() {
+    let res = FluentResource::try_new(
+        r#"
+-brand = { -brand.short ->
+       *[other] { -brand }
+    }
+    .short = other
+attrs =
+    .title = Title
+valid = { attrs.title } { UPPER($name) }
+broken = { attrs } { attrs.label } { -missing } { missing }
+    .call = { LOWER(-missing.attr) }
+self = { self }
+"#
+        .to_string(),
+    )
+    .unwrap();
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+    bundle
+        .add_function("UPPER", |_, _| FluentValue::None)
+        .expect("Failed to add a function");
+
+    let message = |id: &str, attribute: Option<&str>| {
+        ResolverError::Reference(ReferenceKind::Message {
+            id: id.to_string(),
+            attribute: attribute.map(str::to_string),
+        })
+    };
+    let term = |id: &str, attribute: Option<&str>| {
+        ResolverError::Reference(ReferenceKind::Term {
+            id: id.to_string(),
+            attribute: attribute.map(str::to_string),
+        })
+    };
+    assert_eq!(
+        bundle.check(),
+        vec![
+            invalid("broken", ResolverError::NoValue("attrs".to_string())),
+            invalid("broken", message("attrs", Some("label"))),
+            invalid("broken", message("missing", None)),
+            invalid("broken", term("missing", None)),
+            invalid("broken.call", term("missing", Some("attr"))),
+            invalid(
+                "broken.call",
+                ResolverError::Reference(ReferenceKind::Function {
+                    id: "LOWER".to_string(),
+                }),
+            ),
+            invalid("self", ResolverError::Cyclic),
+            invalid("-brand", ResolverError::Cyclic),
+        ]
+    );
+}
+
+#[test]
+fn check_valid_bundle() {
+    let res = FluentResource::try_new(
+        r#"
+-brand = Firefox
+    .gender = masculine
+about = { -brand.gender ->
+        [masculine] About { -brand }
+       *[other] About
+    }
+    .title = { about }
+"#
+        .to_string(),
+    )
+    .unwrap();
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+
+    assert_eq!(bundle.check(), vec![]);
+}
+
+#[test]
+fn message_variables() {
+    let res = FluentResource::try_new(
+        r#"
+-brand = { $case ->
+       *[nominative] { $brand-name }
+        [genitive] { other }
+    }
+other = { $other }
+user = { $user }
+    .title = { DATETIME($date) } { $user }
+loop = { $loop } { loop.title }
+    .title = { loop }
+message = { user.title } { -brand(case: "genitive") } { loop }
+    .count = { NUMBER($count) ->
+        [one] One
+       *[other] { $count }
+    }
+"#
+        .to_string(),
+    )
+    .unwrap();
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+
+    let variables = bundle
+        .message_variables("message")
+        .expect("Failed to retrieve a message");
+    assert_eq!(
+        variables.keys().collect::<Vec<_>>(),
+        vec!["count", "date", "loop", "user"]
+    );
+    assert!(variables["count"].placeable);
+    assert!(!variables["count"].selector);
+    assert_eq!(
+        variables["count"].functions.iter().collect::<Vec<_>>(),
+        vec!["NUMBER"]
+    );
+    assert!(!variables["date"].placeable);
+    assert!(variables["date"].functions.contains("DATETIME"));
+
+    let message = bundle.get_message("message").unwrap();
+    let value = message.value().unwrap();
+    assert_eq!(
+        bundle.pattern_variables(value).keys().collect::<Vec<_>>(),
+        vec!["date", "loop", "user"]
+    );
+    assert_eq!(
+        message.variables().keys().collect::<Vec<_>>(),
+        vec!["count"]
+    );
+    assert_eq!(bundle.message_variables("missing"), None);
+}
+
+#[test]
+fn strict_arguments() {
+    let res = FluentResource::try_new(
+        r#"
+-brand = { $case ->
+       *[nominative] Firefox
+        [genitive] Firefox's
+    }
+user = { $name }
+emails = { $count ->
+        [one] { user } has one new email in { -brand(case: "genitive") } inbox
+       *[other] { $count } new emails for { $recipient }
+    }
+gender = { $gender ->
+        [masculine] His
+       *[other] Their
+    }
+"#
+        .to_string(),
+    )
+    .unwrap();
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+    bundle.set_use_isolating(false);
+
+    let format = |bundle: &FluentBundle<&FluentResource>, id: &str, args: &FluentArgs| {
+        let pattern = bundle.get_message(id).unwrap().value().unwrap();
+        let mut errors = vec![];
+        let value = bundle.format_pattern(pattern, Some(args), &mut errors);
+        (value.into_owned(), errors)
+    };
+    let variable = |id: &str| {
+        FluentError::from(ResolverError::Reference(ReferenceKind::Variable {
+            id: id.to_string(),
+        }))
+    };
+
+    let mut args = FluentArgs::new();
+    args.set("count", "1");
+    args.set("name", "Jane");
+    args.set("extra", true.to_string());
+
+    let (value, errors) = format(&bundle, "emails", &args);
+    assert_eq!(value, "1 new emails for {$recipient}");
+    assert_eq!(errors, vec![variable("recipient")]);
+
+    bundle.set_strict(true);
+    let (_, errors) = format(&bundle, "emails", &args);
+    assert_eq!(
+        errors,
+        vec![
+            FluentError::from(ResolverError::ExpectedNumber("count".to_string())),
+            variable("recipient"),
+            FluentError::from(ResolverError::UnusedArgument("extra".to_string())),
+        ]
+    );
+
+    let mut args = FluentArgs::new();
+    args.set("count", 1);
+    let (value, errors) = format(&bundle, "emails", &args);
+    assert_eq!(value, "{$name} has one new email in Firefox's inbox");
+    assert_eq!(errors, vec![variable("name"), variable("recipient")]);
+
+    let mut args = FluentArgs::new();
+    args.set("gender", "masculine");
+    let (value, errors) = format(&bundle, "gender", &args);
+    assert_eq!(value, "His");
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn resolver_limits() {
+    let res = FluentResource::try_new(
+        r#"
+lol0 = LOL
+lol1 = { lol0 } { lol0 } { lol0 }
+lol2 = { lol1 } { lol1 } { lol1 }
+nested = { lol2 }
+greeting = Hello, { $name }!
+"#
+        .to_string(),
+    )
+    .unwrap();
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+    bundle.set_use_isolating(false);
+
+    let format = |bundle: &FluentBundle<&FluentResource>, id: &str, args: Option<&FluentArgs>| {
+        let pattern = bundle.get_message(id).unwrap().value().unwrap();
+        let mut errors = vec![];
+        let value = bundle.format_pattern(pattern, args, &mut errors);
+        let mut written = String::new();
+        let mut write_errors = vec![];
+        bundle
+            .write_pattern(&mut written, pattern, args, &mut write_errors)
+            .expect("Failed to write.");
+        assert_eq!(value, written);
+        assert_eq!(errors, write_errors);
+        (value.into_owned(), errors)
+    };
+
+    let (value, errors) = format(&bundle, "nested", None);
+    assert_eq!(value, "LOL LOL LOL LOL LOL LOL LOL LOL LOL");
+    assert_eq!(errors, vec![]);
+
+    bundle.set_limits(ResolverLimits {
+        max_placeables: 5,
+        ..Default::default()
+    });
+    let (_, errors) = format(&bundle, "nested", None);
+    assert_eq!(
+        errors,
+        vec![FluentError::from(ResolverError::TooManyPlaceables)]
+    );
+
+    bundle.set_limits(ResolverLimits {
+        max_depth: 1,
+        ..Default::default()
+    });
+    let (value, errors) = format(&bundle, "nested", None);
+    assert_eq!(value, "{lol1} {lol1} {lol1}");
+    assert_eq!(
+        errors,
+        vec![FluentError::from(ResolverError::TooDeeplyNested); 3]
+    );
+
+    bundle.set_limits(ResolverLimits {
+        max_output_bytes: Some(10),
+        ..Default::default()
+    });
+    let (value, errors) = format(&bundle, "nested", None);
+    assert_eq!(value, "LOL LOL ");
+    assert_eq!(
+        errors,
+        vec![FluentError::from(ResolverError::OutputTooLong)]
+    );
+
+    let mut args = FluentArgs::new();
+    args.set("name", "Jane");
+    let (value, errors) = format(&bundle, "greeting", Some(&args));
+    assert_eq!(value, "Hello, ");
+    assert_eq!(
+        errors,
+        vec![FluentError::from(ResolverError::OutputTooLong)]
+    );
+}
+
+#[test]
+fn isolation() {
+    let res = FluentResource::try_new(
+        r#"
+-brand = Firefox
+greeting = { -brand } welcomes { $name } and { $count } others
+"#
+        .to_string(),
+    )
+    .unwrap();
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+
+    let mut args = FluentArgs::new();
+    args.set("name", "\u{5e9}\u{5e8}\u{5d4}");
+    args.set("count", 2);
+    let format = |bundle: &FluentBundle<&FluentResource>| {
+        let pattern = bundle.get_message("greeting").unwrap().value().unwrap();
+        let mut errors = vec![];
+        let value = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        assert_eq!(errors, vec![]);
+        value.into_owned()
+    };
+
+    assert_eq!(
+        format(&bundle),
+        "Firefox welcomes \u{2068}\u{5e9}\u{5e8}\u{5d4}\u{2069} and \u{2068}2\u{2069} others"
+    );
+
+    bundle.set_isolation(Isolation::None);
+    assert_eq!(
+        format(&bundle),
+        "Firefox welcomes \u{5e9}\u{5e8}\u{5d4} and 2 others"
+    );
+
+    bundle.set_isolation(Isolation::Directional);
+    assert_eq!(
+        format(&bundle),
+        "Firefox welcomes \u{2067}\u{5e9}\u{5e8}\u{5d4}\u{2069} and \u{2068}2\u{2069} others"
+    );
+
+    bundle.set_isolation(Isolation::Html);
+    assert_eq!(
+        format(&bundle),
+        "Firefox welcomes <bdi>\u{5e9}\u{5e8}\u{5d4}</bdi> and <bdi>2</bdi> others"
+    );
+
+    bundle.set_isolation(Isolation::Custom(|value| format!("[{}]", value).into()));
+    assert_eq!(
+        format(&bundle),
+        "Firefox welcomes [\u{5e9}\u{5e8}\u{5d4}] and [2] others"
+    );
+
+    bundle.set_use_isolating(true);
+    assert_eq!(
+        format(&bundle),
+        "Firefox welcomes \u{2068}\u{5e9}\u{5e8}\u{5d4}\u{2069} and \u{2068}2\u{2069} others"
+    );
+
+    bundle.set_isolation(Isolation::Html);
+    let pattern = bundle.get_message("greeting").unwrap().value().unwrap();
+    let mut args = FluentArgs::new();
+    args.set("name", "<b>Tom & Jerry</b>");
+    args.set("count", 2);
+    let mut errors = vec![];
+    assert_eq!(
+        bundle.format_pattern(pattern, Some(&args), &mut errors),
+        "Firefox welcomes <bdi>&lt;b&gt;Tom &amp; Jerry&lt;/b&gt;</bdi> and <bdi>2</bdi> others"
+    );
+
+    // The placeables buffered to be isolated are limited too.
+    bundle.set_limits(ResolverLimits {
+        max_output_bytes: Some(20),
+        ..Default::default()
+    });
+    let pattern = bundle.get_message("greeting").unwrap().value().unwrap();
+    args.set("name", "x".repeat(100));
+    let mut errors = vec![];
+    assert_eq!(
+        bundle.format_pattern(pattern, Some(&args), &mut errors),
+        "Firefox welcomes "
+    );
+    assert_eq!(
+        errors,
+        vec![FluentError::from(ResolverError::OutputTooLong)]
+    );
+}
+
+#[test]
+fn format_to_parts() {
+    let res = FluentResource::try_new(
+        r#"
+-brand = Firefox
+menu = Menu
+    .label = Open the menu
+emails = { $count ->
+        [one] { $user } has one email in { menu.label }
+       *[other] { $user } has { NUMBER($count) } emails in { { "the" } } { -brand } inbox
+    }
+"#
+        .to_string(),
+    )
+    .unwrap();
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+    bundle
+        .add_function("NUMBER", |positional, _named| match positional.first() {
+            Some(FluentValue::Number(n)) => format!("#{}", n.value).into(),
+            _ => FluentValue::Error,
+        })
+        .expect("Failed to add a function");
+
+    let pattern = bundle.get_message("emails").unwrap().value().unwrap();
+    let text = |value: &str| FluentPart::Text(value.to_string().into());
+    let user = || FluentPart::Variable {
+        name: "user",
+        value: "Jane".to_string(),
+    };
+
+    let mut args = FluentArgs::new();
+    args.set("count", 1);
+    args.set("user", "Jane");
+    let mut errors = vec![];
+    let parts = bundle.format_to_parts(pattern, Some(&args), &mut errors);
+    assert_eq!(
+        parts,
+        vec![
+            user(),
+            text(" has one email in "),
+            FluentPart::Message {
+                id: "menu",
+                attribute: Some("label"),
+                value: "Open the menu".to_string(),
+            },
+        ]
+    );
+    assert_eq!(errors, vec![]);
+
+    args.set("count", 5);
+    let parts = bundle.format_to_parts(pattern, Some(&args), &mut errors);
+    assert_eq!(
+        parts,
+        vec![
+            user(),
+            text(" has "),
+            FluentPart::Function {
+                id: "NUMBER",
+                value: "#5".to_string(),
+            },
+            text(" emails in the "),
+            FluentPart::Term {
+                id: "brand",
+                attribute: None,
+                value: "Firefox".to_string(),
+            },
+            text(" inbox"),
+        ]
+    );
+    assert_eq!(errors, vec![]);
+
+    let parts = bundle.format_to_parts(pattern, None, &mut errors);
+    assert_eq!(
+        parts,
+        vec![
+            FluentPart::Variable {
+                name: "user",
+                value: "{$user}".to_string(),
+            },
+            text(" has "),
+            FluentPart::Function {
+                id: "NUMBER",
+                value: "NUMBER()".to_string(),
+            },
+            text(" emails in the "),
+            FluentPart::Term {
+                id: "brand",
+                attribute: None,
+                value: "Firefox".to_string(),
+            },
+            text(" inbox"),
+        ]
+    );
+    assert_eq!(errors.len(), 3);
+
+    bundle.set_limits(ResolverLimits {
+        max_output_bytes: Some(10),
+        ..Default::default()
+    });
+    let pattern = bundle.get_message("emails").unwrap().value().unwrap();
+    let mut errors = vec![];
+    let parts = bundle.format_to_parts(pattern, Some(&args), &mut errors);
+    assert_eq!(parts, vec![user(), text(" has ")]);
+    assert_eq!(
+        errors,
+        vec![FluentError::from(ResolverError::OutputTooLong)]
+    );
+}
+
+#[test]
+fn format_pattern_with_trace() {
+    let res = FluentResource::try_new(
+        r#"
+-brand = { $case ->
+       *[nominative] Firefox
+        [genitive] Firefox's
+    }
+    .gender = masculine
+emails = { NUMBER($count, minimumFractionDigits: 1) ->
+        [one] One email in { -brand(case: "genitive") } inbox
+       *[other] { $count } emails in { -brand.gender ->
+            [feminine] her
+           *[other] its
+        } inbox
+    }
+"#
+        .to_string(),
+    )
+    .unwrap();
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+    bundle.add_builtins().expect("Failed to add the builtins");
+    bundle.set_use_isolating(false);
+
+    let pattern = bundle.get_message("emails").unwrap().value().unwrap();
+    let number = |result: &str| TraceEvent::Function {
+        id: "NUMBER".to_string(),
+        positional: vec!["1".to_string()],
+        named: vec![("minimumFractionDigits".to_string(), "1".to_string())],
+        result: result.to_string(),
+    };
+    let step = |depth, event| TraceStep { depth, event };
+
+    let mut args = FluentArgs::new();
+    args.set("count", 1);
+    let mut errors = vec![];
+    let (value, trace) = bundle.format_pattern_with_trace(pattern, Some(&args), &mut errors);
+    assert_eq!(value, "1 emails in its inbox");
+    assert_eq!(errors, vec![]);
+    assert_eq!(
+        trace.steps,
+        vec![
+            step(0, number("1.0")),
+            step(
+                0,
+                TraceEvent::Select {
+                    selector: "NUMBER()".to_string(),
+                    value: "1.0".to_string(),
+                    category: Some("other"),
+                    key: "other".to_string(),
+                    default: false,
+                }
+            ),
+            step(
+                0,
+                TraceEvent::Enter(Node::attribute(Node::term("brand"), "gender"))
+            ),
+            step(
+                0,
+                TraceEvent::Select {
+                    selector: "-brand.gender".to_string(),
+                    value: "masculine".to_string(),
+                    category: None,
+                    key: "other".to_string(),
+                    default: true,
+                }
+            ),
+        ]
+    );
+
+    let res = FluentResource::try_new(
+        r#"
+greeting = { $count ->
+        [one] Hello { -brand(case: "genitive") } { user }
+       *[other] Hello
+    }
+user = { NUMBER($count) ->
+        [0] nobody
+       *[one] someone
+    }
+-brand = { $case ->
+       *[nominative] Firefox
+        [genitive] Firefox's
+    }
+"#
+        .to_string(),
+    )
+    .unwrap();
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+    bundle.add_builtins().expect("Failed to add the builtins");
+    bundle.set_use_isolating(false);
+
+    let pattern = bundle.get_message("greeting").unwrap().value().unwrap();
+    let (value, trace) = bundle.format_pattern_with_trace(pattern, Some(&args), &mut errors);
+    assert_eq!(value, "Hello Firefox's someone");
+    assert_eq!(errors, vec![]);
+    assert_eq!(
+        trace.to_string(),
+        r#"select $count = "1" (one) -> [one]
+enter -brand
+  select $case = "genitive" -> [genitive]
+enter user
+  call NUMBER("1") = "1"
+  select NUMBER() = "1" (one) -> [one]
+"#
+    );
+
+    let (_, trace) = bundle.format_pattern_with_trace(pattern, None, &mut errors);
+    assert_eq!(
+        trace.to_string(),
+        "select $count = \"\" -> [other] (default)\n"
+    );
+}
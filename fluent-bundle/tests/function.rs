@@ -37,10 +37,7 @@ liked-count2 = { NUMBER($num) ->
                 } else {
                     return FluentValue::Error;
                 };
-                let mut num = FluentNumber {
-                    value: num,
-                    options: Default::default(),
-                };
+                let mut num = FluentNumber::new(num, Default::default());
                 num.options.merge(named);
 
                 FluentValue::Number(num)
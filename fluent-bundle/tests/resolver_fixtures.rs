@@ -23,8 +23,7 @@ fn transform_example(s: &str) -> Cow<'_, str> {
 #[derive(Clone)]
 struct ScopeLevel {
     name: String,
-    resources: Vec<TestResource>,
-    bundles: Vec<TestBundle>,
+    setup: TestSetup,
 }
 
 #[derive(Clone)]
@@ -48,11 +47,11 @@ impl Scope {
         let mut available_resources = vec![];
 
         for lvl in self.0.iter() {
-            for r in lvl.resources.iter() {
+            for r in lvl.setup.resources.iter() {
                 available_resources.push(r);
             }
 
-            for b in lvl.bundles.iter() {
+            for b in lvl.setup.bundles.iter() {
                 let name = b
                     .name
                     .as_ref()
@@ -211,8 +210,10 @@ fn test_suite(suite: &TestSuite, defaults: &Option<TestDefaults>, mut scope: Sco
 
     scope.0.push(ScopeLevel {
         name: suite.name.clone(),
-        bundles: suite.bundles.clone(),
-        resources: suite.resources.clone(),
+        setup: TestSetup {
+            bundles: suite.bundles.clone(),
+            resources: suite.resources.clone(),
+        },
     });
 
     for test in &suite.tests {
@@ -231,8 +232,10 @@ fn test_test(test: &Test, defaults: &Option<TestDefaults>, mut scope: Scope) {
 
     scope.0.push(ScopeLevel {
         name: test.name.clone(),
-        bundles: test.bundles.clone(),
-        resources: test.resources.clone(),
+        setup: TestSetup {
+            bundles: test.bundles.clone(),
+            resources: test.resources.clone(),
+        },
     });
 
     for assert in &test.asserts {
@@ -334,6 +337,12 @@ fn test_errors(errors: &[FluentError], reference: Option<&[TestError]>) {
                 ResolverError::TooManyPlaceables => {
                     assert_eq!(reference.error_type, "TooManyPlaceables");
                 }
+                ResolverError::TooDeeplyNested => {
+                    assert_eq!(reference.error_type, "TooDeeplyNested");
+                }
+                ResolverError::OutputTooLong => {
+                    assert_eq!(reference.error_type, "OutputTooLong");
+                }
                 _ => unimplemented!(),
             },
             FluentError::ParserError(_) => {
@@ -342,6 +351,13 @@ fn test_errors(errors: &[FluentError], reference: Option<&[TestError]>) {
             FluentError::Overriding { .. } => {
                 assert_eq!(reference.error_type, "Overriding");
             }
+            FluentError::ResourceLoad { .. } => {
+                assert_eq!(reference.error_type, "ResourceLoad");
+            }
+            FluentError::InvalidEntry { .. } => {
+                assert_eq!(reference.error_type, "InvalidEntry");
+            }
+            _ => unimplemented!(),
         }
     }
 }
@@ -1,13 +1,14 @@
 use fluent_bundle::resolver::Scope;
 use fluent_bundle::types::{
-    FluentNumber, FluentNumberCurrencyDisplayStyle, FluentNumberOptions, FluentNumberStyle,
+    DateTimeFormat, FluentDateTime, FluentDecimal, FluentNumber, FluentNumberCurrencyDisplayStyle,
+    FluentNumberOptions, FluentNumberStyle, NumberFormat,
 };
 use fluent_bundle::FluentArgs;
 use fluent_bundle::FluentBundle;
 use fluent_bundle::FluentResource;
 use fluent_bundle::FluentValue;
 use intl_pluralrules::operands::PluralOperands;
-use unic_langid::langid;
+use unic_langid::{langid, LanguageIdentifier};
 
 #[test]
 fn fluent_value_try_number() {
@@ -154,3 +155,324 @@ fn fluent_number_to_operands() {
         }
     );
 }
+
+#[test]
+fn fluent_number_format() {
+    let format = |locale: LanguageIdentifier, value: f64, args: &[(&'static str, FluentValue)]| {
+        let mut opts = FluentNumberOptions::default();
+        let args: FluentArgs = args.iter().cloned().collect();
+        opts.merge(&args);
+        NumberFormat::new(&locale).format(&FluentNumber::new(value, opts))
+    };
+
+    assert_eq!(format(langid!("en-US"), 1234567.891, &[]), "1,234,567.891");
+    assert_eq!(format(langid!("de-DE"), 1234567.891, &[]), "1.234.567,891");
+    assert_eq!(format(langid!("fr-FR"), 1234.5, &[]), "1\u{202f}234,5");
+    assert_eq!(format(langid!("hi-IN"), 1234567.0, &[]), "12,34,567");
+    assert_eq!(format(langid!("es-ES"), 1234.0, &[]), "1234");
+    assert_eq!(format(langid!("es-ES"), 12345.0, &[]), "12.345");
+    assert_eq!(
+        format(langid!("en-US"), 1234.0, &[("useGrouping", "false".into())]),
+        "1234"
+    );
+
+    assert_eq!(format(langid!("en-US"), 0.1234567, &[]), "0.123");
+    assert_eq!(
+        format(
+            langid!("en-US"),
+            1.005,
+            &[("maximumFractionDigits", 2.into())]
+        ),
+        "1.01"
+    );
+    assert_eq!(
+        format(langid!("en-US"), 3.0, &[("minimumIntegerDigits", 3.into())]),
+        "003"
+    );
+    assert_eq!(
+        format(
+            langid!("en-US"),
+            1234.5,
+            &[("maximumSignificantDigits", 2.into())]
+        ),
+        "1,200"
+    );
+    assert_eq!(
+        format(
+            langid!("en-US"),
+            1.5,
+            &[("minimumSignificantDigits", 4.into())]
+        ),
+        "1.500"
+    );
+
+    let percent = [("style", FluentValue::from("percent"))];
+    assert_eq!(format(langid!("en-US"), 0.256, &percent), "26%");
+    assert_eq!(format(langid!("de-DE"), 0.256, &percent), "26\u{a0}%");
+    assert_eq!(format(langid!("tr"), 0.256, &percent), "%26");
+
+    let currency = |code: &'static str, display: &'static str| {
+        [
+            ("style", FluentValue::from("currency")),
+            ("currency", code.into()),
+            ("currencyDisplay", display.into()),
+        ]
+    };
+    assert_eq!(
+        format(langid!("en-US"), -1234.5, &currency("USD", "symbol")),
+        "-$1,234.50"
+    );
+    assert_eq!(
+        format(langid!("de-DE"), 1234.5, &currency("EUR", "symbol")),
+        "1.234,50\u{a0}€"
+    );
+    assert_eq!(
+        format(langid!("ja"), 1234.5, &currency("JPY", "symbol")),
+        "¥1,235"
+    );
+    assert_eq!(
+        format(langid!("en-US"), 10.0, &currency("EUR", "code")),
+        "EUR\u{a0}10.00"
+    );
+    assert_eq!(
+        format(langid!("en-US"), 10.0, &currency("EUR", "name")),
+        "10.00 euros"
+    );
+    // The plural rules select the name on the displayed digits.
+    assert_eq!(
+        format(langid!("en-US"), 1.0, &currency("USD", "name")),
+        "1.00 US dollars"
+    );
+    assert_eq!(
+        format(
+            langid!("en-US"),
+            1.0,
+            &[
+                ("maximumFractionDigits", 0.into()),
+                ("minimumFractionDigits", 0.into()),
+                ("style", "currency".into()),
+                ("currency", "USD".into()),
+                ("currencyDisplay", "name".into())
+            ]
+        ),
+        "1 US dollar"
+    );
+    // Locales without names display the code rather than an English name.
+    assert_eq!(
+        format(langid!("de-DE"), 10.0, &currency("EUR", "name")),
+        "10,00 EUR"
+    );
+
+    assert_eq!(format(langid!("en-US"), f64::NAN, &[]), "NaN");
+    assert_eq!(format(langid!("sv"), f64::NEG_INFINITY, &[]), "\u{2212}∞");
+}
+
+#[test]
+fn fluent_datetime_format() {
+    let dt: FluentDateTime = "2022-10-27T15:04:05Z".parse().unwrap();
+    let format = |locale: LanguageIdentifier, args: &[(&'static str, &'static str)]| {
+        let mut dt = dt.clone();
+        let args: FluentArgs = args.iter().cloned().collect();
+        dt.options.merge(&args);
+        DateTimeFormat::new(&locale).format(&dt)
+    };
+
+    assert_eq!(format(langid!("en-US"), &[]), "10/27/2022");
+    assert_eq!(format(langid!("de-DE"), &[]), "27.10.2022");
+    assert_eq!(format(langid!("fr-FR"), &[]), "27/10/2022");
+
+    assert_eq!(
+        format(langid!("en-US"), &[("dateStyle", "full")]),
+        "Thursday, October 27, 2022"
+    );
+    assert_eq!(
+        format(langid!("de-DE"), &[("dateStyle", "long")]),
+        "27. Oktober 2022"
+    );
+    assert_eq!(
+        format(langid!("es-ES"), &[("dateStyle", "long")]),
+        "27 de octubre de 2022"
+    );
+    assert_eq!(
+        format(langid!("en-GB"), &[("dateStyle", "short")]),
+        "27/10/2022"
+    );
+    assert_eq!(
+        format(langid!("en-US"), &[("timeStyle", "short")]),
+        "3:04 PM"
+    );
+    assert_eq!(
+        format(langid!("fr-FR"), &[("timeStyle", "medium")]),
+        "15:04:05"
+    );
+    assert_eq!(
+        format(
+            langid!("en-US"),
+            &[("dateStyle", "medium"), ("timeStyle", "long")]
+        ),
+        "Oct 27, 2022, 3:04:05 PM UTC"
+    );
+    assert_eq!(
+        format(
+            langid!("de-DE"),
+            &[("dateStyle", "long"), ("timeStyle", "short")]
+        ),
+        "27. Oktober 2022 um 15:04"
+    );
+
+    assert_eq!(
+        format(
+            langid!("en-US"),
+            &[("weekday", "long"), ("month", "long"), ("day", "numeric")]
+        ),
+        "Thursday, October 27"
+    );
+    assert_eq!(
+        format(langid!("fr-FR"), &[("month", "short"), ("year", "numeric")]),
+        "oct. 2022"
+    );
+    assert_eq!(
+        format(
+            langid!("en-US"),
+            &[
+                ("hour", "numeric"),
+                ("minute", "2-digit"),
+                ("hour12", "false")
+            ]
+        ),
+        "15:04"
+    );
+    assert_eq!(
+        format(
+            langid!("de-DE"),
+            &[("timeStyle", "long"), ("timeZone", "+02:00")]
+        ),
+        "17:04:05 GMT+2"
+    );
+    assert_eq!(
+        format(
+            langid!("en-US"),
+            &[("dateStyle", "short"), ("timeZone", "-16:00")]
+        ),
+        "10/26/22"
+    );
+}
+
+#[test]
+fn fluent_datetime_from_system_time() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let from = |time| FluentDateTime::from(time).epoch;
+    assert_eq!(from(UNIX_EPOCH + Duration::from_millis(1500)), 1500);
+    assert_eq!(from(UNIX_EPOCH - Duration::from_millis(1500)), -1500);
+    // Times out of the supported range are clamped to it.
+    let far = Duration::from_secs(u64::MAX / 2);
+    if let Some(time) = UNIX_EPOCH.checked_add(far) {
+        assert_eq!(from(time), FluentDateTime::MAX_EPOCH);
+    }
+    if let Some(time) = UNIX_EPOCH.checked_sub(far) {
+        assert_eq!(from(time), -FluentDateTime::MAX_EPOCH);
+    }
+}
+
+#[test]
+fn fluent_datetime_parse() {
+    let parse = |input: &str| input.parse::<FluentDateTime>().map(|dt| dt.epoch);
+    assert_eq!(parse("1970-01-01"), Ok(0));
+    assert_eq!(parse("1970-01-01T00:00:01.5Z"), Ok(1500));
+    assert_eq!(parse("1970-01-01T02:00+02:00"), Ok(0));
+    assert_eq!(parse("1969-12-31T23:59:59"), Ok(-1000));
+    assert!(parse("2022-02-29").is_err());
+    assert!(parse("2022-10-27T25:00").is_err());
+    assert!(parse("yesterday").is_err());
+}
+
+#[test]
+fn fluent_number_to_operands_with_options() {
+    let operands = |value: f64, args: &[(&'static str, FluentValue)]| {
+        let mut opts = FluentNumberOptions::default();
+        let args: FluentArgs = args.iter().cloned().collect();
+        opts.merge(&args);
+        PluralOperands::from(&FluentNumber::new(value, opts))
+    };
+
+    let ops = operands(1.4, &[("maximumFractionDigits", 0.into())]);
+    assert_eq!((ops.i, ops.v, ops.f), (1, 0, 0));
+
+    let ops = operands(1.0, &[("minimumFractionDigits", 2.into())]);
+    assert_eq!((ops.i, ops.v, ops.f), (1, 2, 0));
+
+    let ops = operands(1.23456, &[]);
+    assert_eq!((ops.i, ops.v, ops.f), (1, 3, 235));
+
+    let ops = operands(0.96, &[("maximumSignificantDigits", 1.into())]);
+    assert_eq!((ops.i, ops.v, ops.f), (1, 0, 0));
+
+    let ops = operands(1.0, &[("minimumSignificantDigits", 3.into())]);
+    assert_eq!((ops.i, ops.v, ops.f, ops.t), (1, 2, 0, 0));
+
+    let ops = operands(
+        1.0,
+        &[("style", "currency".into()), ("currency", "USD".into())],
+    );
+    assert_eq!((ops.i, ops.v), (1, 2));
+}
+
+#[test]
+fn fluent_number_exact_decimal() {
+    let big = FluentNumber::from(u128::MAX);
+    assert_eq!(big.as_string(), u128::MAX.to_string());
+    assert_eq!(u128::from(&big), u128::MAX);
+
+    let small = FluentNumber::from(42u64);
+    assert_eq!(small.decimal(), None);
+    assert_eq!(
+        small,
+        FluentNumber::new(42.0, FluentNumberOptions::default())
+    );
+
+    let parsed: FluentNumber = "12345678901234567890.25".parse().unwrap();
+    assert_eq!(parsed.as_string(), "12345678901234567890.25");
+    assert_eq!(
+        NumberFormat::new(&langid!("de-DE")).format(&parsed),
+        "12.345.678.901.234.567.890,25"
+    );
+    let operands: PluralOperands = (&parsed).into();
+    assert_eq!((operands.v, operands.f), (2, 25));
+
+    let price = FluentNumber::from_decimal(FluentDecimal::new(1010, 2), Default::default());
+    assert_eq!(price.as_string(), "10.10");
+    assert_eq!(FluentValue::from(price), FluentValue::try_number("10.10"));
+
+    let mut opts = FluentNumberOptions::default();
+    let args: FluentArgs = [("style", "currency"), ("currency", "USD")]
+        .into_iter()
+        .collect();
+    opts.merge(&args);
+    let amount = FluentNumber::from_decimal("90071992547409.93".parse().unwrap(), opts);
+    assert_eq!(
+        NumberFormat::new(&langid!("en-US")).format(&amount),
+        "$90,071,992,547,409.93"
+    );
+
+    // The scale is capped, rounding the digits beyond it.
+    let tiny = FluentDecimal::new(15, 101);
+    assert_eq!(tiny.scale(), 100);
+    assert_eq!(tiny.to_string(), format!("0.{}2", "0".repeat(99)));
+    let zero = FluentDecimal::new(i128::MAX, u32::MAX);
+    assert_eq!(zero.to_string(), format!("0.{}", "0".repeat(100)));
+
+    let mut opts = FluentNumberOptions::default();
+    let args: FluentArgs = [
+        ("minimumFractionDigits", 1e12),
+        ("minimumIntegerDigits", 1e12),
+    ]
+    .into_iter()
+    .collect();
+    opts.merge(&args);
+    let num = FluentNumber::new(1.5, opts);
+    assert_eq!(
+        num.as_string(),
+        format!("{}1.5{}", "0".repeat(20), "0".repeat(99))
+    );
+}
@@ -0,0 +1,111 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use fluent_syntax::ast::Resource;
+use fluent_syntax::compare::{compare, Difference};
+use fluent_syntax::parser::parse;
+
+/// Compares the FTL file at `translation` with the one at `reference`.
+pub fn compare_files(reference: &Path, translation: &Path) -> Result<Vec<Difference>, io::Error> {
+    let reference_source = fs::read_to_string(reference)?;
+    let translation_source = fs::read_to_string(translation)?;
+    let reference = parse_resource(reference, &reference_source);
+    let translation = parse_resource(translation, &translation_source);
+    Ok(compare(&reference, &translation))
+}
+
+fn parse_resource<'s>(path: &Path, source: &'s str) -> Resource<&'s str> {
+    match parse(source) {
+        Ok(res) => res,
+        Err((res, errors)) => {
+            let origin = path.display().to_string();
+            for err in &errors {
+                eprintln!("{}", err.diagnostic(source).with_origin(&origin));
+            }
+            res
+        }
+    }
+}
+
+/// Returns the paths of the FTL files in `dir` and its subdirectories,
+/// relative to `dir`.
+fn ftl_files(dir: &Path) -> Result<BTreeSet<PathBuf>, io::Error> {
+    let mut files = BTreeSet::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(current) = dirs.pop() {
+        for entry in fs::read_dir(&current)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.is_dir() {
+                // Symlinked directories are skipped, since they may form a loop.
+                if !entry.file_type()?.is_symlink() {
+                    dirs.push(path);
+                }
+            } else if path.extension().map_or(false, |ext| ext == "ftl") {
+                let relative = path
+                    .strip_prefix(dir)
+                    .expect("The path is in the directory");
+                files.insert(relative.to_path_buf());
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Compares the translation at `translation` with the reference at
+/// `reference`, printing the differences. Both paths are either FTL files, or
+/// directories in which the FTL files with the same relative path are
+/// compared.
+///
+/// Returns `false` if there are any differences, or if any file couldn't be
+/// read.
+pub fn compare_paths(reference: &str, translation: &str) -> bool {
+    let (reference, translation) = (Path::new(reference), Path::new(translation));
+    if !reference.is_dir() {
+        return report(translation, compare_files(reference, translation));
+    }
+
+    let (reference_files, translation_files) = match (ftl_files(reference), ftl_files(translation))
+    {
+        (Ok(reference_files), Ok(translation_files)) => (reference_files, translation_files),
+        (Err(err), _) | (_, Err(err)) => {
+            eprintln!("Failed to list the FTL files: {}", err);
+            return false;
+        }
+    };
+
+    let mut success = true;
+    for path in reference_files.union(&translation_files) {
+        let translated = translation.join(path);
+        if !translation_files.contains(path) {
+            println!("{}: Missing file", translated.display());
+            success = false;
+        } else if !reference_files.contains(path) {
+            println!("{}: Obsolete file", translated.display());
+            success = false;
+        } else {
+            success &= report(
+                &translated,
+                compare_files(&reference.join(path), &translated),
+            );
+        }
+    }
+    success
+}
+
+fn report(path: &Path, differences: Result<Vec<Difference>, io::Error>) -> bool {
+    match differences {
+        Ok(differences) => {
+            for difference in &differences {
+                println!("{}: {}", path.display(), difference);
+            }
+            differences.is_empty()
+        }
+        Err(err) => {
+            eprintln!("Failed to compare {}: {}", path.display(), err);
+            false
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::process;
+
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+
+use fluent_cli::compare::compare_paths;
+use fluent_cli::fmt::format_files;
+use fluent_cli::lint::{lint_files, Linter};
+use fluent_syntax::serializer::{Options, SelectLayout};
+
+fn format_options(matches: &ArgMatches) -> Options {
+    let mut options = Options::default();
+    if let Some(indent) = number_arg(matches, "indent") {
+        options.indent = indent;
+    }
+    if let Some(width) = number_arg(matches, "inline-width") {
+        options.inline_width = Some(width);
+    }
+    if let Some(layout) = matches.value_of("select-layout") {
+        options.select_layout = match layout {
+            "block" => SelectLayout::Block,
+            "inline" => SelectLayout::Inline,
+            _ => unreachable!(),
+        };
+    }
+    options
+}
+
+fn number_arg(matches: &ArgMatches, name: &str) -> Option<usize> {
+    let value = matches.value_of(name)?;
+    match value.parse() {
+        Ok(number) => Some(number),
+        Err(_) => {
+            eprintln!("Invalid value for --{}: {} isn't a number", name, value);
+            process::exit(2);
+        }
+    }
+}
+
+fn linter(matches: &ArgMatches) -> Linter {
+    let mut linter = Linter::default();
+    for (arg, enabled) in [("enable", true), ("disable", false)] {
+        for name in matches.values_of(arg).into_iter().flatten() {
+            if !linter.set_enabled(name, enabled) {
+                eprintln!("Unknown rule: {}", name);
+                process::exit(2);
+            }
+        }
+    }
+    linter
+}
+
+fn list_rules(linter: &Linter) {
+    for (rule, enabled) in linter.rules() {
+        let state = if enabled { "enabled" } else { "disabled" };
+        println!("{:<24} {:<9} {}", rule.name(), state, rule.description());
+    }
+}
+
+fn main() {
+    let matches = App::new("Fluent")
+        .version("1.0")
+        .about("Developer tools for Fluent Translation Lists")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("fmt")
+                .about("Formats FTL files in place")
+                .args_from_usage(
+                    "--check 'Checks that the files are formatted without rewriting them'
+                     --indent=[N] 'Number of spaces in a level of indentation'
+                     --inline-width=[N] 'Maximum width of a pattern on the line of its identifier'
+                     <FILE>... 'FTL files to format'",
+                )
+                .arg(
+                    Arg::from_usage(
+                        "--select-layout=[LAYOUT] 'Layout of patterns with a select expression'",
+                    )
+                    .possible_values(&["block", "inline"]),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("lint")
+                .about("Checks FTL files for common problems")
+                .args_from_usage(
+                    "--json 'Prints the lints as JSON'
+                     --list-rules 'Lists the rules and whether they are enabled'
+                     [FILE]... 'FTL files to lint together'",
+                )
+                .arg(
+                    Arg::from_usage("--enable=[RULE] 'Enables a rule'")
+                        .multiple(true)
+                        .number_of_values(1),
+                )
+                .arg(
+                    Arg::from_usage("--disable=[RULE] 'Disables a rule'")
+                        .multiple(true)
+                        .number_of_values(1),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("compare")
+                .about("Compares a translation with its reference locale")
+                .args_from_usage(
+                    "<REFERENCE> 'FTL file or directory of the reference locale'
+                     <TRANSLATION> 'FTL file or directory of the translation'",
+                ),
+        )
+        .get_matches();
+
+    let success = match matches.subcommand() {
+        ("fmt", Some(matches)) => format_files(
+            matches.values_of("FILE").unwrap(),
+            format_options(matches),
+            matches.is_present("check"),
+        ),
+        ("lint", Some(matches)) => {
+            let linter = linter(matches);
+            if matches.is_present("list-rules") {
+                list_rules(&linter);
+                true
+            } else {
+                lint_files(
+                    matches.values_of("FILE").into_iter().flatten(),
+                    &linter,
+                    matches.is_present("json"),
+                )
+            }
+        }
+        ("compare", Some(matches)) => compare_paths(
+            matches.value_of("REFERENCE").unwrap(),
+            matches.value_of("TRANSLATION").unwrap(),
+        ),
+        _ => unreachable!(),
+    };
+    if !success {
+        process::exit(1);
+    }
+}
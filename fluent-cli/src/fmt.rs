@@ -0,0 +1,100 @@
+use std::fs;
+use std::io;
+
+use fluent_syntax::parser::{parse, ParserError};
+use fluent_syntax::serializer::{serialize_with_options, Options};
+
+/// The outcome of formatting a single file.
+#[derive(Debug)]
+pub enum Status {
+    /// The file is already formatted.
+    Unchanged,
+    /// The file is not formatted, and has been rewritten unless checking.
+    Formatted,
+    /// The file has syntax errors and has been left untouched.
+    Invalid(Vec<ParserError>),
+}
+
+/// Formats `source` with the serializer.
+///
+/// Sources with syntax errors are not formatted, because the serializer would
+/// have to either drop or copy the invalid parts.
+pub fn format_source(source: &str, options: Options) -> Result<String, Vec<ParserError>> {
+    let res = parse(source).map_err(|(_, errors)| errors)?;
+    Ok(serialize_with_options(&res, options))
+}
+
+/// Formats the file at `path`, rewriting it in place unless `check` is set.
+pub fn format_file(path: &str, options: Options, check: bool) -> Result<Status, io::Error> {
+    let source = fs::read_to_string(path)?;
+    match format_source(&source, options) {
+        Ok(formatted) if formatted == source => Ok(Status::Unchanged),
+        Ok(formatted) => {
+            if !check {
+                fs::write(path, formatted)?;
+            }
+            Ok(Status::Formatted)
+        }
+        Err(errors) => {
+            for err in &errors {
+                eprintln!("{}", err.diagnostic(&source).with_origin(path));
+            }
+            Ok(Status::Invalid(errors))
+        }
+    }
+}
+
+/// Formats the files at `paths`, reporting the files which were reformatted,
+/// or with `check`, the files which would be.
+///
+/// Returns `false` if any file has syntax errors or couldn't be read or
+/// written, or with `check`, if any file isn't formatted.
+pub fn format_files<'p>(
+    paths: impl IntoIterator<Item = &'p str>,
+    options: Options,
+    check: bool,
+) -> bool {
+    let mut success = true;
+    for path in paths {
+        match format_file(path, options, check) {
+            Ok(Status::Unchanged) => {}
+            Ok(Status::Formatted) if check => {
+                println!("Would reformat: {}", path);
+                success = false;
+            }
+            Ok(Status::Formatted) => println!("Reformatted: {}", path),
+            Ok(Status::Invalid(_)) => {
+                eprintln!("Skipped {} because of syntax errors", path);
+                success = false;
+            }
+            Err(err) => {
+                eprintln!("Failed to format {}: {}", path, err);
+                success = false;
+            }
+        }
+    }
+    success
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_source_normalizes() {
+        let source = "key=Value\n-term =   Term\n";
+        let formatted = format_source(source, Options::default()).expect("Failed to format");
+        assert_eq!(formatted, "key = Value\n-term = Term\n");
+        assert_eq!(
+            format_source(&formatted, Options::default()).as_deref(),
+            Ok(formatted.as_str())
+        );
+    }
+
+    #[test]
+    fn format_source_rejects_errors() {
+        let errors = format_source("key = { Value\n", Options::default())
+            .expect_err("Expected syntax errors");
+        assert_eq!(errors.len(), 1);
+    }
+}
@@ -0,0 +1,206 @@
+//! A linter for Fluent Translation Lists with a pluggable set of [`Rule`]s.
+mod rules;
+
+use std::fs;
+use std::ops::Range;
+
+use fluent_syntax::ast;
+use fluent_syntax::parser::{parse_with_spans, LineColumn, ParserError, Spanned, Spans};
+use serde::Serialize;
+
+pub use rules::{
+    DuplicateId, InconsistentVariables, SelectWithoutDefault, Syntax, TrailingWhitespace,
+    UndefinedReference, UnreferencedTerm,
+};
+
+/// A parsed FTL file to lint.
+pub struct SourceFile<'s> {
+    pub path: String,
+    pub source: &'s str,
+    pub resource: ast::Resource<&'s str>,
+    pub errors: Vec<ParserError>,
+    pub spans: Spans,
+}
+
+impl<'s> SourceFile<'s> {
+    pub fn parse(path: impl Into<String>, source: &'s str) -> Self {
+        let (result, spans) = parse_with_spans(source);
+        let (resource, errors) = match result {
+            Ok(resource) => (resource, vec![]),
+            Err(result) => result,
+        };
+        Self {
+            path: path.into(),
+            source,
+            resource,
+            errors,
+            spans,
+        }
+    }
+
+    /// Returns the span of `node`, a node of the entry at `index`.
+    pub fn span<N: Spanned>(&self, index: usize, node: &N) -> Option<Range<usize>> {
+        self.spans.get(index, &self.resource.body[index], node)
+    }
+
+    /// Creates a [`Lint`] of `rule` for the `span` of this file.
+    pub fn lint(&self, rule: &dyn Rule, span: Range<usize>, message: String) -> Lint {
+        let LineColumn { line, column } = LineColumn::from_offset(self.source, span.start);
+        Lint {
+            rule: rule.name(),
+            path: self.path.clone(),
+            line,
+            column,
+            span,
+            message,
+        }
+    }
+}
+
+/// A problem reported by a [`Rule`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Lint {
+    pub rule: &'static str,
+    pub path: String,
+    pub line: usize,
+    pub column: usize,
+    pub span: Range<usize>,
+    pub message: String,
+}
+
+impl std::fmt::Display for Lint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: [{}] {}",
+            self.path, self.line, self.column, self.rule, self.message
+        )
+    }
+}
+
+/// A check over a set of files.
+///
+/// Rules get all of the files at once, so that they can check references and
+/// ids across files.
+pub trait Rule {
+    /// The name used to enable or disable the rule.
+    fn name(&self) -> &'static str;
+
+    /// A short description of what the rule reports.
+    fn description(&self) -> &'static str;
+
+    /// Whether the rule runs unless it's disabled.
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    fn check(&self, files: &[SourceFile], lints: &mut Vec<Lint>);
+}
+
+/// Runs a set of rules, each of which can be enabled or disabled.
+pub struct Linter {
+    rules: Vec<(Box<dyn Rule>, bool)>,
+}
+
+impl Default for Linter {
+    /// Creates a linter with the built-in rules.
+    fn default() -> Self {
+        let mut linter = Self::empty();
+        linter.add_rule(Box::new(Syntax));
+        linter.add_rule(Box::new(SelectWithoutDefault));
+        linter.add_rule(Box::new(UndefinedReference));
+        linter.add_rule(Box::new(UnreferencedTerm));
+        linter.add_rule(Box::new(DuplicateId));
+        linter.add_rule(Box::new(InconsistentVariables));
+        linter.add_rule(Box::new(TrailingWhitespace));
+        linter
+    }
+}
+
+impl Linter {
+    /// Creates a linter without any rules.
+    pub fn empty() -> Self {
+        Self { rules: vec![] }
+    }
+
+    /// Adds `rule`, enabled according to [`Rule::enabled_by_default`].
+    pub fn add_rule(&mut self, rule: Box<dyn Rule>) {
+        let enabled = rule.enabled_by_default();
+        self.rules.push((rule, enabled));
+    }
+
+    /// Enables or disables the rule called `name`. Returns `false` if there's
+    /// no such rule.
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        let mut found = false;
+        for (rule, rule_enabled) in &mut self.rules {
+            if rule.name() == name {
+                *rule_enabled = enabled;
+                found = true;
+            }
+        }
+        found
+    }
+
+    /// Returns the rules, and whether each of them is enabled.
+    pub fn rules(&self) -> impl Iterator<Item = (&dyn Rule, bool)> {
+        self.rules
+            .iter()
+            .map(|(rule, enabled)| (rule.as_ref(), *enabled))
+    }
+
+    /// Runs the enabled rules over `files`, returning the lints ordered by
+    /// their position.
+    pub fn lint(&self, files: &[SourceFile]) -> Vec<Lint> {
+        let mut lints = vec![];
+        for (rule, enabled) in self.rules() {
+            if enabled {
+                rule.check(files, &mut lints);
+            }
+        }
+        let order = |path: &str| files.iter().position(|file| file.path == path);
+        lints.sort_by(|a, b| {
+            (order(&a.path), a.span.start, a.rule).cmp(&(order(&b.path), b.span.start, b.rule))
+        });
+        lints
+    }
+}
+
+/// Lints the files at `paths`, printing the lints in a human-readable form, or
+/// as a JSON array with `json`.
+///
+/// Returns `false` if there are any lints, or if any file couldn't be read.
+pub fn lint_files<'p>(
+    paths: impl IntoIterator<Item = &'p str>,
+    linter: &Linter,
+    json: bool,
+) -> bool {
+    let mut success = true;
+    let mut sources = vec![];
+    for path in paths {
+        match fs::read_to_string(path) {
+            Ok(source) => sources.push((path, source)),
+            Err(err) => {
+                eprintln!("Failed to read {}: {}", path, err);
+                success = false;
+            }
+        }
+    }
+    let files: Vec<_> = sources
+        .iter()
+        .map(|(path, source)| SourceFile::parse(*path, source))
+        .collect();
+
+    let lints = linter.lint(&files);
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&lints).expect("Serializing JSON failed.")
+        );
+    } else {
+        for lint in &lints {
+            println!("{}", lint);
+        }
+    }
+    success && lints.is_empty()
+}
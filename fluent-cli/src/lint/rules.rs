@@ -0,0 +1,441 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use fluent_syntax::ast;
+use fluent_syntax::parser::{ErrorKind, LineColumn};
+use fluent_syntax::walk::{walk_pattern, Node};
+
+use super::{Lint, Rule, SourceFile};
+
+/// Reports syntax errors.
+pub struct Syntax;
+
+impl Rule for Syntax {
+    fn name(&self) -> &'static str {
+        "syntax"
+    }
+
+    fn description(&self) -> &'static str {
+        "Syntax errors"
+    }
+
+    fn check(&self, files: &[SourceFile], lints: &mut Vec<Lint>) {
+        for file in files {
+            for err in &file.errors {
+                if err.kind != ErrorKind::MissingDefaultVariant {
+                    lints.push(file.lint(self, err.pos.clone(), err.kind.to_string()));
+                }
+            }
+        }
+    }
+}
+
+/// Reports select expressions without a default variant.
+pub struct SelectWithoutDefault;
+
+impl Rule for SelectWithoutDefault {
+    fn name(&self) -> &'static str {
+        "select-without-default"
+    }
+
+    fn description(&self) -> &'static str {
+        "Select expressions without a default variant"
+    }
+
+    fn check(&self, files: &[SourceFile], lints: &mut Vec<Lint>) {
+        // The parser rejects such select expressions, so they never reach the AST.
+        for file in files {
+            for err in &file.errors {
+                if err.kind == ErrorKind::MissingDefaultVariant {
+                    let message = "Select expression has no default variant".to_owned();
+                    lints.push(file.lint(self, err.pos.clone(), message));
+                }
+            }
+        }
+    }
+}
+
+/// Reports references to messages, terms and attributes which aren't defined
+/// in any of the files.
+pub struct UndefinedReference;
+
+impl Rule for UndefinedReference {
+    fn name(&self) -> &'static str {
+        "undefined-reference"
+    }
+
+    fn description(&self) -> &'static str {
+        "References to undefined messages, terms and attributes"
+    }
+
+    fn check(&self, files: &[SourceFile], lints: &mut Vec<Lint>) {
+        let mut defined: HashMap<String, HashSet<&str>> = HashMap::new();
+        for file in files {
+            for entry in &file.resource.body {
+                if let Some((id, _)) = entry_id(entry) {
+                    let attributes = defined.entry(id).or_default();
+                    for (attribute, _) in entry_patterns(entry) {
+                        if let Some(attribute) = attribute {
+                            attributes.insert(attribute.name);
+                        }
+                    }
+                }
+            }
+        }
+
+        for file in files {
+            for (index, pattern) in file_patterns(file) {
+                walk_pattern(pattern, &mut |node| {
+                    let Node::Inline(reference, _) = node else {
+                        return;
+                    };
+                    let (id, attribute) = match reference {
+                        ast::InlineExpression::MessageReference { id, attribute } => {
+                            (id.name.to_owned(), attribute)
+                        }
+                        ast::InlineExpression::TermReference { id, attribute, .. } => {
+                            (format!("-{}", id.name), attribute)
+                        }
+                        _ => return,
+                    };
+                    let kind = if id.starts_with('-') {
+                        "term"
+                    } else {
+                        "message"
+                    };
+                    let message = match (defined.get(&id), attribute) {
+                        (None, _) => format!("Unknown {} `{}`", kind, id),
+                        (Some(attributes), Some(attribute))
+                            if !attributes.contains(attribute.name) =>
+                        {
+                            format!("Unknown attribute `{}.{}`", id, attribute.name)
+                        }
+                        _ => return,
+                    };
+                    let span = file.span(index, reference).unwrap_or_default();
+                    lints.push(file.lint(self, span, message));
+                });
+            }
+        }
+    }
+}
+
+/// Reports terms which aren't referenced in any of the files.
+pub struct UnreferencedTerm;
+
+impl Rule for UnreferencedTerm {
+    fn name(&self) -> &'static str {
+        "unreferenced-term"
+    }
+
+    fn description(&self) -> &'static str {
+        "Terms which are never referenced"
+    }
+
+    fn check(&self, files: &[SourceFile], lints: &mut Vec<Lint>) {
+        let mut referenced = HashSet::new();
+        for file in files {
+            for (_, pattern) in file.resource.body.iter().flat_map(entry_patterns) {
+                walk_pattern(pattern, &mut |node| {
+                    if let Node::Inline(ast::InlineExpression::TermReference { id, .. }, _) = node {
+                        referenced.insert(id.name);
+                    }
+                });
+            }
+        }
+
+        for file in files {
+            for (index, entry) in file.resource.body.iter().enumerate() {
+                if let ast::Entry::Term(term) = entry {
+                    if !referenced.contains(term.id.name) {
+                        let span = file.span(index, &term.id).unwrap_or_default();
+                        let message = format!("Term `-{}` is never referenced", term.id.name);
+                        lints.push(file.lint(self, span, message));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Reports messages and terms defined more than once, in the same file or
+/// across files.
+pub struct DuplicateId;
+
+impl Rule for DuplicateId {
+    fn name(&self) -> &'static str {
+        "duplicate-id"
+    }
+
+    fn description(&self) -> &'static str {
+        "Messages and terms defined more than once"
+    }
+
+    fn check(&self, files: &[SourceFile], lints: &mut Vec<Lint>) {
+        let mut defined = HashMap::new();
+        for file in files {
+            for (index, entry) in file.resource.body.iter().enumerate() {
+                let (id, identifier) = match entry_id(entry) {
+                    Some(id) => id,
+                    None => continue,
+                };
+                let span = file.span(index, identifier).unwrap_or_default();
+                let position = LineColumn::from_offset(file.source, span.start);
+                match defined.get(&id) {
+                    Some((path, position)) => {
+                        let message =
+                            format!("`{}` is already defined at {}:{}", id, path, position);
+                        lints.push(file.lint(self, span, message));
+                    }
+                    None => {
+                        defined.insert(id, (&file.path, position));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Reports variables which are used in some of the patterns of a message or
+/// a term, but not in others which use variables too.
+pub struct InconsistentVariables;
+
+impl Rule for InconsistentVariables {
+    fn name(&self) -> &'static str {
+        "inconsistent-variables"
+    }
+
+    fn description(&self) -> &'static str {
+        "Variables used in some of the value and attributes of an entry but not in others"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn check(&self, files: &[SourceFile], lints: &mut Vec<Lint>) {
+        for file in files {
+            for (index, entry) in file.resource.body.iter().enumerate() {
+                let (id, _) = match entry_id(entry) {
+                    Some(id) => id,
+                    None => continue,
+                };
+                let patterns: Vec<_> = entry_patterns(entry)
+                    .into_iter()
+                    .map(|(attribute, pattern)| (attribute, pattern, variables(pattern)))
+                    .filter(|(_, _, variables)| !variables.is_empty())
+                    .collect();
+                let all: BTreeSet<&str> = patterns
+                    .iter()
+                    .flat_map(|(_, _, variables)| variables.iter().copied())
+                    .collect();
+
+                for (attribute, pattern, variables) in &patterns {
+                    let name = match attribute {
+                        Some(attribute) => format!("{}.{}", id, attribute.name),
+                        None => id.clone(),
+                    };
+                    for variable in all.difference(variables) {
+                        let message = format!(
+                            "`{}` doesn't use `${}`, which other patterns of `{}` use",
+                            name, variable, id
+                        );
+                        let span = file.span(index, *pattern).unwrap_or_default();
+                        lints.push(file.lint(self, span, message));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Reports lines of patterns which end with whitespace.
+pub struct TrailingWhitespace;
+
+impl Rule for TrailingWhitespace {
+    fn name(&self) -> &'static str {
+        "trailing-whitespace"
+    }
+
+    fn description(&self) -> &'static str {
+        "Trailing whitespace in patterns"
+    }
+
+    fn check(&self, files: &[SourceFile], lints: &mut Vec<Lint>) {
+        for file in files {
+            for (index, pattern) in file_patterns(file) {
+                let span = match file.span(index, pattern) {
+                    Some(span) => span,
+                    None => continue,
+                };
+                // The parser leaves the whitespace at the end of the pattern out
+                // of its span.
+                let end = file.source[span.end..]
+                    .find('\n')
+                    .map_or(file.source.len(), |idx| span.end + idx);
+
+                let mut line_start = span.start;
+                for line in file.source[span.start..end].split('\n') {
+                    let content = line.strip_suffix('\r').unwrap_or(line);
+                    let trimmed = content.trim_end_matches([' ', '\t']);
+                    if trimmed.len() < content.len() {
+                        let start = line_start + trimmed.len();
+                        let end = line_start + content.len();
+                        lints.push(file.lint(self, start..end, "Trailing whitespace".to_owned()));
+                    }
+                    line_start += line.len() + 1;
+                }
+            }
+        }
+    }
+}
+
+/// Returns the id of a message or a term, with a leading `-` for terms.
+fn entry_id<'a, 's>(
+    entry: &'a ast::Entry<&'s str>,
+) -> Option<(String, &'a ast::Identifier<&'s str>)> {
+    match entry {
+        ast::Entry::Message(message) => Some((message.id.name.to_owned(), &message.id)),
+        ast::Entry::Term(term) => Some((format!("-{}", term.id.name), &term.id)),
+        _ => None,
+    }
+}
+
+type EntryPattern<'a, 's> = (
+    Option<&'a ast::Identifier<&'s str>>,
+    &'a ast::Pattern<&'s str>,
+);
+
+/// Returns the value and the attributes of a message or a term.
+fn entry_patterns<'a, 's>(entry: &'a ast::Entry<&'s str>) -> Vec<EntryPattern<'a, 's>> {
+    let (value, attributes) = match entry {
+        ast::Entry::Message(message) => (message.value.as_ref(), &message.attributes),
+        ast::Entry::Term(term) => (Some(&term.value), &term.attributes),
+        _ => return vec![],
+    };
+    value
+        .map(|value| (None, value))
+        .into_iter()
+        .chain(
+            attributes
+                .iter()
+                .map(|attribute| (Some(&attribute.id), &attribute.value)),
+        )
+        .collect()
+}
+
+/// Returns the values and the attributes of the messages and terms of `file`,
+/// with the index of their entry.
+fn file_patterns<'a, 's>(
+    file: &'a SourceFile<'s>,
+) -> impl Iterator<Item = (usize, &'a ast::Pattern<&'s str>)> {
+    file.resource
+        .body
+        .iter()
+        .enumerate()
+        .flat_map(|(index, entry)| {
+            entry_patterns(entry)
+                .into_iter()
+                .map(move |(_, pattern)| (index, pattern))
+        })
+}
+
+fn variables<'s>(pattern: &ast::Pattern<&'s str>) -> BTreeSet<&'s str> {
+    let mut variables = BTreeSet::new();
+    walk_pattern(pattern, &mut |node| {
+        if let Node::Inline(ast::InlineExpression::VariableReference { id }, _) = node {
+            variables.insert(id.name);
+        }
+    });
+    variables
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Linter;
+    use super::*;
+
+    fn lint(sources: &[(&str, &str)], rule: &str) -> Vec<String> {
+        let files: Vec<_> = sources
+            .iter()
+            .map(|(path, source)| SourceFile::parse(*path, source))
+            .collect();
+        let mut linter = Linter::default();
+        for (name, _) in linter
+            .rules()
+            .map(|(rule, enabled)| (rule.name(), enabled))
+            .collect::<Vec<_>>()
+        {
+            linter.set_enabled(name, name == rule);
+        }
+        linter
+            .lint(&files)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn syntax_and_select_without_default() {
+        let source = "key = { $num ->\n    [one] One\n}\nkey2 = { Value\n";
+        assert_eq!(
+            lint(&[("a.ftl", source)], "select-without-default"),
+            ["a.ftl:3:1: [select-without-default] Select expression has no default variant"]
+        );
+        assert_eq!(
+            lint(&[("a.ftl", source)], "syntax"),
+            ["a.ftl:5:1: [syntax] Expected a token starting with \"}\""]
+        );
+    }
+
+    #[test]
+    fn references() {
+        let a = "-term = Term\n    .attr = Attr\n-unused = Unused\nkey = { -term } { other }\n";
+        let b = "other = { -term.missing ->\n   *[a] { key.attr } { missing }\n}\n";
+        assert_eq!(
+            lint(&[("a.ftl", a), ("b.ftl", b)], "undefined-reference"),
+            [
+                "b.ftl:1:11: [undefined-reference] Unknown attribute `-term.missing`",
+                "b.ftl:2:11: [undefined-reference] Unknown attribute `key.attr`",
+                "b.ftl:2:24: [undefined-reference] Unknown message `missing`",
+            ]
+        );
+        assert_eq!(
+            lint(&[("a.ftl", a), ("b.ftl", b)], "unreferenced-term"),
+            ["a.ftl:3:2: [unreferenced-term] Term `-unused` is never referenced"]
+        );
+    }
+
+    #[test]
+    fn duplicate_ids() {
+        let a = "key = Value\n-key = Term\n";
+        let b = "key = Other\n";
+        assert_eq!(
+            lint(&[("a.ftl", a), ("b.ftl", b)], "duplicate-id"),
+            ["b.ftl:1:1: [duplicate-id] `key` is already defined at a.ftl:1:1"]
+        );
+    }
+
+    #[test]
+    fn inconsistent_variables() {
+        let source = "key = { $count } items\n    .title = { $num } items\n    .label = Items\n";
+        assert_eq!(
+            lint(&[("a.ftl", source)], "inconsistent-variables"),
+            [
+                "a.ftl:1:7: [inconsistent-variables] `key` doesn't use `$num`, which other patterns of `key` use",
+                "a.ftl:2:14: [inconsistent-variables] `key.title` doesn't use `$count`, which other patterns of `key` use",
+            ]
+        );
+    }
+
+    #[test]
+    fn trailing_whitespace() {
+        let source = "key = Value  \nkey2 =\n    Line 1 \n    Line 2\n";
+        assert_eq!(
+            lint(&[("a.ftl", source)], "trailing-whitespace"),
+            [
+                "a.ftl:1:12: [trailing-whitespace] Trailing whitespace",
+                "a.ftl:3:11: [trailing-whitespace] Trailing whitespace",
+            ]
+        );
+    }
+}
@@ -0,0 +1,453 @@
+//! Fluent is a modern localization system designed to improve how software is translated.
+//!
+//! The `fluent-codegen` crate generates typed accessors for the messages of reference
+//! FTL files at build time. Each message value and attribute gets a function taking one
+//! argument per variable it uses, so that typos in ids and missing arguments become
+//! compile errors rather than runtime ones.
+//!
+//! The arguments are typed from how the variables are used:
+//!
+//!   - Variables passed to `NUMBER`, or used as the selector of variants keyed by numbers
+//!     or plural categories, take an `impl Into<FluentNumber>`.
+//!   - Other variables passed to functions take an `impl Into<FluentValue>`.
+//!   - All other variables take an `impl Into<Cow<str>>`.
+//!
+//! The functions return a `Message`, or an `Attribute` for attributes, which can be
+//! formatted with a [`FluentBundle`], or passed by id and
+//! arguments to `fluent_fallback`.
+//!
+//! # Example
+//!
+//! In `build.rs`, generate the module from the reference locale:
+//!
+//! ```no_run
+//! use std::env;
+//! use std::path::Path;
+//!
+//! let out = Path::new(&env::var("OUT_DIR").unwrap()).join("messages.rs");
+//! fluent_codegen::write_module(&["locales/en-US"], out, &Default::default())
+//!     .expect("Failed to generate the messages.");
+//! ```
+//!
+//! And include it in the crate, allowing the accessors which aren't used (yet):
+//!
+//! ```ignore
+//! #[allow(dead_code)]
+//! mod messages {
+//!     include!(concat!(env!("OUT_DIR"), "/messages.rs"));
+//! }
+//!
+//! let mut errors = vec![];
+//! let value = messages::emails(5, "Jane").format(&bundle, &mut errors);
+//! ```
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use fluent_bundle::analysis::VariableUsage;
+use fluent_bundle::{FluentBundle, FluentError, FluentResource};
+use fluent_syntax::ast;
+use fluent_syntax::parser::ParserError;
+use thiserror::Error;
+
+/// Errors generated while generating the accessors.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Error while reading an FTL file or writing the module
+    #[error("Failed to access {}: {source}", .path.display())]
+    Io { path: PathBuf, source: io::Error },
+
+    /// Error while parsing an FTL file
+    #[error("Failed to parse {}: {}", .path.display(), .errors[0])]
+    Parse {
+        path: PathBuf,
+        errors: Vec<ParserError>,
+    },
+
+    /// Error while adding the resources to a bundle, such as a duplicate id
+    #[error("{0}")]
+    Fluent(FluentError),
+
+    /// Two messages, attributes or variables map to the same Rust identifier
+    #[error("`{first}` and `{second}` both generate `{name}`")]
+    Collision {
+        name: String,
+        first: String,
+        second: String,
+    },
+}
+
+/// Options of the generated module.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// The path of the crate providing the `fluent-bundle` API to the generated code.
+    ///
+    /// Defaults to `::fluent_bundle`. Crates using the `fluent` crate instead can use
+    /// `::fluent`.
+    pub bundle_crate: String,
+    /// The path of the `fluent-fallback` crate, to generate conversions of messages into
+    /// its `L10nKey`.
+    ///
+    /// Defaults to `None`, which generates no conversions.
+    pub fallback_crate: Option<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            bundle_crate: "::fluent_bundle".to_string(),
+            fallback_crate: None,
+        }
+    }
+}
+
+/// Generates the source of a module with accessors for the messages of `resources`.
+pub fn generate(resources: &[FluentResource], options: &Options) -> Result<String, Error> {
+    let mut bundle = FluentBundle::default();
+    for resource in resources {
+        bundle
+            .add_resource(resource)
+            .map_err(|mut errors| Error::Fluent(errors.remove(0)))?;
+    }
+
+    let mut names = Names::default();
+    let mut functions = String::new();
+    for entry in resources.iter().flat_map(FluentResource::entries) {
+        let message = match entry {
+            ast::Entry::Message(message) => message,
+            _ => continue,
+        };
+        let id = message.id.name;
+        if let Some(value) = &message.value {
+            let name = names.insert(rust_ident(id), id)?;
+            let variables = bundle.pattern_variables(value);
+            write_function(&mut functions, options, &name, id, None, &variables)?;
+        }
+        for attribute in &message.attributes {
+            let attr = attribute.id.name;
+            let path = format!("{}.{}", id, attr);
+            // Joined before converting, so that keywords are escaped on the whole name.
+            let name = names.insert(rust_ident(&format!("{}-{}", id, attr)), &path)?;
+            let variables = bundle.pattern_variables(&attribute.value);
+            write_function(&mut functions, options, &name, id, Some(attr), &variables)?;
+        }
+    }
+
+    let mut module = String::new();
+    write_prelude(&mut module, options);
+    module.push_str(&functions);
+    Ok(module)
+}
+
+/// Generates the source of a module with accessors for the messages of the FTL files
+/// at `paths`, which are either files, or directories searched recursively for `.ftl`
+/// files.
+pub fn generate_from_paths<P: AsRef<Path>>(
+    paths: &[P],
+    options: &Options,
+) -> Result<String, Error> {
+    let mut resources = vec![];
+    for path in ftl_files(paths)? {
+        let source = fs::read_to_string(&path).map_err(|source| Error::Io {
+            path: path.clone(),
+            source,
+        })?;
+        let resource =
+            FluentResource::try_new(source).map_err(|(_, errors)| Error::Parse { path, errors })?;
+        resources.push(resource);
+    }
+    generate(&resources, options)
+}
+
+/// Generates the module for the FTL files at `paths` into the file at `out`, as in
+/// [`generate_from_paths`].
+///
+/// This is meant to be called from a build script, so it also tells Cargo to run the
+/// build script again when any of `paths` changes.
+pub fn write_module<P: AsRef<Path>>(
+    paths: &[P],
+    out: impl AsRef<Path>,
+    options: &Options,
+) -> Result<(), Error> {
+    for path in paths {
+        println!("cargo:rerun-if-changed={}", path.as_ref().display());
+    }
+    let module = generate_from_paths(paths, options)?;
+    let out = out.as_ref();
+    fs::write(out, module).map_err(|source| Error::Io {
+        path: out.to_path_buf(),
+        source,
+    })
+}
+
+/// Returns the FTL files at `paths`, in a stable order.
+fn ftl_files<P: AsRef<Path>>(paths: &[P]) -> Result<Vec<PathBuf>, Error> {
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| Error::Io { path, source }
+    };
+
+    let mut files = vec![];
+    for path in paths {
+        let path = path.as_ref();
+        if !path.is_dir() {
+            files.push(path.to_path_buf());
+            continue;
+        }
+        let mut found = vec![];
+        let mut dirs = vec![path.to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            for entry in fs::read_dir(&dir).map_err(io_error(&dir))? {
+                let entry = entry.map_err(io_error(&dir))?;
+                let path = entry.path();
+                if path.is_dir() {
+                    // Symlinked directories are skipped, since they may form a loop.
+                    if !entry.file_type().map_err(io_error(&dir))?.is_symlink() {
+                        dirs.push(path);
+                    }
+                } else if path.extension().map_or(false, |ext| ext == "ftl") {
+                    found.push(path);
+                }
+            }
+        }
+        found.sort();
+        files.extend(found);
+    }
+    Ok(files)
+}
+
+/// The Rust identifiers in use, and the FTL names they were generated from.
+#[derive(Default)]
+struct Names(BTreeMap<String, String>);
+
+impl Names {
+    fn insert(&mut self, name: String, source: &str) -> Result<String, Error> {
+        if let Some(first) = self.0.get(&name) {
+            return Err(Error::Collision {
+                name,
+                first: first.clone(),
+                second: source.to_string(),
+            });
+        }
+        self.0.insert(name.clone(), source.to_string());
+        Ok(name)
+    }
+}
+
+/// The keywords which can't be used as identifiers, nor as raw identifiers.
+const PATH_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// Converts an FTL identifier, such as `emailCount` or `new-tab`, into a snake case Rust
+/// identifier.
+fn rust_ident(id: &str) -> String {
+    let mut ident = String::with_capacity(id.len());
+    let mut previous_lowercase = false;
+    for ch in id.chars() {
+        if ch == '-' {
+            ident.push('_');
+        } else if ch.is_ascii_uppercase() {
+            if previous_lowercase {
+                ident.push('_');
+            }
+            ident.push(ch.to_ascii_lowercase());
+        } else {
+            ident.push(ch);
+        }
+        previous_lowercase = ch.is_ascii_lowercase() || ch.is_ascii_digit();
+    }
+
+    if PATH_KEYWORDS.contains(&ident.as_str()) {
+        ident.push('_');
+    } else if KEYWORDS.contains(&ident.as_str()) {
+        ident.insert_str(0, "r#");
+    }
+    ident
+}
+
+const PLURAL_CATEGORIES: &[&str] = &["zero", "one", "two", "few", "many"];
+
+/// The type of the argument of a variable.
+enum ArgumentType {
+    Number,
+    Value,
+    String,
+}
+
+impl ArgumentType {
+    fn new(usage: &VariableUsage) -> Self {
+        let plural = usage
+            .selector_keys
+            .iter()
+            .any(|key| PLURAL_CATEGORIES.contains(&key.as_str()) || key.parse::<f64>().is_ok());
+        if plural || usage.functions.contains("NUMBER") {
+            Self::Number
+        } else if !usage.functions.is_empty() {
+            Self::Value
+        } else {
+            Self::String
+        }
+    }
+}
+
+fn write_prelude(out: &mut String, options: &Options) {
+    let bundle = &options.bundle_crate;
+    let _ = write!(
+        out,
+        r#"// This file is generated by fluent-codegen. Do not edit.
+
+/// A message value with its arguments.
+#[derive(Debug)]
+pub struct Message<'a> {{
+    pub id: &'static str,
+    pub args: Option<{bundle}::FluentArgs<'a>>,
+}}
+
+impl<'a> Message<'a> {{
+    /// Formats the message with `bundle`, or returns `None` if the bundle doesn't have
+    /// a value for it.
+    pub fn format<'b, R, M>(
+        &self,
+        bundle: &'b {bundle}::bundle::FluentBundle<R, M>,
+        errors: &mut Vec<{bundle}::FluentError>,
+    ) -> Option<::std::borrow::Cow<'b, str>>
+    where
+        R: ::std::borrow::Borrow<{bundle}::FluentResource>,
+        M: {bundle}::memoizer::MemoizerKind,
+    {{
+        let pattern = bundle.get_message(self.id)?.value()?;
+        Some(bundle.format_pattern(pattern, self.args.as_ref(), errors))
+    }}
+}}
+
+/// A message attribute with its arguments.
+#[derive(Debug)]
+pub struct Attribute<'a> {{
+    pub id: &'static str,
+    pub attribute: &'static str,
+    pub args: Option<{bundle}::FluentArgs<'a>>,
+}}
+
+impl<'a> Attribute<'a> {{
+    /// Formats the attribute with `bundle`, or returns `None` if the bundle doesn't have
+    /// it.
+    pub fn format<'b, R, M>(
+        &self,
+        bundle: &'b {bundle}::bundle::FluentBundle<R, M>,
+        errors: &mut Vec<{bundle}::FluentError>,
+    ) -> Option<::std::borrow::Cow<'b, str>>
+    where
+        R: ::std::borrow::Borrow<{bundle}::FluentResource>,
+        M: {bundle}::memoizer::MemoizerKind,
+    {{
+        let message = bundle.get_message(self.id)?;
+        let pattern = message.get_attribute(self.attribute)?.value();
+        Some(bundle.format_pattern(pattern, self.args.as_ref(), errors))
+    }}
+}}
+"#
+    );
+
+    if let Some(fallback) = &options.fallback_crate {
+        let _ = write!(
+            out,
+            r#"
+impl<'a> From<Message<'a>> for {fallback}::types::L10nKey<'a> {{
+    fn from(message: Message<'a>) -> Self {{
+        Self {{
+            id: message.id.into(),
+            args: message.args,
+        }}
+    }}
+}}
+"#
+        );
+    }
+}
+
+fn write_function(
+    out: &mut String,
+    options: &Options,
+    name: &str,
+    id: &str,
+    attribute: Option<&str>,
+    variables: &BTreeMap<String, VariableUsage>,
+) -> Result<(), Error> {
+    let bundle = &options.bundle_crate;
+    let mut params = Names::default();
+    let mut arguments = vec![];
+    for (variable, usage) in variables {
+        let param = params.insert(rust_ident(variable), &format!("${}", variable))?;
+        arguments.push((variable, param, ArgumentType::new(usage)));
+    }
+
+    let _ = match attribute {
+        Some(attribute) => writeln!(out, "\n/// The attribute `{}.{}`.", id, attribute),
+        None => writeln!(out, "\n/// The message `{}`.", id),
+    };
+    let (return_type, lifetime) = match (attribute, arguments.is_empty()) {
+        (Some(_), true) => ("Attribute<'static>", ""),
+        (Some(_), false) => ("Attribute<'a>", "<'a>"),
+        (None, true) => ("Message<'static>", ""),
+        (None, false) => ("Message<'a>", "<'a>"),
+    };
+    let _ = write!(out, "pub fn {}{}(", name, lifetime);
+    for (i, (_, param, ty)) in arguments.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        let _ = match ty {
+            ArgumentType::Number => {
+                write!(out, "{}: impl Into<{}::types::FluentNumber>", param, bundle)
+            }
+            ArgumentType::Value => write!(out, "{}: impl Into<{}::FluentValue<'a>>", param, bundle),
+            ArgumentType::String => {
+                write!(out, "{}: impl Into<::std::borrow::Cow<'a, str>>", param)
+            }
+        };
+    }
+    let _ = writeln!(out, ") -> {} {{", return_type);
+
+    let _ = match attribute {
+        Some(attribute) => writeln!(
+            out,
+            "    Attribute {{\n        id: {:?},\n        attribute: {:?},",
+            id, attribute
+        ),
+        None => writeln!(out, "    Message {{\n        id: {:?},", id),
+    };
+    if arguments.is_empty() {
+        out.push_str("        args: None,\n");
+    } else {
+        out.push_str("        args: Some(::std::iter::FromIterator::from_iter([\n");
+        for (variable, param, ty) in &arguments {
+            let _ = match ty {
+                ArgumentType::Number => writeln!(
+                    out,
+                    "            ({:?}, {}::FluentValue::Number({}.into())),",
+                    variable, bundle, param
+                ),
+                ArgumentType::Value => {
+                    writeln!(out, "            ({:?}, {}.into()),", variable, param)
+                }
+                ArgumentType::String => writeln!(
+                    out,
+                    "            ({:?}, {}::FluentValue::String({}.into())),",
+                    variable, bundle, param
+                ),
+            };
+        }
+        out.push_str("        ])),\n");
+    }
+    out.push_str("    }\n}\n");
+    Ok(())
+}
@@ -0,0 +1,18 @@
+## Messages used to test the generated accessors.
+
+-brand-name = { $case ->
+       *[nominative] Firefox
+        [genitive] Firefox's
+    }
+
+hello = Hello, world!
+welcome = Welcome, { $userName }, to { -brand-name }!
+emails = { $count ->
+        [one] You have one new email.
+       *[other] You have { $count } new emails.
+    }
+last-visit = Last visit: { DATETIME($date) }
+    .title = { $userName } visited { welcome }
+type = Type
+login = Log in
+    .type = button
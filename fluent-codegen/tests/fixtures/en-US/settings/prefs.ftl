@@ -0,0 +1,4 @@
+prefs-title =
+    .label = Settings
+    .accesskey = S
+prefs-download = { NUMBER($size, maximumFractionDigits: 1) } MB of { $total } MB
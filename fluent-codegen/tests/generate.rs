@@ -0,0 +1,122 @@
+use std::fs;
+
+use fluent_bundle::{FluentBundle, FluentResource};
+use fluent_codegen::{generate, generate_from_paths, Error, Options};
+use fluent_fallback::types::L10nKey;
+use unic_langid::langid;
+
+#[allow(dead_code)]
+mod messages {
+    include!("generated/messages.rs");
+}
+
+fn options() -> Options {
+    Options {
+        fallback_crate: Some("::fluent_fallback".to_string()),
+        ..Options::default()
+    }
+}
+
+fn bundle() -> FluentBundle<FluentResource> {
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    for path in [
+        "tests/fixtures/en-US/main.ftl",
+        "tests/fixtures/en-US/settings/prefs.ftl",
+    ] {
+        let source = fs::read_to_string(path).expect("Failed to read a fixture.");
+        let resource = FluentResource::try_new(source).expect("Failed to parse a fixture.");
+        bundle
+            .add_resource(resource)
+            .expect("Failed to add a resource.");
+    }
+    bundle.add_builtins().expect("Failed to add the builtins.");
+    bundle
+}
+
+#[test]
+fn generated_module_is_up_to_date() {
+    let generated = generate_from_paths(&["tests/fixtures"], &options())
+        .expect("Failed to generate the module.");
+    let expected = fs::read_to_string("tests/generated/messages.rs")
+        .expect("Failed to read the generated module.");
+    assert_eq!(generated, expected);
+}
+
+#[test]
+fn format_generated_messages() {
+    let bundle = bundle();
+    let mut errors = vec![];
+
+    let mut format = |message: messages::Message| {
+        message
+            .format(&bundle, &mut errors)
+            .expect("Failed to find a message.")
+            .into_owned()
+    };
+    assert_eq!(format(messages::hello()), "Hello, world!");
+    assert_eq!(
+        format(messages::welcome("Jane")),
+        "Welcome, Jane, to Firefox!"
+    );
+    assert_eq!(format(messages::emails(1)), "You have one new email.");
+    assert_eq!(format(messages::emails(3)), "You have 3 new emails.");
+    assert_eq!(
+        format(messages::prefs_download(12.34, "100".to_string())),
+        "12.3 MB of 100 MB"
+    );
+    assert_eq!(format(messages::r#type()), "Type");
+    assert_eq!(
+        messages::login_type()
+            .format(&bundle, &mut errors)
+            .as_deref(),
+        Some("button")
+    );
+
+    let title = messages::last_visit_title("Jane");
+    assert_eq!(
+        title.format(&bundle, &mut errors).as_deref(),
+        Some("Jane visited Welcome, Jane, to Firefox!")
+    );
+    assert_eq!(
+        messages::prefs_title_accesskey()
+            .format(&bundle, &mut errors)
+            .as_deref(),
+        Some("S")
+    );
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn convert_into_l10n_key() {
+    let key: L10nKey = messages::welcome("Jane").into();
+    assert_eq!(key.id, "welcome");
+    assert_eq!(
+        key.args.and_then(|args| args.get("userName").cloned()),
+        Some("Jane".into())
+    );
+}
+
+#[test]
+fn generate_errors() {
+    let resource = |source: &str| FluentResource::try_new(source.to_string()).unwrap();
+
+    let collision = generate(
+        &[resource("new-tab = New tab\nnewTab = New tab\n")],
+        &Options::default(),
+    );
+    assert!(matches!(
+        collision,
+        Err(Error::Collision { name, first, second })
+            if name == "new_tab" && first == "new-tab" && second == "newTab"
+    ));
+
+    let duplicate = generate(
+        &[resource("key = Value\n"), resource("key = Value\n")],
+        &Options::default(),
+    );
+    assert!(matches!(duplicate, Err(Error::Fluent(_))));
+
+    let parse = generate_from_paths(&["tests/generated/messages.rs"], &Options::default());
+    assert!(matches!(parse, Err(Error::Parse { .. })));
+}
@@ -0,0 +1,163 @@
+// This file is generated by fluent-codegen. Do not edit.
+
+/// A message value with its arguments.
+#[derive(Debug)]
+pub struct Message<'a> {
+    pub id: &'static str,
+    pub args: Option<::fluent_bundle::FluentArgs<'a>>,
+}
+
+impl<'a> Message<'a> {
+    /// Formats the message with `bundle`, or returns `None` if the bundle doesn't have
+    /// a value for it.
+    pub fn format<'b, R, M>(
+        &self,
+        bundle: &'b ::fluent_bundle::bundle::FluentBundle<R, M>,
+        errors: &mut Vec<::fluent_bundle::FluentError>,
+    ) -> Option<::std::borrow::Cow<'b, str>>
+    where
+        R: ::std::borrow::Borrow<::fluent_bundle::FluentResource>,
+        M: ::fluent_bundle::memoizer::MemoizerKind,
+    {
+        let pattern = bundle.get_message(self.id)?.value()?;
+        Some(bundle.format_pattern(pattern, self.args.as_ref(), errors))
+    }
+}
+
+/// A message attribute with its arguments.
+#[derive(Debug)]
+pub struct Attribute<'a> {
+    pub id: &'static str,
+    pub attribute: &'static str,
+    pub args: Option<::fluent_bundle::FluentArgs<'a>>,
+}
+
+impl<'a> Attribute<'a> {
+    /// Formats the attribute with `bundle`, or returns `None` if the bundle doesn't have
+    /// it.
+    pub fn format<'b, R, M>(
+        &self,
+        bundle: &'b ::fluent_bundle::bundle::FluentBundle<R, M>,
+        errors: &mut Vec<::fluent_bundle::FluentError>,
+    ) -> Option<::std::borrow::Cow<'b, str>>
+    where
+        R: ::std::borrow::Borrow<::fluent_bundle::FluentResource>,
+        M: ::fluent_bundle::memoizer::MemoizerKind,
+    {
+        let message = bundle.get_message(self.id)?;
+        let pattern = message.get_attribute(self.attribute)?.value();
+        Some(bundle.format_pattern(pattern, self.args.as_ref(), errors))
+    }
+}
+
+impl<'a> From<Message<'a>> for ::fluent_fallback::types::L10nKey<'a> {
+    fn from(message: Message<'a>) -> Self {
+        Self {
+            id: message.id.into(),
+            args: message.args,
+        }
+    }
+}
+
+/// The message `hello`.
+pub fn hello() -> Message<'static> {
+    Message {
+        id: "hello",
+        args: None,
+    }
+}
+
+/// The message `welcome`.
+pub fn welcome<'a>(user_name: impl Into<::std::borrow::Cow<'a, str>>) -> Message<'a> {
+    Message {
+        id: "welcome",
+        args: Some(::std::iter::FromIterator::from_iter([
+            ("userName", ::fluent_bundle::FluentValue::String(user_name.into())),
+        ])),
+    }
+}
+
+/// The message `emails`.
+pub fn emails<'a>(count: impl Into<::fluent_bundle::types::FluentNumber>) -> Message<'a> {
+    Message {
+        id: "emails",
+        args: Some(::std::iter::FromIterator::from_iter([
+            ("count", ::fluent_bundle::FluentValue::Number(count.into())),
+        ])),
+    }
+}
+
+/// The message `last-visit`.
+pub fn last_visit<'a>(date: impl Into<::fluent_bundle::FluentValue<'a>>) -> Message<'a> {
+    Message {
+        id: "last-visit",
+        args: Some(::std::iter::FromIterator::from_iter([
+            ("date", date.into()),
+        ])),
+    }
+}
+
+/// The attribute `last-visit.title`.
+pub fn last_visit_title<'a>(user_name: impl Into<::std::borrow::Cow<'a, str>>) -> Attribute<'a> {
+    Attribute {
+        id: "last-visit",
+        attribute: "title",
+        args: Some(::std::iter::FromIterator::from_iter([
+            ("userName", ::fluent_bundle::FluentValue::String(user_name.into())),
+        ])),
+    }
+}
+
+/// The message `type`.
+pub fn r#type() -> Message<'static> {
+    Message {
+        id: "type",
+        args: None,
+    }
+}
+
+/// The message `login`.
+pub fn login() -> Message<'static> {
+    Message {
+        id: "login",
+        args: None,
+    }
+}
+
+/// The attribute `login.type`.
+pub fn login_type() -> Attribute<'static> {
+    Attribute {
+        id: "login",
+        attribute: "type",
+        args: None,
+    }
+}
+
+/// The attribute `prefs-title.label`.
+pub fn prefs_title_label() -> Attribute<'static> {
+    Attribute {
+        id: "prefs-title",
+        attribute: "label",
+        args: None,
+    }
+}
+
+/// The attribute `prefs-title.accesskey`.
+pub fn prefs_title_accesskey() -> Attribute<'static> {
+    Attribute {
+        id: "prefs-title",
+        attribute: "accesskey",
+        args: None,
+    }
+}
+
+/// The message `prefs-download`.
+pub fn prefs_download<'a>(size: impl Into<::fluent_bundle::types::FluentNumber>, total: impl Into<::std::borrow::Cow<'a, str>>) -> Message<'a> {
+    Message {
+        id: "prefs-download",
+        args: Some(::std::iter::FromIterator::from_iter([
+            ("size", ::fluent_bundle::FluentValue::Number(size.into())),
+            ("total", ::fluent_bundle::FluentValue::String(total.into())),
+        ])),
+    }
+}
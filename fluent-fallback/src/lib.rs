@@ -28,7 +28,7 @@
 //! use fluent_resmgr::ResourceManager;
 //! use unic_langid::langid;
 //!
-//! let res_mgr = ResourceManager::new("./tests/resources/{locale}/".to_string());
+//! let res_mgr = ResourceManager::new("./tests/resources/{locale}/{res_id}".to_string());
 //!
 //! let loc = Localization::with_env(
 //!     vec![
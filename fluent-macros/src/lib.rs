@@ -0,0 +1,279 @@
+//! Fluent is a modern localization system designed to improve how software is translated.
+//!
+//! The `fluent-macros` crate provides the procedural macros of the `fluent` crate, which
+//! re-exports them. Use them through `fluent` rather than depending on this crate.
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use fluent_bundle::{FluentBundle, FluentResource};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Error, Expr, LitStr, Token};
+
+/// Creates a [`MessageRef`] to a message or an attribute, checking at compile time that
+/// it exists in the reference FTL files, and that the arguments match the variables it
+/// uses.
+///
+/// The first argument is the path of the reference FTL files, relative to the
+/// `Cargo.toml` of the crate: either a file, or a directory which is searched
+/// recursively for `.ftl` files. The second one is the id of the message, or
+/// `id.attribute` for an attribute. The arguments follow as in `fluent_args!`.
+///
+/// Using a message which doesn't exist, omitting an argument, or passing one the message
+/// doesn't use, are compile errors.
+///
+/// The crate is rebuilt when the FTL files which were read change, but Cargo can't
+/// tell when FTL files are added to a directory. To rebuild the crate when they are,
+/// add a build script watching the directory:
+///
+/// ```no_run
+/// // In the `main` function of build.rs:
+/// println!("cargo:rerun-if-changed=locales/en-US");
+/// ```
+///
+/// # Example
+///
+/// ```ignore
+/// use fluent::fluent_message;
+///
+/// // locales/en-US/main.ftl:
+/// //
+/// // emails = { $name } has { $count } new emails.
+/// //     .title = New emails
+/// let emails = fluent_message!("locales/en-US", "emails", "name" => "Jane", "count" => 5);
+/// let title = fluent_message!("locales/en-US", "emails.title");
+///
+/// let mut errors = vec![];
+/// let value = emails.format(&bundle, &mut errors);
+/// ```
+///
+/// [`MessageRef`]: https://docs.rs/fluent/latest/fluent/struct.MessageRef.html
+#[proc_macro]
+pub fn fluent_message(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as MessageInput);
+    expand_message(input)
+        .unwrap_or_else(|error| {
+            // Wrapped in a block, so that the errors are valid as an expression.
+            let errors = error.into_compile_error();
+            quote!({ #errors })
+        })
+        .into()
+}
+
+/// The arguments of [`fluent_message!`].
+struct MessageInput {
+    path: LitStr,
+    id: LitStr,
+    args: Vec<(LitStr, Expr)>,
+}
+
+impl Parse for MessageInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let id = input.parse()?;
+        let mut args = vec![];
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let key = input.parse()?;
+            input.parse::<Token![=>]>()?;
+            args.push((key, input.parse()?));
+        }
+        Ok(Self { path, id, args })
+    }
+}
+
+fn expand_message(input: MessageInput) -> syn::Result<proc_macro2::TokenStream> {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = Path::new(&manifest_dir).join(input.path.value());
+    let files = ftl_files(&path).map_err(|err| {
+        let message = format!("Failed to read {}: {}", path.display(), err);
+        Error::new(input.path.span(), message)
+    })?;
+
+    let mut bundle = FluentBundle::default();
+    for file in &files {
+        let source = fs::read_to_string(file).map_err(|err| {
+            let message = format!("Failed to read {}: {}", file.display(), err);
+            Error::new(input.path.span(), message)
+        })?;
+        let resource = FluentResource::try_new(source).map_err(|(_, errors)| {
+            let message = format!("Failed to parse {}: {}", file.display(), errors[0]);
+            Error::new(input.path.span(), message)
+        })?;
+        // As in the bundles formatting the messages, the first definition wins.
+        let _ = bundle.add_resource(resource);
+    }
+
+    let full_id = input.id.value();
+    let (id, attribute) = match full_id.split_once('.') {
+        Some((id, attribute)) => (id, Some(attribute)),
+        None => (full_id.as_str(), None),
+    };
+    let variables = message_variables(&bundle, id, attribute)
+        .map_err(|message| Error::new(input.id.span(), message))?;
+
+    let mut errors = vec![];
+    let mut passed = BTreeSet::new();
+    for (key, _) in &input.args {
+        let name = key.value();
+        if !passed.insert(name.clone()) {
+            let message = format!("Duplicate argument `${}`", name);
+            errors.push(Error::new(key.span(), message));
+        } else if !variables.contains(&name) {
+            let message = format!("`{}` doesn't use the variable `${}`", full_id, name);
+            errors.push(Error::new(key.span(), message));
+        }
+    }
+    for name in variables.difference(&passed) {
+        let message = format!("Missing argument `${}` of `{}`", name, full_id);
+        errors.push(Error::new(input.id.span(), message));
+    }
+    if let Some(mut error) = errors.pop() {
+        for other in errors {
+            error.combine(other);
+        }
+        return Err(error);
+    }
+
+    let files = files
+        .iter()
+        .map(|file| file.canonicalize().unwrap_or_else(|_| file.clone()))
+        .map(|file| file.display().to_string());
+    let attribute = match attribute {
+        Some(attribute) => quote!(Some(#attribute)),
+        None => quote!(None),
+    };
+    let args = if input.args.is_empty() {
+        quote!(None)
+    } else {
+        let (keys, values): (Vec<_>, Vec<_>) = input.args.into_iter().unzip();
+        quote!(Some(::fluent::fluent_args![#(#keys => #values),*]))
+    };
+    Ok(quote! {
+        {
+            // Rebuild when the FTL files change; new files aren't tracked.
+            #(const _: &str = include_str!(#files);)*
+            ::fluent::MessageRef {
+                id: #id,
+                attribute: #attribute,
+                args: #args,
+            }
+        }
+    })
+}
+
+/// Returns the variables used by the message `id`, or its `attribute`, or an error
+/// message if there's no such message or attribute.
+fn message_variables<R: std::borrow::Borrow<FluentResource>>(
+    bundle: &FluentBundle<R>,
+    id: &str,
+    attribute: Option<&str>,
+) -> Result<BTreeSet<String>, String> {
+    let message = bundle
+        .get_message(id)
+        .ok_or_else(|| format!("Unknown message `{}`", id))?;
+    let pattern = match attribute {
+        Some(attribute) => message
+            .get_attribute(attribute)
+            .ok_or_else(|| format!("Unknown attribute `{}.{}`", id, attribute))?
+            .value(),
+        None => message
+            .value()
+            .ok_or_else(|| format!("Message `{}` has no value", id))?,
+    };
+    Ok(bundle.pattern_variables(pattern).into_keys().collect())
+}
+
+/// Returns the FTL files at `path`, in a stable order.
+fn ftl_files(path: &Path) -> Result<Vec<PathBuf>, io::Error> {
+    if !path.is_dir() {
+        fs::metadata(path)?;
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = vec![];
+    let mut dirs = vec![path.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.is_dir() {
+                // Symlinked directories are skipped, since they may form a loop.
+                if !entry.file_type()?.is_symlink() {
+                    dirs.push(path);
+                }
+            } else if path.extension().map_or(false, |ext| ext == "ftl") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle(source: &str) -> FluentBundle<FluentResource> {
+        let mut bundle = FluentBundle::default();
+        let resource = FluentResource::try_new(source.to_string()).unwrap();
+        bundle.add_resource(resource).unwrap();
+        bundle
+    }
+
+    #[test]
+    fn variables_of_messages_and_attributes() {
+        let bundle = bundle(
+            "user = { $name }\nemails = { user }: { $count }\n    .title = { $count } emails\n",
+        );
+        let names = |variables: BTreeSet<String>| variables.into_iter().collect::<Vec<_>>();
+
+        assert_eq!(
+            message_variables(&bundle, "emails", None).map(names),
+            Ok(vec!["count".to_string(), "name".to_string()])
+        );
+        assert_eq!(
+            message_variables(&bundle, "emails", Some("title")).map(names),
+            Ok(vec!["count".to_string()])
+        );
+    }
+
+    #[test]
+    fn unknown_messages() {
+        let bundle = bundle("attrs =\n    .title = Title\n");
+
+        assert_eq!(
+            message_variables(&bundle, "missing", None),
+            Err("Unknown message `missing`".to_string())
+        );
+        assert_eq!(
+            message_variables(&bundle, "attrs", Some("label")),
+            Err("Unknown attribute `attrs.label`".to_string())
+        );
+        assert_eq!(
+            message_variables(&bundle, "attrs", None),
+            Err("Message `attrs` has no value".to_string())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_directories() {
+        let dir = std::env::temp_dir().join(format!("fluent-macros-{}", std::process::id()));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub/a.ftl"), "key = Value\n").unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("sub/loop")).unwrap();
+
+        let files = ftl_files(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(files.unwrap(), vec![dir.join("sub/a.ftl")]);
+    }
+}
@@ -0,0 +1,259 @@
+//! Fluent is a modern localization system designed to improve how software is translated.
+//!
+//! The `fluent-markup` crate is an overlay for the inline markup of formatted messages,
+//! like the DOM overlays of fluent.js. It parses the formatted messages into text and
+//! element nodes, sanitizes the elements and attributes which translations may not use,
+//! and lets the caller render the named elements with their own markup.
+//!
+//! Translations may use the text-level elements, such as `<b>` or `<em>`, with
+//! localizable attributes like `title`. Other elements must come from the caller: the
+//! translation marks them with a `data-l10n-name` attribute, and the caller decides how
+//! to render them. Everything else is replaced by its text content.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_markup::Overlay;
+//!
+//! let mut overlay = Overlay::new();
+//! overlay.add_named_element("link", |_element, children| {
+//!     format!(r#"<a href="/settings">{}</a>"#, children)
+//! });
+//!
+//! // The formatted message, e.g. from `FluentBundle::format_pattern`.
+//! let value = r#"Change your <b>settings</b> <a data-l10n-name="link" href="javascript:">here</a>.<script>alert(1)</script>"#;
+//!
+//! assert_eq!(
+//!     overlay.render(value),
+//!     r#"Change your <b>settings</b> <a href="/settings">here</a>.alert(1)"#
+//! );
+//! ```
+mod parser;
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+/// The text-level elements which translations may use, as in fluent.js.
+const TEXT_LEVEL_ELEMENTS: &[&str] = &[
+    "em", "strong", "small", "s", "cite", "q", "dfn", "abbr", "data", "time", "code", "var",
+    "samp", "kbd", "sub", "sup", "i", "b", "u", "mark", "bdi", "bdo", "span", "br", "wbr",
+];
+
+/// The attributes which translations may use, as in fluent.js.
+const LOCALIZABLE_ATTRIBUTES: &[&str] = &["title", "aria-label", "aria-valuetext"];
+
+/// The attribute naming the elements which the caller renders.
+const NAME_ATTRIBUTE: &str = "data-l10n-name";
+
+/// A node of a formatted message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Node<'s> {
+    /// Text, with its character references decoded.
+    Text(Cow<'s, str>),
+    /// An element, with its children.
+    Element(Element<'s>),
+}
+
+impl Node<'_> {
+    /// Returns the text content of the node.
+    pub fn text(&self) -> Cow<'_, str> {
+        match self {
+            Self::Text(text) => Cow::Borrowed(text),
+            Self::Element(element) => element.text().into(),
+        }
+    }
+}
+
+/// An element of a formatted message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Element<'s> {
+    /// The name of the element, in lowercase.
+    pub name: Cow<'s, str>,
+    /// The attributes of the element, with their names in lowercase and their character
+    /// references decoded.
+    pub attributes: Vec<(Cow<'s, str>, Cow<'s, str>)>,
+    /// The children of the element.
+    pub children: Vec<Node<'s>>,
+}
+
+impl Element<'_> {
+    /// Returns the value of the attribute `name`.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attr_name, _)| attr_name == name)
+            .map(|(_, value)| value.as_ref())
+    }
+
+    /// Returns the value of the `data-l10n-name` attribute, which names the elements
+    /// rendered by the caller.
+    pub fn l10n_name(&self) -> Option<&str> {
+        self.attribute(NAME_ATTRIBUTE)
+    }
+
+    /// Returns the text content of the element.
+    pub fn text(&self) -> String {
+        self.children.iter().map(Node::text).collect()
+    }
+}
+
+type RenderElement<'r> = Box<dyn Fn(&Element, &str) -> String + 'r>;
+
+/// Parses, sanitizes and renders the markup of formatted messages.
+///
+/// By default, the overlay allows the text-level elements and localizable attributes
+/// which fluent.js allows. Named elements, with a `data-l10n-name` attribute, are
+/// kept whatever their name, and rendered by the functions added with
+/// [`add_named_element`](Overlay::add_named_element).
+pub struct Overlay<'r> {
+    elements: HashSet<String>,
+    attributes: HashSet<String>,
+    named_elements: HashMap<String, RenderElement<'r>>,
+}
+
+impl<'r> Overlay<'r> {
+    /// Creates an overlay allowing the default elements and attributes.
+    pub fn new() -> Self {
+        Self {
+            elements: TEXT_LEVEL_ELEMENTS.iter().map(|&s| s.to_string()).collect(),
+            attributes: LOCALIZABLE_ATTRIBUTES
+                .iter()
+                .map(|&s| s.to_string())
+                .collect(),
+            named_elements: HashMap::new(),
+        }
+    }
+
+    /// Allows translations to use the element `name`.
+    pub fn add_element(&mut self, name: &str) {
+        self.elements.insert(name.to_ascii_lowercase());
+    }
+
+    /// Allows translations to use the attribute `name` on the elements they may use.
+    pub fn add_attribute(&mut self, name: &str) {
+        self.attributes.insert(name.to_ascii_lowercase());
+    }
+
+    /// Renders the elements named `name` with `render`, which receives the sanitized
+    /// element, and its rendered children.
+    ///
+    /// The result of `render` is trusted: it's written to the output as it is. The named
+    /// elements which don't have a renderer are replaced by their text content.
+    pub fn add_named_element<F>(&mut self, name: &str, render: F)
+    where
+        F: Fn(&Element, &str) -> String + 'r,
+    {
+        self.named_elements
+            .insert(name.to_string(), Box::new(render));
+    }
+
+    /// Parses `source` into nodes, replacing the elements which aren't allowed by their
+    /// text content, and removing the attributes which aren't allowed.
+    ///
+    /// Elements nested more than 64 levels deep are replaced by their content.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_markup::{Node, Overlay};
+    ///
+    /// let overlay = Overlay::new();
+    /// let nodes = overlay.parse(r#"<em onclick="steal()">Hello</em> <img src=x>world"#);
+    ///
+    /// assert_eq!(nodes.len(), 2);
+    /// assert!(matches!(&nodes[0], Node::Element(em) if em.attributes.is_empty()));
+    /// assert_eq!(nodes[1], Node::Text(" world".into()));
+    /// ```
+    pub fn parse<'s>(&self, source: &'s str) -> Vec<Node<'s>> {
+        let mut nodes = vec![];
+        self.sanitize(parser::parse(source), &mut nodes);
+        nodes
+    }
+
+    /// Renders `source` to HTML, sanitized as by [`parse`](Overlay::parse), with the
+    /// named elements rendered by the caller.
+    pub fn render(&self, source: &str) -> String {
+        let mut result = String::with_capacity(source.len());
+        self.render_nodes(&self.parse(source), &mut result);
+        result
+    }
+
+    fn sanitize<'s>(&self, nodes: Vec<Node<'s>>, result: &mut Vec<Node<'s>>) {
+        for node in nodes {
+            match node {
+                Node::Text(text) => parser::push_text(result, text),
+                Node::Element(mut element) => {
+                    if element.l10n_name().is_some() || self.elements.contains(&*element.name) {
+                        element.attributes.retain(|(name, _)| {
+                            name == NAME_ATTRIBUTE || self.attributes.contains(&**name)
+                        });
+                        let children = std::mem::take(&mut element.children);
+                        self.sanitize(children, &mut element.children);
+                        result.push(Node::Element(element));
+                    } else {
+                        parser::push_text(result, element.text().into());
+                    }
+                }
+            }
+        }
+    }
+
+    fn render_nodes(&self, nodes: &[Node], result: &mut String) {
+        for node in nodes {
+            let element = match node {
+                Node::Text(text) => {
+                    escape(text, result);
+                    continue;
+                }
+                Node::Element(element) => element,
+            };
+            if let Some(name) = element.l10n_name() {
+                match self.named_elements.get(name) {
+                    Some(render) => {
+                        let mut children = String::new();
+                        self.render_nodes(&element.children, &mut children);
+                        result.push_str(&render(element, &children));
+                    }
+                    None => escape(&element.text(), result),
+                }
+                continue;
+            }
+
+            result.push('<');
+            result.push_str(&element.name);
+            for (name, value) in &element.attributes {
+                result.push(' ');
+                result.push_str(name);
+                result.push_str("=\"");
+                escape(value, result);
+                result.push('"');
+            }
+            result.push('>');
+            if !parser::is_void(&element.name) {
+                self.render_nodes(&element.children, result);
+                result.push_str("</");
+                result.push_str(&element.name);
+                result.push('>');
+            }
+        }
+    }
+}
+
+impl Default for Overlay<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Escapes text or an attribute value for HTML.
+fn escape(text: &str, result: &mut String) {
+    for ch in text.chars() {
+        match ch {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            _ => result.push(ch),
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! A forgiving parser of the HTML markup found in translations.
+//!
+//! It doesn't implement the HTML parsing algorithm: it only recognizes tags, comments,
+//! and character references. Anything which isn't a well-formed tag is kept as text,
+//! unmatched end tags are ignored, and elements left open are closed at the end.
+//!
+//! Elements nested deeper than [`MAX_DEPTH`] are flattened into their parent, so that
+//! the tree can be walked recursively. Their end tags are dropped with their start tags.
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::{Element, Node};
+
+/// The elements which never have children.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// The maximum depth of the elements; the tags of the elements nested deeper are dropped,
+/// keeping their content.
+pub(crate) const MAX_DEPTH: usize = 64;
+
+pub(crate) fn is_void(name: &str) -> bool {
+    VOID_ELEMENTS.contains(&name)
+}
+
+enum Tag<'s> {
+    Start {
+        name: Cow<'s, str>,
+        attributes: Vec<(Cow<'s, str>, Cow<'s, str>)>,
+        self_closing: bool,
+    },
+    End(Cow<'s, str>),
+    Comment,
+}
+
+/// Parses `source` into a tree of nodes, keeping all elements and attributes.
+pub(crate) fn parse(source: &str) -> Vec<Node<'_>> {
+    let mut root = vec![];
+    let mut open: Vec<Element> = vec![];
+    // The start tags dropped beyond `MAX_DEPTH` by name, whose end tags are dropped too.
+    let mut dropped: HashMap<Cow<str>, usize> = HashMap::new();
+    let mut text_start = 0;
+    let mut pos = 0;
+
+    while let Some(offset) = source[pos..].find('<') {
+        let start = pos + offset;
+        let Some((tag, end)) = parse_tag(source, start) else {
+            pos = start + 1;
+            continue;
+        };
+        let depth = open.len();
+        let children = open.last_mut().map_or(&mut root, |el| &mut el.children);
+        push_text(children, decode(&source[text_start..start]));
+        pos = end;
+        text_start = end;
+
+        match tag {
+            Tag::Start {
+                name,
+                attributes,
+                self_closing,
+            } => {
+                let element = Element {
+                    name,
+                    attributes,
+                    children: vec![],
+                };
+                let void = self_closing || is_void(&element.name);
+                if depth >= MAX_DEPTH {
+                    if !void {
+                        *dropped.entry(element.name).or_default() += 1;
+                    }
+                    continue;
+                }
+                if void {
+                    children.push(Node::Element(element));
+                } else {
+                    open.push(element);
+                }
+            }
+            Tag::End(name) => {
+                if let Some(count) = dropped.get_mut(&name).filter(|count| **count > 0) {
+                    *count -= 1;
+                } else if let Some(idx) = open.iter().rposition(|el| el.name == name) {
+                    while open.len() > idx {
+                        close(&mut root, &mut open);
+                    }
+                    // The dropped elements left open are closed with their ancestor.
+                    if open.len() < MAX_DEPTH {
+                        dropped.clear();
+                    }
+                }
+            }
+            Tag::Comment => {}
+        }
+    }
+
+    let children = open.last_mut().map_or(&mut root, |el| &mut el.children);
+    push_text(children, decode(&source[text_start..]));
+    while !open.is_empty() {
+        close(&mut root, &mut open);
+    }
+    root
+}
+
+/// Closes the innermost open element, adding it to its parent.
+fn close<'s>(root: &mut Vec<Node<'s>>, open: &mut Vec<Element<'s>>) {
+    if let Some(element) = open.pop() {
+        let children = open.last_mut().map_or(root, |el| &mut el.children);
+        children.push(Node::Element(element));
+    }
+}
+
+/// Appends text to the nodes, merging it with the previous node if it's text too.
+pub(crate) fn push_text<'s>(nodes: &mut Vec<Node<'s>>, text: Cow<'s, str>) {
+    if text.is_empty() {
+        return;
+    }
+    if let Some(Node::Text(last)) = nodes.last_mut() {
+        last.to_mut().push_str(&text);
+    } else {
+        nodes.push(Node::Text(text));
+    }
+}
+
+/// Parses the tag starting with the `<` at `start`, returning it with the position
+/// following it, or `None` if it isn't a well-formed tag.
+fn parse_tag(source: &str, start: usize) -> Option<(Tag<'_>, usize)> {
+    let rest = &source[start..];
+    if let Some(comment) = rest.strip_prefix("<!--") {
+        let end = comment
+            .find("-->")
+            .map_or(source.len(), |idx| start + 4 + idx + 3);
+        return Some((Tag::Comment, end));
+    }
+
+    let bytes = source.as_bytes();
+    let (end_tag, mut pos) = match bytes.get(start + 1) {
+        Some(b'/') => (true, start + 2),
+        _ => (false, start + 1),
+    };
+    if !bytes.get(pos)?.is_ascii_alphabetic() {
+        return None;
+    }
+    let name = take_while(source, &mut pos, |b| {
+        !b.is_ascii_whitespace() && b != b'/' && b != b'>'
+    });
+    let name = lowercase(name);
+
+    if end_tag {
+        take_while(source, &mut pos, |b| b.is_ascii_whitespace());
+        return match bytes.get(pos)? {
+            b'>' => Some((Tag::End(name), pos + 1)),
+            _ => None,
+        };
+    }
+
+    let mut attributes = vec![];
+    loop {
+        take_while(source, &mut pos, |b| b.is_ascii_whitespace());
+        match bytes.get(pos)? {
+            b'>' => {
+                let tag = Tag::Start {
+                    name,
+                    attributes,
+                    self_closing: false,
+                };
+                return Some((tag, pos + 1));
+            }
+            b'/' if bytes.get(pos + 1) == Some(&b'>') => {
+                let tag = Tag::Start {
+                    name,
+                    attributes,
+                    self_closing: true,
+                };
+                return Some((tag, pos + 2));
+            }
+            b'/' => pos += 1,
+            _ => {
+                let attr_name = take_while(source, &mut pos, |b| {
+                    !b.is_ascii_whitespace() && b != b'=' && b != b'/' && b != b'>'
+                });
+                let mut value_pos = pos;
+                take_while(source, &mut value_pos, |b| b.is_ascii_whitespace());
+                let value = if bytes.get(value_pos) == Some(&b'=') {
+                    value_pos += 1;
+                    take_while(source, &mut value_pos, |b| b.is_ascii_whitespace());
+                    let value = match bytes.get(value_pos)? {
+                        &quote @ (b'"' | b'\'') => {
+                            let value_start = value_pos + 1;
+                            let len = source[value_start..].find(quote as char)?;
+                            value_pos = value_start + len + 1;
+                            &source[value_start..value_start + len]
+                        }
+                        _ => take_while(source, &mut value_pos, |b| {
+                            !b.is_ascii_whitespace() && b != b'>'
+                        }),
+                    };
+                    pos = value_pos;
+                    decode(value)
+                } else {
+                    Cow::Borrowed("")
+                };
+                attributes.push((lowercase(attr_name), value));
+            }
+        }
+    }
+}
+
+/// Advances `pos` over the bytes matching `predicate`, returning them.
+fn take_while<'s>(source: &'s str, pos: &mut usize, predicate: impl Fn(u8) -> bool) -> &'s str {
+    let start = *pos;
+    let len = source.as_bytes()[start..]
+        .iter()
+        .take_while(|&&b| predicate(b))
+        .count();
+    *pos += len;
+    &source[start..*pos]
+}
+
+fn lowercase(name: &str) -> Cow<'_, str> {
+    if name.bytes().any(|b| b.is_ascii_uppercase()) {
+        name.to_ascii_lowercase().into()
+    } else {
+        name.into()
+    }
+}
+
+/// Decodes the character references of `text`, leaving the unknown ones as they are.
+fn decode(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') {
+        return text.into();
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(idx) = rest.find('&') {
+        result.push_str(&rest[..idx]);
+        rest = &rest[idx..];
+        let decoded = rest[1..]
+            .find(';')
+            .filter(|&len| len <= 32)
+            .and_then(|len| Some((decode_reference(&rest[1..len + 1])?, len + 2)));
+        match decoded {
+            Some((ch, len)) => {
+                result.push(ch);
+                rest = &rest[len..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result.into()
+}
+
+fn decode_reference(reference: &str) -> Option<char> {
+    let code = if let Some(hex) = reference
+        .strip_prefix("#x")
+        .or_else(|| reference.strip_prefix("#X"))
+    {
+        u32::from_str_radix(hex, 16).ok()?
+    } else if let Some(decimal) = reference.strip_prefix('#') {
+        decimal.parse().ok()?
+    } else {
+        return match reference {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            _ => None,
+        };
+    };
+    char::from_u32(code)
+}
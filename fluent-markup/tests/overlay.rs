@@ -0,0 +1,165 @@
+use fluent_bundle::resolver::Isolation;
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+use fluent_markup::{Element, Node, Overlay};
+use unic_langid::langid;
+
+fn element<'s>(
+    name: &'s str,
+    attributes: &[(&'s str, &'s str)],
+    children: Vec<Node<'s>>,
+) -> Node<'s> {
+    Node::Element(Element {
+        name: name.into(),
+        attributes: attributes
+            .iter()
+            .map(|&(name, value)| (name.into(), value.into()))
+            .collect(),
+        children,
+    })
+}
+
+fn text(value: &str) -> Node<'_> {
+    Node::Text(value.into())
+}
+
+#[test]
+fn parse_markup() {
+    let overlay = Overlay::new();
+
+    assert_eq!(
+        overlay.parse("1 < 2 &amp;&#x41;&#66; &unknown; <B Title='a &quot;b&quot;'>bold <i>and italic</b> <br/>end"),
+        vec![
+            text("1 < 2 &AB &unknown; "),
+            element(
+                "b",
+                &[("title", "a \"b\"")],
+                vec![text("bold "), element("i", &[], vec![text("and italic")])]
+            ),
+            text(" "),
+            element("br", &[], vec![]),
+            text("end"),
+        ]
+    );
+    assert_eq!(
+        overlay.parse("<em>unclosed <!-- comment --></span>text"),
+        vec![element("em", &[], vec![text("unclosed text")])]
+    );
+    assert_eq!(
+        overlay.parse("<em title=unquoted aria-label>a</em><em title=\"broken>b"),
+        vec![
+            element(
+                "em",
+                &[("title", "unquoted"), ("aria-label", "")],
+                vec![text("a")]
+            ),
+            text("<em title=\"broken>b"),
+        ]
+    );
+
+    // Deeply nested elements are flattened.
+    let source = format!("{}x{}", "<b>".repeat(100_000), "</b>".repeat(100_000));
+    let nodes = overlay.parse(&source);
+    let mut depth = 0;
+    let mut node = &nodes[0];
+    while let Node::Element(element) = node {
+        depth += 1;
+        assert_eq!(element.children.len(), 1);
+        node = &element.children[0];
+    }
+    assert_eq!(depth, 64);
+    assert_eq!(nodes[0].text(), "x");
+    assert_eq!(
+        overlay.render(&source),
+        format!("{}x{}", "<b>".repeat(64), "</b>".repeat(64))
+    );
+    // The end tags of the flattened elements don't close their ancestors.
+    let source = format!("{}<b>x</b>y{}", "<b>".repeat(64), "</b>".repeat(64));
+    assert_eq!(
+        overlay.render(&source),
+        format!("{}xy{}", "<b>".repeat(64), "</b>".repeat(64))
+    );
+    let source = format!("{}<i>x</i></b><i>y</i>", "<b>".repeat(64));
+    assert_eq!(
+        overlay.render(&source),
+        format!("{}x</b><i>y</i>{}", "<b>".repeat(64), "</b>".repeat(63))
+    );
+}
+
+#[test]
+fn sanitize_markup() {
+    let mut overlay = Overlay::new();
+
+    assert_eq!(
+        overlay.parse(
+            r#"<strong class="x" title="Title">a</strong><div>b <em>c</em></div><a data-l10n-name="link" href="x" title="Link">d</a><script>e</script>"#
+        ),
+        vec![
+            element("strong", &[("title", "Title")], vec![text("a")]),
+            text("b c"),
+            element(
+                "a",
+                &[("data-l10n-name", "link"), ("title", "Link")],
+                vec![text("d")]
+            ),
+            text("e"),
+        ]
+    );
+
+    overlay.add_element("DIV");
+    overlay.add_attribute("class");
+    assert_eq!(
+        overlay.parse(r#"<div class="x">b</div>"#),
+        vec![element("div", &[("class", "x")], vec![text("b")])]
+    );
+}
+
+#[test]
+fn render_markup() {
+    let mut overlay = Overlay::new();
+    overlay.add_named_element("link", |element, children| {
+        let title = element.attribute("title").unwrap_or_default();
+        format!(
+            r#"<a href="https://example.com" title="{}">{}</a>"#,
+            title, children
+        )
+    });
+
+    assert_eq!(
+        overlay.render(
+            r#"Read <a data-l10n-name="link" title="Terms" onclick="x()">the <em>terms</em></a> &amp; <a data-l10n-name="other">others</a>.<br>"#
+        ),
+        r#"Read <a href="https://example.com" title="Terms">the <em>terms</em></a> &amp; others.<br>"#
+    );
+    assert_eq!(
+        overlay.render(r#"<b title="&quot;x&quot;">1 &lt; 2</b>"#),
+        r#"<b title="&quot;x&quot;">1 &lt; 2</b>"#
+    );
+}
+
+#[test]
+fn render_formatted_message() {
+    let res = FluentResource::try_new(
+        r#"welcome = Welcome, <b>{ $name }</b>! Open your <a data-l10n-name="profile">profile</a>."#
+            .to_string(),
+    )
+    .unwrap();
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+    bundle.set_isolation(Isolation::Html);
+
+    let mut args = FluentArgs::new();
+    args.set("name", "Jane");
+    let pattern = bundle.get_message("welcome").unwrap().value().unwrap();
+    let mut errors = vec![];
+    let value = bundle.format_pattern(pattern, Some(&args), &mut errors);
+    assert_eq!(errors, vec![]);
+
+    let mut overlay = Overlay::new();
+    overlay.add_named_element("profile", |_, children| {
+        format!(r#"<a href="/profile">{}</a>"#, children)
+    });
+    assert_eq!(
+        overlay.render(&value),
+        r#"Welcome, <b><bdi>Jane</bdi></b>! Open your <a href="/profile">profile</a>."#
+    );
+}
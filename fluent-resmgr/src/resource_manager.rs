@@ -1,13 +1,18 @@
-use elsa::FrozenMap;
-use fluent_bundle::{FluentBundle, FluentResource};
+use elsa::sync::FrozenMap;
+use fluent_bundle::{FluentBundle, FluentError, FluentResource};
 use fluent_fallback::{
-    generator::{BundleGenerator, FluentBundleResult},
+    generator::{self, BundleGenerator, FluentBundleResult},
     types::ResourceId,
 };
+use futures::channel::oneshot;
+use futures::future::{self, BoxFuture};
 use futures::stream::Stream;
+use futures::{ready, Future, FutureExt};
 use rustc_hash::FxHashSet;
-use std::io;
-use std::{fs, iter};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::{fs, io, iter, panic};
 use thiserror::Error;
 use unic_langid::LanguageIdentifier;
 
@@ -15,11 +20,65 @@ fn read_file(path: &str) -> Result<String, io::Error> {
     fs::read_to_string(path)
 }
 
+/// A function running a task on another thread, used by a [`ResourceManager`] to
+/// read files without blocking the executor of its async bundle stream.
+type Spawner = Arc<dyn Fn(Box<dyn FnOnce() + Send>) + Send + Sync>;
+
+/// Reads a file with `spawner`, so that awaiting the result doesn't block the
+/// executor, or in place without one. A panic while reading is resumed when the
+/// result is awaited, and the file is read in place if `spawner` drops the task.
+fn read_file_async(
+    path: String,
+    spawner: Option<&Spawner>,
+) -> impl Future<Output = Result<String, io::Error>> + Send {
+    let (sender, receiver) = oneshot::channel();
+    let read = {
+        let path = path.clone();
+        move || {
+            let result = panic::catch_unwind(|| read_file(&path));
+            // The receiver is gone if the stream was dropped in the meantime.
+            let _ = sender.send(result);
+        }
+    };
+    match spawner {
+        Some(spawn) => spawn(Box::new(read)),
+        None => read(),
+    }
+    receiver.map(move |result| match result {
+        Ok(Ok(source)) => source,
+        Ok(Err(payload)) => panic::resume_unwind(payload),
+        Err(oneshot::Canceled) => read_file(&path),
+    })
+}
+
+/// A parsed resource held in the cache of a [`ResourceManager`], along with the
+/// errors encountered while parsing it.
+struct CachedResource {
+    resource: Arc<FluentResource>,
+    errors: Vec<FluentError>,
+}
+
+impl CachedResource {
+    fn new(source: String) -> Self {
+        match FluentResource::try_new(source) {
+            Ok(resource) => Self {
+                resource: Arc::new(resource),
+                errors: vec![],
+            },
+            Err((resource, errors)) => Self {
+                resource: Arc::new(resource),
+                errors: errors.into_iter().map(FluentError::from).collect(),
+            },
+        }
+    }
+}
+
 /// [`ResourceManager`] provides a standalone solution for managing localization resources which
 /// can be used by `fluent-fallback` or other higher level bindings.
 pub struct ResourceManager {
-    resources: FrozenMap<String, Box<FluentResource>>,
+    resources: Arc<FrozenMap<String, Box<CachedResource>>>,
     path_scheme: String,
+    spawner: Option<Spawner>,
 }
 
 impl ResourceManager {
@@ -41,30 +100,65 @@ impl ResourceManager {
     ///
     pub fn new(path_scheme: String) -> Self {
         ResourceManager {
-            resources: FrozenMap::new(),
+            resources: Arc::new(FrozenMap::new()),
             path_scheme,
+            spawner: None,
         }
     }
 
+    /// Reads the files of the async bundle stream with `spawn`, which runs the task
+    /// it's given on another thread, such as [`std::thread::spawn`] or the blocking
+    /// pool of an async runtime. Without it, the stream reads the files in place
+    /// when it's polled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_resmgr::resource_manager::ResourceManager;
+    ///
+    /// let res_mgr = ResourceManager::new("./tests/resources/{locale}/{res_id}".into())
+    ///     .with_spawner(|task| {
+    ///         std::thread::spawn(task);
+    ///     });
+    /// ```
+    pub fn with_spawner(
+        mut self,
+        spawn: impl Fn(Box<dyn FnOnce() + Send>) + Send + Sync + 'static,
+    ) -> Self {
+        self.spawner = Some(Arc::new(spawn));
+        self
+    }
+
+    /// Returns a handle to the same resources, so that bundle iterators and streams
+    /// can populate the cache without borrowing the [`ResourceManager`].
+    fn share(&self) -> Self {
+        ResourceManager {
+            resources: Arc::clone(&self.resources),
+            path_scheme: self.path_scheme.clone(),
+            spawner: self.spawner.clone(),
+        }
+    }
+
+    fn resource_path(&self, resource_id: &str, locale: &str) -> String {
+        self.path_scheme
+            .replace("{locale}", locale)
+            .replace("{res_id}", resource_id)
+    }
+
     /// Returns a [`FluentResource`], by either reading the file and loading it into
-    /// memory, or retrieving it from an in-memory cache.
+    /// memory, or retrieving it from an in-memory cache. Errors encountered while
+    /// parsing the file are cached along with the resource.
     fn get_resource(
         &self,
         resource_id: &str,
         locale: &str,
-    ) -> Result<&FluentResource, ResourceManagerError> {
-        let path = self
-            .path_scheme
-            .replace("{locale}", locale)
-            .replace("{res_id}", resource_id);
+    ) -> Result<&CachedResource, ResourceManagerError> {
+        let path = self.resource_path(resource_id, locale);
         Ok(if let Some(resource) = self.resources.get(&path) {
             resource
         } else {
-            let resource = match FluentResource::try_new(read_file(&path)?) {
-                Ok(resource) => resource,
-                Err((resource, _err)) => resource,
-            };
-            self.resources.insert(path.to_string(), Box::new(resource))
+            let resource = CachedResource::new(read_file(&path)?);
+            self.resources.insert(path, Box::new(resource))
         })
     }
 
@@ -84,8 +178,8 @@ impl ResourceManager {
 
         for resource_id in &resource_ids {
             match self.get_resource(resource_id, &locale.to_string()) {
-                Ok(resource) => {
-                    if let Err(errs) = bundle.add_resource(resource) {
+                Ok(cached) => {
+                    if let Err(errs) = bundle.add_resource(&*cached.resource) {
                         for error in errs {
                             errors.push(ResourceManagerError::Fluent(error));
                         }
@@ -122,8 +216,8 @@ impl ResourceManager {
 
                 for resource_id in &resource_ids {
                     match self.get_resource(resource_id, &locale.to_string()) {
-                        Ok(resource) => {
-                            if let Err(errs) = bundle.add_resource(resource) {
+                        Ok(cached) => {
+                            if let Err(errs) = bundle.add_resource(&*cached.resource) {
                                 for error in errs {
                                     errors.push(ResourceManagerError::Fluent(error));
                                 }
@@ -158,45 +252,198 @@ pub enum ResourceManagerError {
 // Due to limitation of trait, we need a nameable Iterator type.  Due to the
 // lack of GATs, these have to own members instead of taking slices.
 pub struct BundleIter {
+    manager: ResourceManager,
     locales: <Vec<LanguageIdentifier> as IntoIterator>::IntoIter,
     res_ids: FxHashSet<ResourceId>,
+    /// The bundle being loaded when used as a [`Stream`].
+    pending: Option<BoxFuture<'static, FluentBundleResult<Arc<FluentResource>>>>,
+}
+
+impl BundleIter {
+    fn new(
+        manager: ResourceManager,
+        locales: <Vec<LanguageIdentifier> as IntoIterator>::IntoIter,
+        res_ids: FxHashSet<ResourceId>,
+    ) -> Self {
+        Self {
+            manager,
+            locales,
+            res_ids,
+            pending: None,
+        }
+    }
+}
+
+/// Adds a cached resource to the bundle, collecting its parser errors and any
+/// errors from adding it.
+fn add_cached_resource(
+    bundle: &mut FluentBundle<Arc<FluentResource>>,
+    cached: &CachedResource,
+    errors: &mut Vec<FluentError>,
+) {
+    errors.extend(cached.errors.iter().cloned());
+    if let Err(errs) = bundle.add_resource(Arc::clone(&cached.resource)) {
+        errors.extend(errs);
+    }
+}
+
+/// Records a resource which couldn't be read. Optional resources which don't
+/// exist are skipped silently.
+fn resource_load_error(res_id: &ResourceId, err: &io::Error, errors: &mut Vec<FluentError>) {
+    if res_id.is_optional() && err.kind() == io::ErrorKind::NotFound {
+        return;
+    }
+    errors.push(FluentError::ResourceLoad {
+        id: res_id.value.clone(),
+        reason: err.to_string(),
+    });
 }
 
 impl Iterator for BundleIter {
-    type Item = FluentBundleResult<FluentResource>;
+    type Item = FluentBundleResult<Arc<FluentResource>>;
 
     fn next(&mut self) -> Option<Self::Item> {
         let locale = self.locales.next()?;
+        let locale_str = locale.to_string();
 
-        let mut bundle = FluentBundle::new(vec![locale.clone()]);
+        let mut bundle = FluentBundle::new(vec![locale]);
+        let mut errors = vec![];
 
         for res_id in self.res_ids.iter() {
-            let full_path = format!("./tests/resources/{}/{}", locale, res_id);
-            let source = fs::read_to_string(full_path).unwrap();
-            let res = FluentResource::try_new(source).unwrap();
-            bundle.add_resource(res).unwrap();
+            match self.manager.get_resource(&res_id.value, &locale_str) {
+                Ok(cached) => add_cached_resource(&mut bundle, cached, &mut errors),
+                Err(ResourceManagerError::Io(err)) => {
+                    resource_load_error(res_id, &err, &mut errors)
+                }
+                Err(ResourceManagerError::Fluent(err)) => errors.push(err),
+            }
         }
-        Some(Ok(bundle))
+        Some(if errors.is_empty() {
+            Ok(bundle)
+        } else {
+            Err((bundle, errors))
+        })
     }
 }
 
-// TODO - These need to be implemented.
-// https://github.com/projectfluent/fluent-rs/issues/281
+/// Caches the resources of `locale` which aren't cached yet, reading them
+/// concurrently. Returns the error of each resource which couldn't be read.
+async fn cache_resources(
+    manager: &ResourceManager,
+    locale: &str,
+    res_ids: &[ResourceId],
+) -> Vec<Option<io::Error>> {
+    let reads = future::join_all(res_ids.iter().map(|res_id| {
+        let path = manager.resource_path(&res_id.value, locale);
+        let read = if manager.resources.get(&path).is_none() {
+            Some(read_file_async(path.clone(), manager.spawner.as_ref()))
+        } else {
+            None
+        };
+        async move {
+            match read {
+                Some(read) => Some((path, read.await)),
+                None => None,
+            }
+        }
+    }))
+    .await;
+
+    reads
+        .into_iter()
+        .map(|read| match read {
+            Some((path, Ok(source))) => {
+                manager
+                    .resources
+                    .insert(path, Box::new(CachedResource::new(source)));
+                None
+            }
+            Some((_, Err(err))) => Some(err),
+            None => None,
+        })
+        .collect()
+}
+
+/// Loads the resources of a single locale. Resources which aren't cached yet are
+/// read concurrently, and any resource which can't be read or parsed is reported
+/// as an error of the resulting bundle.
+async fn load_bundle(
+    manager: ResourceManager,
+    locale: LanguageIdentifier,
+    res_ids: Vec<ResourceId>,
+) -> FluentBundleResult<Arc<FluentResource>> {
+    let locale_str = locale.to_string();
+    let read_errors = cache_resources(&manager, &locale_str, &res_ids).await;
+
+    let mut bundle = FluentBundle::new(vec![locale]);
+    let mut errors = vec![];
+
+    for (res_id, read_error) in res_ids.iter().zip(read_errors) {
+        if let Some(err) = read_error {
+            resource_load_error(res_id, &err, &mut errors);
+            continue;
+        }
+        let path = manager.resource_path(&res_id.value, &locale_str);
+        let cached = manager
+            .resources
+            .get(&path)
+            .expect("The resource is cached");
+        add_cached_resource(&mut bundle, cached, &mut errors);
+    }
+
+    if errors.is_empty() {
+        Ok(bundle)
+    } else {
+        Err((bundle, errors))
+    }
+}
 
-// coverage(off)
 impl Stream for BundleIter {
-    type Item = FluentBundleResult<FluentResource>;
+    type Item = FluentBundleResult<Arc<FluentResource>>;
 
-    fn poll_next(
-        self: std::pin::Pin<&mut Self>,
-        _cx: &mut std::task::Context<'_>,
-    ) -> std::task::Poll<Option<Self::Item>> {
-        todo!()
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.pending.is_none() {
+            let Some(locale) = self.locales.next() else {
+                return Poll::Ready(None);
+            };
+            let res_ids = self.res_ids.iter().cloned().collect();
+            let manager = self.manager.share();
+            self.pending = Some(load_bundle(manager, locale, res_ids).boxed());
+        }
+        let pending = self.pending.as_mut().expect("A bundle is being loaded");
+        let bundle = ready!(pending.poll_unpin(cx));
+        self.pending = None;
+        Poll::Ready(Some(bundle))
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl generator::BundleStream for BundleIter {
+    /// Reads the resources of the next locale into the cache. The errors are
+    /// reported when its bundle is loaded.
+    async fn prefetch_async(&mut self) {
+        if let Some(locale) = self.locales.as_slice().first() {
+            let res_ids: Vec<_> = self.res_ids.iter().cloned().collect();
+            cache_resources(&self.manager, &locale.to_string(), &res_ids).await;
+        }
+    }
+}
+
+impl generator::BundleIterator for BundleIter {
+    /// Reads the resources of the next locale into the cache. The errors are
+    /// reported when its bundle is loaded.
+    fn prefetch_sync(&mut self) {
+        if let Some(locale) = self.locales.as_slice().first() {
+            let locale = locale.to_string();
+            for res_id in &self.res_ids {
+                let _ = self.manager.get_resource(&res_id.value, &locale);
+            }
+        }
     }
 }
 
 impl BundleGenerator for ResourceManager {
-    type Resource = FluentResource;
+    type Resource = Arc<FluentResource>;
     type LocalesIter = std::vec::IntoIter<LanguageIdentifier>;
     type Iter = BundleIter;
     type Stream = BundleIter;
@@ -206,18 +453,17 @@ impl BundleGenerator for ResourceManager {
         locales: Self::LocalesIter,
         res_ids: FxHashSet<ResourceId>,
     ) -> Self::Iter {
-        BundleIter { locales, res_ids }
+        BundleIter::new(self.share(), locales, res_ids)
     }
 
     fn bundles_stream(
         &self,
-        _locales: Self::LocalesIter,
-        _res_ids: FxHashSet<ResourceId>,
+        locales: Self::LocalesIter,
+        res_ids: FxHashSet<ResourceId>,
     ) -> Self::Stream {
-        todo!()
+        BundleIter::new(self.share(), locales, res_ids)
     }
 }
-// coverage(on)
 
 #[cfg(test)]
 mod test {
@@ -244,6 +490,38 @@ mod test {
         );
     }
 
+    #[test]
+    fn bundles_iter_caching() {
+        let res_mgr = ResourceManager::new("./tests/resources/{locale}/{res_id}".into());
+        let res_ids: FxHashSet<ResourceId> = iter::once("test.ftl".into()).collect();
+
+        let mut bundles = res_mgr.bundles_iter(vec![langid!("en-US")].into_iter(), res_ids);
+        assert!(matches!(Iterator::next(&mut bundles), Some(Ok(_))));
+        assert!(Iterator::next(&mut bundles).is_none());
+
+        let res_1 = res_mgr
+            .get_resource("test.ftl", "en-US")
+            .expect("Could not get resource");
+        let res_2 = res_mgr
+            .get_resource("test.ftl", "en-US")
+            .expect("Could not get resource");
+        assert_eq!(
+            res_mgr.resources.len(),
+            1,
+            "The generator filled the cache."
+        );
+        assert!(std::ptr::eq(res_1, res_2));
+    }
+
+    #[test]
+    fn thread_safety() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ResourceManager>();
+
+        fn assert_send<T: Send>() {}
+        assert_send::<BundleIter>();
+    }
+
     #[test]
     fn get_resource_error() {
         let res_mgr = ResourceManager::new("./tests/resources/{locale}/{res_id}".into());
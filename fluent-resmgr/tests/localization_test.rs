@@ -82,3 +82,146 @@ fn resmgr_get_bundles() {
 
     assert!(bundles_iter.next().is_none(), "The iterator is consumed.");
 }
+
+#[test]
+fn localization_format_value_async() {
+    let res_mgr = ResourceManager::new("./tests/resources/{locale}/{res_id}".into());
+
+    let mut loc = Localization::with_env(
+        vec!["test.ftl".into()],
+        false,
+        vec!["en-US".parse().unwrap(), "pl".parse().unwrap()],
+        res_mgr,
+    );
+
+    futures::executor::block_on(async {
+        loc.prefetch_async().await;
+        let bundles = loc.bundles();
+        let mut errors = vec![];
+
+        let value = bundles.format_value("hello-world", None, &mut errors).await;
+        assert_eq!(value, Some(Cow::Borrowed("Hello World")));
+
+        let value2 = bundles.format_value("new-message", None, &mut errors).await;
+        assert_eq!(value2, Some(Cow::Borrowed("Nowa Wiadomość")));
+
+        let value3 = bundles
+            .format_value("missing-message", None, &mut errors)
+            .await;
+        assert_eq!(value3, None);
+    });
+}
+
+fn check_bundles_stream(res_mgr: ResourceManager) {
+    use fluent_bundle::FluentError;
+    use fluent_fallback::generator::BundleGenerator;
+    use futures::StreamExt;
+
+    let locales = vec![langid!("en-US"), langid!("pl")];
+    let res_ids = vec!["test.ftl".into(), "invalid.ftl".into()];
+    let mut stream = res_mgr.bundles_stream(locales.into_iter(), res_ids.into_iter().collect());
+
+    futures::executor::block_on(async {
+        let (bundle, errors) = StreamExt::next(&mut stream)
+            .await
+            .unwrap()
+            .map(|_| ())
+            .expect_err("The en-US bundle has a syntax error.");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], FluentError::ParserError(_)));
+        assert!(bundle.has_message("hello-world"));
+
+        let (bundle, errors) = StreamExt::next(&mut stream)
+            .await
+            .unwrap()
+            .map(|_| ())
+            .expect_err("The pl bundle is missing a resource.");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            FluentError::ResourceLoad { id, .. } if id == "invalid.ftl"
+        ));
+        assert!(bundle.has_message("hello-world"));
+
+        assert!(
+            StreamExt::next(&mut stream).await.is_none(),
+            "The stream is consumed."
+        );
+    });
+}
+
+#[test]
+fn resmgr_bundles_stream() {
+    check_bundles_stream(ResourceManager::new(
+        "./tests/resources/{locale}/{res_id}".into(),
+    ));
+}
+
+#[test]
+fn resmgr_bundles_stream_with_spawner() {
+    check_bundles_stream(
+        ResourceManager::new("./tests/resources/{locale}/{res_id}".into()).with_spawner(|task| {
+            std::thread::spawn(task);
+        }),
+    );
+}
+
+#[test]
+fn localization_optional_resources() {
+    use fluent_fallback::types::{ResourceType, ToResourceId};
+
+    let res_mgr = ResourceManager::new("tests/resources/{locale}/{res_id}".into());
+
+    let loc = Localization::with_env(
+        vec![
+            "test.ftl".into(),
+            "missing.ftl".to_resource_id(ResourceType::Optional),
+        ],
+        true,
+        vec!["en-US".parse().unwrap()],
+        res_mgr,
+    );
+    let bundles = loc.bundles();
+    let mut errors = vec![];
+
+    let value = bundles
+        .format_value_sync("hello-world", None, &mut errors)
+        .unwrap();
+    assert_eq!(value, Some(Cow::Borrowed("Hello World")));
+    assert!(errors.is_empty(), "Missing optional resources are skipped.");
+}
+
+#[test]
+fn localization_missing_resource_falls_back() {
+    use fluent_bundle::FluentError;
+    use fluent_fallback::LocalizationError;
+
+    let res_mgr = ResourceManager::new("tests/resources/{locale}/{res_id}".into());
+
+    let loc = Localization::with_env(
+        vec!["test.ftl".into(), "invalid.ftl".into()],
+        true,
+        vec!["pl".parse().unwrap(), "en-US".parse().unwrap()],
+        res_mgr,
+    );
+    let bundles = loc.bundles();
+    let mut errors = vec![];
+
+    let value = bundles
+        .format_value_sync("valid-message", None, &mut errors)
+        .unwrap();
+    assert_eq!(value, Some(Cow::Borrowed("This is a valid message")));
+
+    assert!(errors.iter().any(|error| matches!(
+        error,
+        LocalizationError::Bundle {
+            error: FluentError::ResourceLoad { id, .. }
+        } if id == "invalid.ftl"
+    )));
+    assert!(errors.iter().any(|error| matches!(
+        error,
+        LocalizationError::Bundle {
+            error: FluentError::ParserError(_)
+        }
+    )));
+}
@@ -0,0 +1,313 @@
+//! Comparison of a translation with a reference resource.
+//!
+//! Localizations are usually translated from a reference locale, and drift
+//! away from it as the reference changes. The [`compare`] function lists the
+//! [`Difference`]s which are likely mistakes in a translation, such as
+//! missing messages or variables.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_syntax::compare::{compare, Difference};
+//! use fluent_syntax::parser;
+//!
+//! let reference = parser::parse("emails = You have { $count } emails.\n").unwrap();
+//! let translation = parser::parse("emails = Vous avez des courriels.\n").unwrap();
+//!
+//! assert_eq!(
+//!     compare(&reference, &translation),
+//!     vec![Difference::MissingVariable {
+//!         id: "emails".to_string(),
+//!         attribute: None,
+//!         variable: "count".to_string(),
+//!     }]
+//! );
+//! ```
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+use crate::ast;
+use crate::parser::Slice;
+use crate::walk::{walk_pattern, Node};
+
+/// A difference between a translation and its reference.
+///
+/// Ids of terms start with a `-`. Variables are named without the `$`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Difference {
+    /// A message or a term of the reference is missing in the translation.
+    MissingEntry { id: String },
+    /// A message or a term of the translation is not in the reference.
+    ObsoleteEntry { id: String },
+    /// A message of the translation has no value, while the reference has one.
+    MissingValue { id: String },
+    /// A message of the translation has a value, while the reference has none.
+    ExtraValue { id: String },
+    /// An attribute of a message of the reference is missing in the translation.
+    MissingAttribute { id: String, attribute: String },
+    /// An attribute of a message of the translation is not in the reference.
+    ExtraAttribute { id: String, attribute: String },
+    /// A variable used in the reference is not used in the translation.
+    MissingVariable {
+        id: String,
+        attribute: Option<String>,
+        variable: String,
+    },
+    /// A variable used in the translation is not used in the reference, and
+    /// probably won't be passed when formatting.
+    ExtraVariable {
+        id: String,
+        attribute: Option<String>,
+        variable: String,
+    },
+    /// A term referenced in the reference is not referenced in the translation.
+    MissingTermReference {
+        id: String,
+        attribute: Option<String>,
+        term: String,
+    },
+    /// A term referenced in the translation is not referenced in the reference.
+    ExtraTermReference {
+        id: String,
+        attribute: Option<String>,
+        term: String,
+    },
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn path(id: &str, attribute: &Option<String>) -> String {
+            match attribute {
+                Some(attribute) => format!("{}.{}", id, attribute),
+                None => id.to_owned(),
+            }
+        }
+
+        match self {
+            Self::MissingEntry { id } => write!(f, "Missing `{}`", id),
+            Self::ObsoleteEntry { id } => write!(f, "Obsolete `{}`", id),
+            Self::MissingValue { id } => write!(f, "Missing value of `{}`", id),
+            Self::ExtraValue { id } => write!(f, "Extra value of `{}`", id),
+            Self::MissingAttribute { id, attribute } => {
+                write!(f, "Missing attribute `{}.{}`", id, attribute)
+            }
+            Self::ExtraAttribute { id, attribute } => {
+                write!(f, "Extra attribute `{}.{}`", id, attribute)
+            }
+            Self::MissingVariable {
+                id,
+                attribute,
+                variable,
+            } => write!(
+                f,
+                "Missing variable `${}` in `{}`",
+                variable,
+                path(id, attribute)
+            ),
+            Self::ExtraVariable {
+                id,
+                attribute,
+                variable,
+            } => write!(
+                f,
+                "Extra variable `${}` in `{}`",
+                variable,
+                path(id, attribute)
+            ),
+            Self::MissingTermReference {
+                id,
+                attribute,
+                term,
+            } => write!(
+                f,
+                "Missing reference to `-{}` in `{}`",
+                term,
+                path(id, attribute)
+            ),
+            Self::ExtraTermReference {
+                id,
+                attribute,
+                term,
+            } => write!(
+                f,
+                "Extra reference to `-{}` in `{}`",
+                term,
+                path(id, attribute)
+            ),
+        }
+    }
+}
+
+/// Compares a `translation` with its `reference`.
+///
+/// Messages are compared by their value, attributes, and the variables and
+/// terms referenced in each of their patterns. The attributes and the
+/// variables of terms are specific to each locale, so terms are only checked
+/// for being missing or obsolete.
+///
+/// The differences of the entries are listed in the order of the reference,
+/// followed by the obsolete entries in the order of the translation.
+pub fn compare<'s, S: Slice<'s>>(
+    reference: &ast::Resource<S>,
+    translation: &ast::Resource<S>,
+) -> Vec<Difference> {
+    let mut translated = HashMap::new();
+    for entry in &translation.body {
+        if let Some(id) = entry_id(entry) {
+            translated.entry(id).or_insert(entry);
+        }
+    }
+
+    let mut differences = vec![];
+    let mut seen = BTreeSet::new();
+    for entry in &reference.body {
+        let id = match entry_id(entry) {
+            Some(id) => id,
+            None => continue,
+        };
+        if !seen.insert(id.clone()) {
+            continue;
+        }
+        match (entry, translated.get(&id)) {
+            (_, None) => differences.push(Difference::MissingEntry { id }),
+            (ast::Entry::Message(reference), Some(ast::Entry::Message(translation))) => {
+                compare_messages(id, reference, translation, &mut differences);
+            }
+            _ => {}
+        }
+    }
+
+    for entry in &translation.body {
+        if let Some(id) = entry_id(entry) {
+            if !seen.contains(&id) {
+                seen.insert(id.clone());
+                differences.push(Difference::ObsoleteEntry { id });
+            }
+        }
+    }
+    differences
+}
+
+fn compare_messages<'s, S: Slice<'s>>(
+    id: String,
+    reference: &ast::Message<S>,
+    translation: &ast::Message<S>,
+    differences: &mut Vec<Difference>,
+) {
+    match (&reference.value, &translation.value) {
+        (Some(reference), Some(translation)) => {
+            compare_patterns(&id, None, reference, translation, differences);
+        }
+        (Some(_), None) => differences.push(Difference::MissingValue { id: id.clone() }),
+        (None, Some(_)) => differences.push(Difference::ExtraValue { id: id.clone() }),
+        (None, None) => {}
+    }
+
+    for attribute in &reference.attributes {
+        let name = attribute.id.name.as_ref();
+        match translation
+            .attributes
+            .iter()
+            .find(|translated| translated.id.name.as_ref() == name)
+        {
+            Some(translated) => compare_patterns(
+                &id,
+                Some(name),
+                &attribute.value,
+                &translated.value,
+                differences,
+            ),
+            None => differences.push(Difference::MissingAttribute {
+                id: id.clone(),
+                attribute: name.to_owned(),
+            }),
+        }
+    }
+    for attribute in &translation.attributes {
+        let name = attribute.id.name.as_ref();
+        if !reference
+            .attributes
+            .iter()
+            .any(|reference| reference.id.name.as_ref() == name)
+        {
+            differences.push(Difference::ExtraAttribute {
+                id: id.clone(),
+                attribute: name.to_owned(),
+            });
+        }
+    }
+}
+
+fn compare_patterns<'s, S: Slice<'s>>(
+    id: &str,
+    attribute: Option<&str>,
+    reference: &ast::Pattern<S>,
+    translation: &ast::Pattern<S>,
+    differences: &mut Vec<Difference>,
+) {
+    let reference = References::from_pattern(reference);
+    let translation = References::from_pattern(translation);
+    let id = || id.to_owned();
+    let attribute = || attribute.map(str::to_owned);
+
+    for variable in reference.variables.difference(&translation.variables) {
+        differences.push(Difference::MissingVariable {
+            id: id(),
+            attribute: attribute(),
+            variable: variable.to_string(),
+        });
+    }
+    for variable in translation.variables.difference(&reference.variables) {
+        differences.push(Difference::ExtraVariable {
+            id: id(),
+            attribute: attribute(),
+            variable: variable.to_string(),
+        });
+    }
+    for term in reference.terms.difference(&translation.terms) {
+        differences.push(Difference::MissingTermReference {
+            id: id(),
+            attribute: attribute(),
+            term: term.to_string(),
+        });
+    }
+    for term in translation.terms.difference(&reference.terms) {
+        differences.push(Difference::ExtraTermReference {
+            id: id(),
+            attribute: attribute(),
+            term: term.to_string(),
+        });
+    }
+}
+
+fn entry_id<'s, S: Slice<'s>>(entry: &ast::Entry<S>) -> Option<String> {
+    match entry {
+        ast::Entry::Message(message) => Some(message.id.name.as_ref().to_owned()),
+        ast::Entry::Term(term) => Some(format!("-{}", term.id.name.as_ref())),
+        _ => None,
+    }
+}
+
+/// The variables and the terms referenced in a pattern, including its
+/// select expressions and the arguments of its calls.
+#[derive(Default)]
+struct References<'p> {
+    variables: BTreeSet<&'p str>,
+    terms: BTreeSet<&'p str>,
+}
+
+impl<'p> References<'p> {
+    fn from_pattern<'s, S: Slice<'s>>(pattern: &'p ast::Pattern<S>) -> Self {
+        let mut references = Self::default();
+        walk_pattern(pattern, &mut |node| match node {
+            Node::Inline(ast::InlineExpression::VariableReference { id }, _) => {
+                references.variables.insert(id.name.as_ref());
+            }
+            Node::Inline(ast::InlineExpression::TermReference { id, .. }, _) => {
+                references.terms.insert(id.name.as_ref());
+            }
+            _ => {}
+        });
+        references
+    }
+}
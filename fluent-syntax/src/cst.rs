@@ -0,0 +1,284 @@
+//! Lossless representation of a Fluent Translation List.
+//!
+//! The [`serializer`](crate::serializer) normalizes the whitespace, indentation
+//! and blank lines of the whole resource. A [`Document`] keeps the source text
+//! of every entry, as well as the trivia between the entries, so that it
+//! serializes back byte-for-byte. Entries can be inserted, removed or modified,
+//! and only those entries are normalized, while the rest of the document is
+//! written exactly as it was parsed.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_syntax::ast;
+//! use fluent_syntax::cst;
+//!
+//! let ftl = "key1   =   Value 1\n\n\nkey2 = Value 2\n";
+//!
+//! let mut document = cst::parse(ftl).expect("Failed to parse an FTL resource.");
+//! assert_eq!(document.serialize(), ftl);
+//!
+//! if let Some(ast::Entry::Message(message)) = document.get_mut(1) {
+//!     message.id.name = "key3";
+//! }
+//! assert_eq!(document.serialize(), "key1   =   Value 1\n\n\nkey3 = Value 2\n");
+//! ```
+use crate::ast;
+use crate::parser::{self, ParserError, Slice};
+use crate::serializer::{Options, Serializer};
+
+/// The result of [`parse`], with the errors of the entries parsed as
+/// [`ast::Entry::Junk`].
+pub type Result<S> = std::result::Result<Document<S>, (Document<S>, Vec<ParserError>)>;
+
+/// Parses `source` into a [`Document`].
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::cst;
+///
+/// let ftl = "# Comment\nkey = Value\n\n   \n-term = Term\n";
+///
+/// let document = cst::parse(ftl).expect("Failed to parse an FTL resource.");
+///
+/// assert_eq!(document.len(), 2);
+/// assert_eq!(document.serialize(), ftl);
+/// ```
+pub fn parse<'s, S>(source: S) -> Result<S>
+where
+    S: Slice<'s>,
+{
+    let (result, spans) = parser::parse_with_spans(source.clone());
+    let (resource, errors) = match result {
+        Ok(resource) => (resource, vec![]),
+        Err(result) => result,
+    };
+    let mut items = vec![];
+    let mut pos = 0;
+    for (idx, entry) in resource.body.into_iter().enumerate() {
+        let span = spans.entry(idx).expect("The parser records spans");
+        if pos < span.start {
+            items.push(Item::Trivia(source.slice(pos..span.start)));
+        }
+        pos = span.end;
+        items.push(Item::Entry {
+            entry,
+            text: Some(source.slice(span)),
+        });
+    }
+    let length = source.as_ref().len();
+    if pos < length {
+        items.push(Item::Trivia(source.slice(pos..length)));
+    }
+
+    let document = Document { items };
+    if errors.is_empty() {
+        Ok(document)
+    } else {
+        Err((document, errors))
+    }
+}
+
+/// A list of [`ast::Entry`] nodes together with the source text they were
+/// parsed from.
+///
+/// Entries which were inserted, or accessed with [`Document::get_mut`], are
+/// written with the [`Serializer`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Document<S> {
+    items: Vec<Item<S>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Item<S> {
+    /// Source text between two entries.
+    Trivia(S),
+    /// A line break after an inserted entry.
+    Newline,
+    Entry {
+        entry: ast::Entry<S>,
+        /// The source text of the entry, unless it has been modified.
+        text: Option<S>,
+    },
+}
+
+impl<S> Default for Document<S> {
+    fn default() -> Self {
+        Self { items: vec![] }
+    }
+}
+
+impl<'s, S> Document<S>
+where
+    S: Slice<'s>,
+{
+    /// Returns the number of entries in the document.
+    pub fn len(&self) -> usize {
+        self.entries().count()
+    }
+
+    /// Returns `true` if the document has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries().next().is_none()
+    }
+
+    /// Returns an iterator over the entries of the document.
+    pub fn entries(&self) -> impl Iterator<Item = &ast::Entry<S>> {
+        self.items.iter().filter_map(|item| match item {
+            Item::Entry { entry, .. } => Some(entry),
+            _ => None,
+        })
+    }
+
+    /// Returns the entry at `idx`.
+    pub fn get(&self, idx: usize) -> Option<&ast::Entry<S>> {
+        self.entries().nth(idx)
+    }
+
+    /// Returns the entry at `idx` for modification.
+    ///
+    /// The entry is serialized anew, even if it's left unchanged.
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut ast::Entry<S>> {
+        let pos = self.position(idx)?;
+        match &mut self.items[pos] {
+            Item::Entry { entry, text } => {
+                *text = None;
+                Some(entry)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Inserts `entry` on its own line before the entry at `idx`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx > len`.
+    pub fn insert(&mut self, idx: usize, entry: ast::Entry<S>) {
+        match self.position(idx) {
+            Some(pos) => {
+                self.items
+                    .splice(pos..pos, [Item::Entry { entry, text: None }, Item::Newline]);
+            }
+            None => {
+                assert_eq!(idx, self.len(), "Insertion index is out of bounds");
+                self.push(entry);
+            }
+        }
+    }
+
+    /// Appends `entry` on its own line at the end of the document.
+    pub fn push(&mut self, entry: ast::Entry<S>) {
+        let ends_with_newline = match self.items.last() {
+            None | Some(Item::Newline) => true,
+            Some(Item::Trivia(text)) => text.as_ref().ends_with('\n'),
+            Some(Item::Entry { entry, text }) => match (entry, text) {
+                (ast::Entry::Junk { content }, None) => content.as_ref().ends_with('\n'),
+                (_, Some(text)) => text.as_ref().ends_with('\n'),
+                (_, None) => false,
+            },
+        };
+        if !ends_with_newline {
+            self.items.push(Item::Newline);
+        }
+        self.items.push(Item::Entry { entry, text: None });
+        self.items.push(Item::Newline);
+    }
+
+    /// Removes and returns the entry at `idx`, together with the line break
+    /// following it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx >= len`.
+    pub fn remove(&mut self, idx: usize) -> ast::Entry<S> {
+        let pos = self.position(idx).expect("Removal index is out of bounds");
+        let entry = match self.items.remove(pos) {
+            Item::Entry { entry, .. } => entry,
+            _ => unreachable!(),
+        };
+
+        match self.items.get_mut(pos) {
+            Some(Item::Newline) => {
+                self.items.remove(pos);
+            }
+            Some(Item::Trivia(text)) => {
+                let line_break = if text.as_ref().starts_with("\r\n") {
+                    2
+                } else if text.as_ref().starts_with('\n') {
+                    1
+                } else {
+                    0
+                };
+                let length = text.as_ref().len();
+                if line_break == length {
+                    self.items.remove(pos);
+                } else {
+                    *text = text.slice(line_break..length);
+                }
+            }
+            _ => {}
+        }
+        entry
+    }
+
+    /// Converts the document into an AST.
+    pub fn into_resource(self) -> ast::Resource<S> {
+        let body = self
+            .items
+            .into_iter()
+            .filter_map(|item| match item {
+                Item::Entry { entry, .. } => Some(entry),
+                _ => None,
+            })
+            .collect();
+        ast::Resource { body }
+    }
+
+    /// Serializes the document, writing the unmodified entries and the trivia
+    /// between them exactly as they were parsed.
+    pub fn serialize(&self) -> String {
+        let mut result = String::new();
+        for item in &self.items {
+            match item {
+                Item::Trivia(text)
+                | Item::Entry {
+                    text: Some(text), ..
+                } => result.push_str(text.as_ref()),
+                Item::Newline => result.push('\n'),
+                Item::Entry { entry, text: None } => result.push_str(&serialize_entry(entry)),
+            }
+        }
+        result
+    }
+
+    /// Returns the position in `items` of the entry at `idx`.
+    fn position(&self, idx: usize) -> Option<usize> {
+        self.items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| matches!(item, Item::Entry { .. }))
+            .nth(idx)
+            .map(|(pos, _)| pos)
+    }
+}
+
+/// Serializes a single entry, without the line break the serializer writes
+/// after it.
+fn serialize_entry<'s, S: Slice<'s>>(entry: &ast::Entry<S>) -> String {
+    let mut ser = Serializer::new(Options::default().with_junk(true));
+    match entry {
+        ast::Entry::Message(msg) => ser.serialize_message(msg),
+        ast::Entry::Term(term) => ser.serialize_term(term),
+        ast::Entry::Comment(comment) => ser.serialize_free_comment(comment, "#"),
+        ast::Entry::GroupComment(comment) => ser.serialize_free_comment(comment, "##"),
+        ast::Entry::ResourceComment(comment) => ser.serialize_free_comment(comment, "###"),
+        ast::Entry::Junk { content } => return content.as_ref().to_owned(),
+    }
+    let mut text = ser.into_serialized_text();
+    if text.ends_with('\n') {
+        text.pop();
+    }
+    text
+}
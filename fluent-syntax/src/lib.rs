@@ -8,6 +8,14 @@
 //! The crate provides a [`parser`] module which allows for parsing of an
 //! input string to an Abstract Syntax Tree defined in the [`ast`] module.
 //!
+//! The [`cst`] module keeps the source text of a parsed resource, so that it can
+//! be modified without reformatting the entries which weren't changed.
+//!
+//! The [`compare`] module compares a translation with a reference resource.
+//!
+//! The [`walk`] module visits the expressions of a pattern, for tools analysing the
+//! references and variables of messages.
+//!
 //! The [`unicode`] module exposes a set of helper functions used to decode
 //! escaped unicode literals according to Fluent specification.
 //!
@@ -47,6 +55,9 @@
 //! );
 //! ```
 pub mod ast;
+pub mod compare;
+pub mod cst;
 pub mod parser;
 pub mod serializer;
 pub mod unicode;
+pub mod walk;
@@ -1,4 +1,4 @@
-use super::{core::Parser, core::Result, Slice};
+use super::{core::Parser, core::Result, spans::Kind, Slice};
 use crate::ast;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -14,6 +14,8 @@ where
     S: Slice<'s>,
 {
     pub(super) fn get_comment(&mut self) -> Result<(ast::Comment<S>, Level)> {
+        let start = self.ptr;
+        let mut end = self.ptr;
         let mut level = Level::None;
         let mut content = vec![];
 
@@ -33,6 +35,7 @@ where
                 break;
             } else if self.is_eol() {
                 content.push(self.get_comment_line());
+                end = self.ptr;
             } else {
                 if let Err(e) = self.expect_byte(b' ') {
                     if content.is_empty() {
@@ -43,10 +46,12 @@ where
                     }
                 }
                 content.push(self.get_comment_line());
+                end = self.ptr;
             }
             self.skip_eol();
         }
 
+        self.record_span(Kind::Comment, start..end);
         Ok((ast::Comment { content }, level))
     }
 
@@ -2,8 +2,10 @@ use super::{
     comment,
     errors::{ErrorKind, ParserError},
     slice::Slice,
+    spans::{Kind, Spans},
 };
 use crate::ast;
+use std::ops::Range;
 
 pub type Result<T> = std::result::Result<T, ParserError>;
 
@@ -11,6 +13,7 @@ pub struct Parser<S> {
     pub(super) source: S,
     pub(super) ptr: usize,
     pub(super) length: usize,
+    pub(super) spans: Option<Vec<(Range<usize>, Kind)>>,
 }
 
 impl<'s, S> Parser<S>
@@ -23,12 +26,39 @@ where
             source,
             ptr: 0,
             length,
+            spans: None,
         }
     }
 
     pub fn parse(
         mut self,
     ) -> std::result::Result<ast::Resource<S>, (ast::Resource<S>, Vec<ParserError>)> {
+        let (body, errors) = self.get_entries();
+        if errors.is_empty() {
+            Ok(ast::Resource { body })
+        } else {
+            Err((ast::Resource { body }, errors))
+        }
+    }
+
+    /// Parses like [`Self::parse`], recording the span of every node.
+    pub fn parse_with_spans(mut self) -> (super::Result<S>, Spans) {
+        self.spans = Some(vec![]);
+        let (body, errors) = self.get_entries();
+        let spans = self.take_spans(&body);
+        if errors.is_empty() {
+            (Ok(ast::Resource { body }), spans)
+        } else {
+            (Err((ast::Resource { body }, errors)), spans)
+        }
+    }
+
+    /// Returns the spans recorded while parsing `body`.
+    pub(super) fn take_spans(&mut self, body: &[ast::Entry<S>]) -> Spans {
+        Spans::new(body, self.spans.take().unwrap_or_default(), self.length)
+    }
+
+    fn get_entries(&mut self) -> (Vec<ast::Entry<S>>, Vec<ParserError>) {
         let mut errors = vec![];
 
         let mut body = vec![];
@@ -38,48 +68,61 @@ where
         let mut last_blank_count = 0;
 
         while self.ptr < self.length {
-            let entry_start = self.ptr;
-            let mut entry = self.get_entry(entry_start);
-
-            if let Some(comment) = last_comment.take() {
-                match entry {
-                    Ok(ast::Entry::Message(ref mut msg)) if last_blank_count < 2 => {
-                        msg.comment = Some(comment);
-                    }
-                    Ok(ast::Entry::Term(ref mut term)) if last_blank_count < 2 => {
-                        term.comment = Some(comment);
-                    }
-                    _ => {
-                        body.push(ast::Entry::Comment(comment));
-                    }
-                }
-            }
+            self.parse_entry(&mut body, &mut errors, &mut last_comment, last_blank_count);
+            last_blank_count = self.skip_blank_block();
+        }
 
+        if let Some(last_comment) = last_comment.take() {
+            body.push(ast::Entry::Comment(last_comment));
+        }
+        (body, errors)
+    }
+
+    /// Parses the entry at the current position into `body`, attaching the
+    /// pending `last_comment` to it or pushing the comment on its own.
+    pub(super) fn parse_entry(
+        &mut self,
+        body: &mut Vec<ast::Entry<S>>,
+        errors: &mut Vec<ParserError>,
+        last_comment: &mut Option<ast::Comment<S>>,
+        last_blank_count: usize,
+    ) {
+        let entry_start = self.ptr;
+        let mark = self.spans_mark();
+        let mut entry = self.get_entry(entry_start);
+
+        if let Some(comment) = last_comment.take() {
             match entry {
-                Ok(ast::Entry::Comment(comment)) => {
-                    last_comment = Some(comment);
+                Ok(ast::Entry::Message(ref mut msg)) if last_blank_count < 2 => {
+                    self.record_attached_comment();
+                    msg.comment = Some(comment);
                 }
-                Ok(entry) => {
-                    body.push(entry);
+                Ok(ast::Entry::Term(ref mut term)) if last_blank_count < 2 => {
+                    self.record_attached_comment();
+                    term.comment = Some(comment);
                 }
-                Err(mut err) => {
-                    self.skip_to_next_entry_start();
-                    err.slice = Some(entry_start..self.ptr);
-                    errors.push(err);
-                    let content = self.source.slice(entry_start..self.ptr);
-                    body.push(ast::Entry::Junk { content });
+                _ => {
+                    body.push(ast::Entry::Comment(comment));
                 }
             }
-            last_blank_count = self.skip_blank_block();
         }
 
-        if let Some(last_comment) = last_comment.take() {
-            body.push(ast::Entry::Comment(last_comment));
-        }
-        if errors.is_empty() {
-            Ok(ast::Resource { body })
-        } else {
-            Err((ast::Resource { body }, errors))
+        match entry {
+            Ok(ast::Entry::Comment(comment)) => {
+                *last_comment = Some(comment);
+            }
+            Ok(entry) => {
+                body.push(entry);
+            }
+            Err(mut err) => {
+                self.skip_to_next_entry_start();
+                err.slice = Some(entry_start..self.ptr);
+                errors.push(err);
+                let content = self.source.slice(entry_start..self.ptr);
+                self.rewind_spans(mark);
+                self.record(Kind::Junk, entry_start);
+                body.push(ast::Entry::Junk { content });
+            }
         }
     }
 
@@ -118,6 +161,7 @@ where
             );
         }
 
+        self.record_to_last(Kind::Message, entry_start);
         Ok(ast::Message {
             id,
             value: pattern,
@@ -140,6 +184,7 @@ where
         let attributes = self.get_attributes();
 
         if let Some(value) = value {
+            self.record_to_last(Kind::Term, entry_start);
             Ok(ast::Term {
                 id,
                 value,
@@ -162,29 +207,35 @@ where
         loop {
             let line_start = self.ptr;
             self.skip_blank_inline();
+            let attr_start = self.ptr;
             if !self.take_byte_if(b'.') {
                 self.ptr = line_start;
                 break;
             }
 
-            if let Ok(attr) = self.get_attribute() {
+            let mark = self.spans_mark();
+            if let Ok(attr) = self.get_attribute(attr_start) {
                 attributes.push(attr);
             } else {
                 self.ptr = line_start;
+                self.rewind_spans(mark);
                 break;
             }
         }
         attributes
     }
 
-    fn get_attribute(&mut self) -> Result<ast::Attribute<S>> {
+    fn get_attribute(&mut self, attr_start: usize) -> Result<ast::Attribute<S>> {
         let id = self.get_identifier()?;
         self.skip_blank_inline();
         self.expect_byte(b'=')?;
         let pattern = self.get_pattern()?;
 
         match pattern {
-            Some(pattern) => Ok(ast::Attribute { id, value: pattern }),
+            Some(pattern) => {
+                self.record_to_last(Kind::Attribute, attr_start);
+                Ok(ast::Attribute { id, value: pattern })
+            }
             None => error!(ErrorKind::MissingValue, self.ptr),
         }
     }
@@ -197,8 +248,10 @@ where
             ptr += 1;
         }
 
-        let name = self.source.slice(self.ptr - 1..ptr);
+        let start = self.ptr - 1;
+        let name = self.source.slice(start..ptr);
         self.ptr = ptr;
+        self.record(Kind::Identifier, start);
 
         ast::Identifier { name }
     }
@@ -228,6 +281,8 @@ where
     fn get_variant_key(&mut self) -> Result<ast::VariantKey<S>> {
         self.skip_blank();
 
+        let start = self.ptr;
+        let mark = self.spans_mark();
         let key = if self.is_number_start() {
             ast::VariantKey::NumberLiteral {
                 value: self.get_number_literal()?,
@@ -237,6 +292,8 @@ where
                 name: self.get_identifier()?.name,
             }
         };
+        self.rewind_spans(mark);
+        self.record(Kind::VariantKey, start);
 
         self.skip_blank();
 
@@ -250,6 +307,7 @@ where
         let mut has_default = false;
 
         loop {
+            let start = self.ptr;
             let default = self.take_byte_if(b'*');
             if default {
                 if has_default {
@@ -268,6 +326,7 @@ where
             let value = self.get_pattern()?;
 
             if let Some(value) = value {
+                self.record_to_last(Kind::Variant, start);
                 variants.push(ast::Variant {
                     key,
                     value,
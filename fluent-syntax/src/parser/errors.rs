@@ -1,3 +1,4 @@
+use std::fmt;
 use std::ops::Range;
 use thiserror::Error;
 
@@ -90,8 +91,8 @@ use thiserror::Error;
 /// ```
 ///
 /// The information contained in the `ParserError` should allow the tooling
-/// to display rich contextual annotations of the error slice, using
-/// crates such as `annotate-snippers`.
+/// to display rich contextual annotations of the error slice. The
+/// [`ParserError::diagnostic`] method renders such an annotation.
 #[derive(Clone, Debug, Eq, Error, PartialEq)]
 #[error("{}", self.kind)]
 pub struct ParserError {
@@ -104,6 +105,48 @@ pub struct ParserError {
     pub kind: ErrorKind,
 }
 
+impl ParserError {
+    /// Returns the line and column at which the error starts in `source`.
+    pub fn line_column(&self, source: &str) -> LineColumn {
+        LineColumn::from_offset(source, self.pos.start)
+    }
+
+    /// Returns a [`Diagnostic`] which renders the error with an annotated
+    /// snippet of `source`, the input in which the error was encountered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_syntax::parser;
+    ///
+    /// let ftl = "key1 = Value 1\nkey2 Value 2\n";
+    ///
+    /// let (_, errors) = parser::parse(ftl)
+    ///     .expect_err("Resource should contain errors.");
+    ///
+    /// let diagnostic = errors[0].diagnostic(ftl).with_origin("main.ftl");
+    /// assert_eq!(
+    ///     diagnostic.to_string(),
+    ///     concat!(
+    ///         "error[E0003]: Expected a token starting with \"=\"\n",
+    ///         " --> main.ftl:2:6\n",
+    ///         "  |\n",
+    ///         "2 | key2 Value 2\n",
+    ///         "  |      ^\n",
+    ///         "  |\n",
+    ///         "  = help: Messages and terms are defined as `id = value`.\n",
+    ///     )
+    /// );
+    /// ```
+    pub fn diagnostic<'s>(&'s self, source: &'s str) -> Diagnostic<'s> {
+        Diagnostic {
+            error: self,
+            source,
+            origin: None,
+        }
+    }
+}
+
 macro_rules! error {
     ($kind:expr, $start:expr) => {{
         Err(ParserError {
@@ -167,3 +210,240 @@ pub enum ErrorKind {
     #[error("Expected a string or number literal")]
     ExpectedLiteral,
 }
+
+impl ErrorKind {
+    /// Returns the code of the error, as used by the reference implementations
+    /// of Fluent.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ExpectedToken(_) => "E0003",
+            Self::ExpectedCharRange { .. } => "E0004",
+            Self::ExpectedMessageField { .. } => "E0005",
+            Self::ExpectedTermField { .. } => "E0006",
+            Self::ForbiddenCallee => "E0008",
+            Self::MissingDefaultVariant => "E0010",
+            Self::MissingValue => "E0012",
+            Self::ExpectedLiteral => "E0014",
+            Self::MultipleDefaultVariants => "E0015",
+            Self::MessageReferenceAsSelector => "E0016",
+            Self::TermReferenceAsSelector => "E0017",
+            Self::MessageAttributeAsSelector => "E0018",
+            Self::TermAttributeAsPlaceable => "E0019",
+            Self::UnterminatedStringLiteral => "E0020",
+            Self::PositionalArgumentFollowsNamed => "E0021",
+            Self::DuplicatedNamedArgument(_) => "E0022",
+            Self::UnknownEscapeSequence(_) => "E0025",
+            Self::InvalidUnicodeEscapeSequence(_) => "E0026",
+            Self::UnbalancedClosingBrace => "E0027",
+            Self::ExpectedInlineExpression => "E0028",
+            Self::ExpectedSimpleExpressionAsSelector => "E0029",
+        }
+    }
+
+    /// Returns a hint on how to fix the error.
+    pub fn help(&self) -> String {
+        match self {
+            Self::ExpectedToken('=') => {
+                "Messages and terms are defined as `id = value`.".to_string()
+            }
+            Self::ExpectedToken(token) => format!("Insert \"{}\" here.", token),
+            Self::ExpectedCharRange { range } if range == "a-zA-Z" => {
+                "Identifiers must start with a letter, and may only contain letters, digits, `-` and `_`."
+                    .to_string()
+            }
+            Self::ExpectedCharRange { range } if range == "\n | \r\n" => {
+                "The variants of a select expression start on a new line after `->`.".to_string()
+            }
+            Self::ExpectedCharRange { range } if range == "0-9" => {
+                "Number literals need digits before and after the `.`, like `-1.5`.".to_string()
+            }
+            Self::ExpectedCharRange { range } => format!("Insert one of \"{}\" here.", range),
+            Self::ExpectedMessageField { entry_id } => {
+                format!("Give \"{}\" a value or at least one attribute.", entry_id)
+            }
+            Self::ExpectedTermField { entry_id } => {
+                format!("Give \"-{}\" a value.", entry_id)
+            }
+            Self::ForbiddenCallee => {
+                "Only functions, whose names are upper-case like `NUMBER`, can be called."
+                    .to_string()
+            }
+            Self::MissingDefaultVariant => {
+                "Mark one of the variants as the default with `*`, like `*[other]`.".to_string()
+            }
+            Self::MissingValue => "Add a value after the key.".to_string(),
+            Self::MultipleDefaultVariants => {
+                "Remove the `*` from all but one of the variants.".to_string()
+            }
+            Self::MessageReferenceAsSelector | Self::MessageAttributeAsSelector => {
+                "Select on a variable, a literal, a function call or a term attribute instead."
+                    .to_string()
+            }
+            Self::TermReferenceAsSelector => {
+                "Select on an attribute of the term instead, like `-term.gender`.".to_string()
+            }
+            Self::TermAttributeAsPlaceable => {
+                "Term attributes can only be used as selectors, like `{ -term.gender -> ... }`."
+                    .to_string()
+            }
+            Self::UnterminatedStringLiteral => {
+                "Close the string literal with `\"` on the same line.".to_string()
+            }
+            Self::PositionalArgumentFollowsNamed => {
+                "Move the positional arguments before the named arguments.".to_string()
+            }
+            Self::DuplicatedNamedArgument(name) => {
+                format!("Remove one of the \"{}\" arguments.", name)
+            }
+            Self::UnknownEscapeSequence(_) => {
+                "The supported escape sequences are `\\\\`, `\\\"`, `\\{`, `\\uXXXX` and `\\UXXXXXX`."
+                    .to_string()
+            }
+            Self::InvalidUnicodeEscapeSequence(_) => {
+                "Unicode escape sequences consist of `\\u` and 4 hexadecimal digits, or `\\U` and 6."
+                    .to_string()
+            }
+            Self::UnbalancedClosingBrace => {
+                "Use a string literal to write a brace in text, like `{ \"}\" }`.".to_string()
+            }
+            Self::ExpectedInlineExpression => {
+                "Placeables contain a literal, a variable, a reference or a function call."
+                    .to_string()
+            }
+            Self::ExpectedSimpleExpressionAsSelector => {
+                "Select on a variable, a literal, a function call or a term attribute instead."
+                    .to_string()
+            }
+            Self::ExpectedLiteral => {
+                "Named arguments only accept string or number literals.".to_string()
+            }
+        }
+    }
+}
+
+/// A position in a source, with a 1-based line and a 1-based column counted in
+/// characters.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl LineColumn {
+    /// Returns the line and column of the byte `offset` in `source`. Offsets
+    /// past the end of the source point at its end.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_syntax::parser::LineColumn;
+    ///
+    /// let source = "key = Value\nkey2 = Välue\n";
+    /// assert_eq!(LineColumn::from_offset(source, 0), LineColumn { line: 1, column: 1 });
+    /// assert_eq!(LineColumn::from_offset(source, 22), LineColumn { line: 2, column: 10 });
+    /// ```
+    pub fn from_offset(source: &str, offset: usize) -> Self {
+        let offset = floor_char_boundary(source, offset);
+        let line_start = line_start(source, offset);
+        Self {
+            line: source[..line_start].matches('\n').count() + 1,
+            column: source[line_start..offset].chars().count() + 1,
+        }
+    }
+}
+
+impl fmt::Display for LineColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+fn floor_char_boundary(source: &str, offset: usize) -> usize {
+    let mut offset = offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+fn line_start(source: &str, offset: usize) -> usize {
+    source[..offset].rfind('\n').map_or(0, |idx| idx + 1)
+}
+
+/// A [`ParserError`] paired with its source, which renders as an annotated
+/// snippet of the source with the location of the error underlined.
+///
+/// Created with [`ParserError::diagnostic`].
+#[derive(Clone, Copy, Debug)]
+pub struct Diagnostic<'s> {
+    error: &'s ParserError,
+    source: &'s str,
+    origin: Option<&'s str>,
+}
+
+impl<'s> Diagnostic<'s> {
+    /// Sets the name of the source, usually its path, shown with the location.
+    pub fn with_origin(mut self, origin: &'s str) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    /// Returns the position at which the error starts.
+    pub fn start(&self) -> LineColumn {
+        LineColumn::from_offset(self.source, self.error.pos.start)
+    }
+
+    /// Returns the position right after the end of the error.
+    pub fn end(&self) -> LineColumn {
+        LineColumn::from_offset(self.source, self.error.pos.end)
+    }
+}
+
+impl fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source = self.source;
+        let start = floor_char_boundary(source, self.error.pos.start);
+        let end = floor_char_boundary(source, self.error.pos.end).max(start);
+        let start_pos = self.start();
+        // The range is exclusive, so an error ending right after a line break
+        // doesn't extend to the next line.
+        let last = floor_char_boundary(source, end.saturating_sub(1)).max(start);
+        let end_line = LineColumn::from_offset(source, last).line;
+        let gutter = " ".repeat(end_line.to_string().len());
+
+        writeln!(f, "error[{}]: {}", self.error.kind.code(), self.error.kind)?;
+        match self.origin {
+            Some(origin) => writeln!(f, "{}--> {}:{}", gutter, origin, start_pos)?,
+            None => writeln!(f, "{}--> {}", gutter, start_pos)?,
+        }
+        writeln!(f, "{} |", gutter)?;
+
+        let mut line_start = line_start(source, start);
+        for line in start_pos.line..=end_line {
+            let line_end = source[line_start..]
+                .find('\n')
+                .map_or(source.len(), |idx| line_start + idx);
+            let text = source[line_start..line_end].trim_end_matches('\r');
+
+            // Underline the part of the line covered by the error, or the end of
+            // the line if the error points past it.
+            let from = start.max(line_start).min(line_start + text.len());
+            let to = end.min(line_start + text.len()).max(from);
+            let padding: String = text[..from - line_start]
+                .chars()
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            let carets = text[from - line_start..to - line_start]
+                .chars()
+                .count()
+                .max(1);
+
+            writeln!(f, "{:>width$} | {}", line, text, width = gutter.len())?;
+            writeln!(f, "{} | {}{}", gutter, padding, "^".repeat(carets))?;
+            line_start = line_end + 1;
+        }
+
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} = help: {}", gutter, self.error.kind.help())
+    }
+}
@@ -1,5 +1,5 @@
 use super::errors::{ErrorKind, ParserError};
-use super::{core::Parser, core::Result, slice::Slice};
+use super::{core::Parser, core::Result, slice::Slice, spans::Kind};
 use crate::ast;
 
 impl<'s, S> Parser<S>
@@ -7,6 +7,7 @@ where
     S: Slice<'s>,
 {
     pub(super) fn get_expression(&mut self) -> Result<ast::Expression<S>> {
+        let start = self.ptr;
         let exp = self.get_inline_expression(false)?;
 
         self.skip_blank();
@@ -56,6 +57,7 @@ where
         self.skip_blank();
 
         let variants = self.get_variants()?;
+        self.record_to_last(Kind::Expression, start);
 
         Ok(ast::Expression::Select {
             selector: exp,
@@ -66,6 +68,16 @@ where
     pub(super) fn get_inline_expression(
         &mut self,
         only_literal: bool,
+    ) -> Result<ast::InlineExpression<S>> {
+        let start = self.ptr;
+        let exp = self.get_inline_expression_unrecorded(only_literal)?;
+        self.record(Kind::InlineExpression, start);
+        Ok(exp)
+    }
+
+    fn get_inline_expression_unrecorded(
+        &mut self,
+        only_literal: bool,
     ) -> Result<ast::InlineExpression<S>> {
         match get_current_byte!(self) {
             Some(b'"') => {
@@ -158,6 +170,7 @@ where
 
     pub fn get_call_arguments(&mut self) -> Result<Option<ast::CallArguments<S>>> {
         self.skip_blank();
+        let start = self.ptr;
         if !self.take_byte_if(b'(') {
             return Ok(None);
         }
@@ -173,6 +186,8 @@ where
                 break;
             }
 
+            let expr_start = self.ptr;
+            let mark = self.spans_mark();
             let expr = self.get_inline_expression(false)?;
 
             if let ast::InlineExpression::MessageReference {
@@ -188,9 +203,14 @@ where
                             self.ptr
                         );
                     }
+                    // The name was recorded as a message reference.
+                    self.rewind_spans(mark);
+                    let name_end = expr_start + id.name.as_ref().len();
+                    self.record_span(Kind::Identifier, expr_start..name_end);
                     self.ptr += 1;
                     self.skip_blank();
                     let val = self.get_inline_expression(true)?;
+                    self.record(Kind::NamedArgument, expr_start);
 
                     argument_names.push(id.name.clone());
                     named.push(ast::NamedArgument {
@@ -218,6 +238,7 @@ where
         }
 
         self.expect_byte(b')')?;
+        self.record(Kind::CallArguments, start);
 
         Ok(Some(ast::CallArguments { positional, named }))
     }
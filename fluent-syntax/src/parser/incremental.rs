@@ -0,0 +1,197 @@
+use std::ops::Range;
+
+use super::{core::Parser, slice::Slice, spans::Spans, Result};
+use crate::ast;
+
+/// A change to the source of a [`Resource`](ast::Resource), replacing the bytes
+/// in `range` of the previous source with `len` new bytes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TextEdit {
+    /// The byte range of the previous source which was replaced.
+    pub range: Range<usize>,
+    /// The length in bytes of the text which replaced it.
+    pub len: usize,
+}
+
+impl TextEdit {
+    /// Creates an edit replacing `range` of the previous source with `text`.
+    pub fn new(range: Range<usize>, text: &str) -> Self {
+        Self {
+            range,
+            len: text.len(),
+        }
+    }
+
+    /// Maps a position outside of the edited range from the previous source to
+    /// the new one.
+    fn map(&self, pos: usize) -> usize {
+        if pos <= self.range.start {
+            pos
+        } else {
+            pos - self.range.end + self.range.start + self.len
+        }
+    }
+}
+
+/// Parses `source`, the result of applying `edit` to the source of `resource`,
+/// by re-parsing only the entries affected by the edit.
+///
+/// The `spans` must be the ones returned with `resource` by
+/// [`parse_with_spans`](super::parse_with_spans) or by a previous call to
+/// [`reparse`]. The result, including the errors and the spans, is the same as
+/// parsing the whole `source` with [`parse_with_spans`](super::parse_with_spans).
+/// If `spans` weren't recorded for the entries of `resource`, or the edit doesn't
+/// match the length of `source`, the whole `source` is parsed.
+///
+/// Entries which come after the edit keep their nodes, with their spans moved
+/// to the new position. Junk entries are always re-parsed, so that the errors
+/// they contain are reported again.
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::parser::{self, TextEdit};
+///
+/// let ftl = "key1 = Value 1\nkey2 = Value 2\n";
+/// let (resource, spans) = parser::parse_with_spans(ftl);
+/// let resource = resource.expect("Failed to parse an FTL resource.");
+///
+/// // Replace "Value 1" with "New value".
+/// let new_ftl = "key1 = New value\nkey2 = Value 2\n";
+/// let edit = TextEdit::new(7..14, "New value");
+///
+/// let (result, spans) = parser::reparse(resource, &spans, new_ftl, &edit);
+/// let resource = result.expect("Failed to parse an FTL resource.");
+///
+/// assert_eq!(resource, parser::parse(new_ftl).unwrap());
+/// let entry = &resource.body[1];
+/// assert_eq!(spans.get(1, entry, entry), Some(17..31));
+/// ```
+pub fn reparse<'s, S>(
+    resource: ast::Resource<S>,
+    spans: &Spans,
+    source: S,
+    edit: &TextEdit,
+) -> (Result<S>, Spans)
+where
+    S: Slice<'s>,
+{
+    let new_len = source.as_ref().len();
+    let old_len = spans.len();
+    if resource.body.is_empty() || !spans.matches(&resource.body) {
+        return Parser::new(source).parse_with_spans();
+    }
+    let starts: Vec<usize> = (0..resource.body.len())
+        .map(|idx| spans.entry(idx).expect("Every entry has a span").start)
+        .collect();
+    let Range { start, end } = edit.range;
+    if start > end || end > old_len || old_len - (end - start) + edit.len != new_len {
+        return Parser::new(source).parse_with_spans();
+    }
+
+    let count = starts.len();
+    // The range of an entry extends to the start of the next one, so that edits
+    // of the blank lines between two entries affect both.
+    let extent = |idx: usize| {
+        let from = if idx == 0 { 0 } else { starts[idx] };
+        let to = starts.get(idx + 1).copied().unwrap_or(old_len);
+        from..to
+    };
+
+    let mut reusable: Vec<bool> = resource
+        .body
+        .iter()
+        .enumerate()
+        .map(|(idx, entry)| {
+            let extent = extent(idx);
+            !matches!(entry, ast::Entry::Junk { .. }) && (end < extent.start || extent.end < start)
+        })
+        .collect();
+    // A comment may become attached to the entry following it, or merged with
+    // the comment following it. Junk may start with an indented line, which
+    // the entry before it may take as a continuation after the edit.
+    for idx in (1..count).rev() {
+        let next = &resource.body[idx];
+        if matches!(next, ast::Entry::Junk { .. })
+            || (!reusable[idx] && is_comment(&resource.body[idx - 1]))
+        {
+            reusable[idx - 1] = false;
+        }
+    }
+
+    // The spans of the reusable entries, moved to the new source.
+    let mut entry_spans: Vec<_> = reusable
+        .iter()
+        .enumerate()
+        .map(|(idx, reusable)| {
+            if *reusable {
+                spans.of_entry(idx, |pos| edit.map(pos))
+            } else {
+                vec![]
+            }
+        })
+        .collect();
+
+    // The start and the end of the reusable entries in the new source.
+    let mut entries = resource
+        .body
+        .into_iter()
+        .enumerate()
+        .filter(|(idx, _)| reusable[*idx])
+        .map(|(idx, entry)| {
+            let spans = std::mem::take(&mut entry_spans[idx]);
+            (
+                edit.map(starts[idx]),
+                edit.map(extent(idx).end),
+                entry,
+                spans,
+            )
+        })
+        .peekable();
+
+    let mut parser = Parser::new(source);
+    parser.spans = Some(vec![]);
+    let mut body = Vec::with_capacity(count);
+    let mut errors = vec![];
+    let mut last_comment = None;
+    let mut last_blank_count = parser.skip_blank_block();
+
+    while parser.ptr < parser.length {
+        while entries.next_if(|(from, ..)| *from < parser.ptr).is_some() {}
+
+        match entries.peek() {
+            Some((from, ..)) if *from == parser.ptr && last_comment.is_none() => {
+                let (_, to, entry, spans) = entries.next().expect("The entry exists");
+                for (span, kind) in spans {
+                    parser.record_span(kind, span);
+                }
+                body.push(entry);
+                parser.ptr = to;
+                last_blank_count = 0;
+            }
+            _ => {
+                parser.parse_entry(&mut body, &mut errors, &mut last_comment, last_blank_count);
+                last_blank_count = parser.skip_blank_block();
+            }
+        }
+    }
+
+    if let Some(last_comment) = last_comment.take() {
+        body.push(ast::Entry::Comment(last_comment));
+    }
+
+    let spans = parser.take_spans(&body);
+    let resource = ast::Resource { body };
+    if errors.is_empty() {
+        (Ok(resource), spans)
+    } else {
+        (Err((resource, errors)), spans)
+    }
+}
+
+fn is_comment<S>(entry: &ast::Entry<S>) -> bool {
+    matches!(
+        entry,
+        ast::Entry::Comment(_) | ast::Entry::GroupComment(_) | ast::Entry::ResourceComment(_)
+    )
+}
@@ -4,6 +4,8 @@
 //! * [`parse`] - parses an input into a complete Abstract Syntax Tree representation with all source information preserved.
 //! * [`parse_runtime`] - parses an input into a runtime optimized Abstract Syntax Tree
 //!   representation with comments stripped.
+//! * [`parse_with_spans`] - parses an input like [`parse`], additionally returning the
+//!   byte range of every node in [`Spans`].
 //!
 //! # Example
 //!
@@ -73,14 +75,18 @@ mod comment;
 mod core;
 mod expression;
 mod helper;
+mod incremental;
 mod pattern;
 mod runtime;
 mod slice;
+mod spans;
 
 use crate::ast;
-pub use errors::{ErrorKind, ParserError};
+pub use errors::{Diagnostic, ErrorKind, LineColumn, ParserError};
+pub use incremental::{reparse, TextEdit};
 pub(crate) use slice::matches_fluent_ws;
 pub use slice::Slice;
+pub use spans::{Spanned, Spans};
 
 /// Parser result always returns an AST representation of the input,
 /// and if parsing errors were encountered, a list of [`ParserError`] elements
@@ -229,6 +235,39 @@ where
     core::Parser::new(input).parse()
 }
 
+/// Parses an input into a complete Abstract Syntax Tree representation, like
+/// [`parse`], and records the byte range of every node in the source.
+///
+/// The AST is the same as the one returned by [`parse`], and the ranges are
+/// returned next to it in [`Spans`]. This mode is intended for tooling which needs
+/// to map nodes back to their location in the input.
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::parser;
+/// use fluent_syntax::ast;
+///
+/// let ftl = "hello = Hello, { $name }!\n";
+///
+/// let (resource, spans) = parser::parse_with_spans(ftl);
+/// let resource = resource.expect("Failed to parse an FTL resource.");
+/// assert_eq!(resource, parser::parse(ftl).unwrap());
+///
+/// let entry = &resource.body[0];
+/// let ast::Entry::Message(message) = entry else {
+///     panic!("Expected a message.");
+/// };
+/// assert_eq!(spans.get(0, entry, message), Some(0..25));
+/// assert_eq!(spans.get(0, entry, &message.id), Some(0..5));
+/// ```
+pub fn parse_with_spans<'s, S>(input: S) -> (Result<S>, Spans)
+where
+    S: Slice<'s>,
+{
+    core::Parser::new(input).parse_with_spans()
+}
+
 /// Parses an input into an Abstract Syntax Tree representation with comments stripped.
 ///
 /// This mode is intended for runtime use of Fluent. It currently strips all
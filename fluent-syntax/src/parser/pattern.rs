@@ -1,5 +1,5 @@
 use super::errors::{ErrorKind, ParserError};
-use super::{core::Parser, core::Result, slice::Slice};
+use super::{core::Parser, core::Result, slice::Slice, spans::Kind};
 use crate::ast;
 
 #[derive(Debug, PartialEq)]
@@ -24,7 +24,8 @@ enum TextElementPosition {
 // cheaper since they'll happen on the pointers, rather than extracted slices.
 #[derive(Debug)]
 enum PatternElementPlaceholders<S> {
-    Placeable(ast::Expression<S>),
+    // (expression, start, end)
+    Placeable(ast::Expression<S>, usize, usize),
     // (start, end, indent, position)
     TextElement(usize, usize, usize, TextElementPosition),
 }
@@ -57,13 +58,18 @@ where
         };
 
         while self.ptr < self.length {
+            let placeable_start = self.ptr;
             if self.take_byte_if(b'{') {
                 if text_element_role == TextElementPosition::LineStart {
                     common_indent = Some(0);
                 }
                 let exp = self.get_placeable()?;
                 last_non_blank = Some(elements.len());
-                elements.push(PatternElementPlaceholders::Placeable(exp));
+                elements.push(PatternElementPlaceholders::Placeable(
+                    exp,
+                    placeable_start,
+                    self.ptr,
+                ));
                 text_element_role = TextElementPosition::Continuation;
             } else {
                 let slice_start = self.ptr;
@@ -122,12 +128,18 @@ where
         }
 
         if let Some(last_non_blank) = last_non_blank {
+            // The spans of the elements, if they are recorded.
+            let recording = self.spans.is_some();
+            let mut spans = vec![];
             let elements = elements
                 .into_iter()
                 .take(last_non_blank + 1)
                 .enumerate()
                 .map(|(i, elem)| match elem {
-                    PatternElementPlaceholders::Placeable(expression) => {
+                    PatternElementPlaceholders::Placeable(expression, start, end) => {
+                        if recording {
+                            spans.push(start..end);
+                        }
                         ast::PatternElement::Placeable { expression }
                     }
                     PatternElementPlaceholders::TextElement(start, end, indent, role) => {
@@ -143,10 +155,20 @@ where
                         if last_non_blank == i {
                             value.trim();
                         }
+                        if recording {
+                            spans.push(start..start + value.as_ref().len());
+                        }
                         ast::PatternElement::TextElement { value }
                     }
                 })
                 .collect();
+            if let (Some(first), Some(last)) = (spans.first(), spans.last()) {
+                let span = first.start..last.end;
+                for span in spans {
+                    self.record_span(Kind::PatternElement, span);
+                }
+                self.record_span(Kind::Pattern, span);
+            }
             return Ok(Some(ast::Pattern { elements }));
         }
 
@@ -0,0 +1,407 @@
+use std::ops::Range;
+
+use super::{core::Parser, slice::Slice};
+use crate::ast;
+
+/// The byte ranges in the source of the nodes of a [`Resource`](ast::Resource),
+/// as recorded by [`parse_with_spans`](super::parse_with_spans).
+///
+/// The spans are kept apart from the AST, which is the same as the one returned by
+/// [`parse`](super::parse). They are stored by the index of the entry in the body
+/// of the resource and by the position of the node in the entry, so they apply to
+/// clones of the resource too. Once an entry is modified, or the entries are
+/// reordered, the lookups of its nodes return `None`.
+///
+/// The span of an entry covers the comment attached to it, and the span of an
+/// [`Expression::Inline`](ast::Expression::Inline) is the span of its inline expression.
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::ast;
+/// use fluent_syntax::parser;
+///
+/// let ftl = "hello = Hello, { $name }!\n";
+///
+/// let (resource, spans) = parser::parse_with_spans(ftl);
+/// let resource = resource.expect("Failed to parse an FTL resource.");
+///
+/// let entry = &resource.body[0];
+/// let ast::Entry::Message(message) = entry else {
+///     panic!("Expected a message.");
+/// };
+/// assert_eq!(spans.get(0, entry, entry), Some(0..25));
+/// assert_eq!(spans.get(0, entry, &message.id), Some(0..5));
+///
+/// let value = message.value.as_ref().expect("Message has a value.");
+/// assert_eq!(spans.get(0, entry, value), Some(8..25));
+/// assert_eq!(spans.get(0, entry, &value.elements[1]), Some(15..24));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Spans {
+    /// The spans of the nodes of every entry, in the order of `visit_entry`.
+    entries: Vec<Vec<(Range<usize>, Kind)>>,
+    len: usize,
+}
+
+impl Spans {
+    /// Returns the span of `node`, which is `entry` or one of its nodes, where
+    /// `entry` is the entry at `index` in the body of the resource.
+    ///
+    /// Returns `None` if `node` isn't part of `entry`, or if `entry` doesn't have
+    /// the nodes it was parsed with.
+    pub fn get<S, N: Spanned>(
+        &self,
+        index: usize,
+        entry: &ast::Entry<S>,
+        node: &N,
+    ) -> Option<Range<usize>> {
+        let spans = self.entries.get(index)?;
+        let target = node.key();
+        let mut position = 0;
+        let mut matches = true;
+        let mut found = None;
+        visit_entry(entry, &mut |key| {
+            match spans.get(position) {
+                Some((span, kind)) if *kind == key.1 => {
+                    if key == target && found.is_none() {
+                        found = Some(span.clone());
+                    }
+                }
+                _ => matches = false,
+            }
+            position += 1;
+        });
+        if matches && position == spans.len() {
+            found
+        } else {
+            None
+        }
+    }
+
+    /// Returns the span of the entry at `index`.
+    pub(crate) fn entry(&self, index: usize) -> Option<Range<usize>> {
+        let (span, _) = self.entries.get(index)?.first()?;
+        Some(span.clone())
+    }
+
+    /// Returns the length of the source the spans were recorded in.
+    pub(super) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the spans were recorded for the nodes of `body`.
+    pub(super) fn matches<S>(&self, body: &[ast::Entry<S>]) -> bool {
+        self.entries.len() == body.len()
+            && self.entries.iter().zip(body).all(|(spans, entry)| {
+                let mut kinds = spans.iter().map(|(_, kind)| *kind);
+                let mut matches = true;
+                visit_entry(entry, &mut |key| matches &= kinds.next() == Some(key.1));
+                matches && kinds.next().is_none()
+            })
+    }
+
+    /// Returns the spans of the entry at `index` and of its nodes, mapped by `f`.
+    pub(super) fn of_entry(
+        &self,
+        index: usize,
+        f: impl Fn(usize) -> usize,
+    ) -> Vec<(Range<usize>, Kind)> {
+        self.entries[index]
+            .iter()
+            .map(|(span, kind)| (f(span.start)..f(span.end), *kind))
+            .collect()
+    }
+
+    /// Assigns the spans recorded while parsing `body` to its nodes.
+    ///
+    /// The parser records the spans in no particular order. Sorting them by their
+    /// start, and the enclosing spans first, gives the order in which the nodes of
+    /// `body` are visited, because the span of every node lies within the span of
+    /// its parent, and follows the spans of its previous siblings.
+    pub(super) fn new<S>(
+        body: &[ast::Entry<S>],
+        mut recorded: Vec<(Range<usize>, Kind)>,
+        len: usize,
+    ) -> Self {
+        recorded.sort_by(|(a, a_kind), (b, b_kind)| {
+            a.start
+                .cmp(&b.start)
+                .then(b.end.cmp(&a.end))
+                .then(a_kind.cmp(b_kind))
+        });
+        let mut recorded = recorded.into_iter();
+        let entries = body
+            .iter()
+            .map(|entry| {
+                let mut spans = vec![];
+                visit_entry(entry, &mut |key| {
+                    let (span, kind) = recorded.next().expect("Every node has a span");
+                    assert_eq!(key.1, kind, "The node at {:?} has a span of its kind", span);
+                    spans.push((span, kind));
+                });
+                spans
+            })
+            .collect();
+        assert!(recorded.next().is_none(), "Every span has a node");
+        Self { entries, len }
+    }
+}
+
+/// A node of the AST which has a span in [`Spans`].
+pub trait Spanned: sealed::Sealed {}
+
+mod sealed {
+    /// Identifies a node of an entry by its address and its kind, since a node and
+    /// its first field may share an address.
+    #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+    pub struct Key(pub usize, pub Kind);
+
+    /// The kinds of nodes, in the order of the enclosing nodes first, for the nodes
+    /// which can have the same span as one of their children.
+    #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum Kind {
+        Junk,
+        Message,
+        Term,
+        Comment,
+        Attribute,
+        Pattern,
+        PatternElement,
+        Expression,
+        Variant,
+        VariantKey,
+        InlineExpression,
+        CallArguments,
+        NamedArgument,
+        Identifier,
+    }
+
+    pub trait Sealed {
+        fn key(&self) -> Key;
+    }
+}
+
+pub(super) use sealed::Kind;
+use sealed::{Key, Sealed};
+
+impl Key {
+    fn new<T>(node: &T, kind: Kind) -> Self {
+        Self(node as *const T as usize, kind)
+    }
+}
+
+macro_rules! spanned {
+    ($($node:ident),*) => {
+        $(
+            impl<S> Sealed for ast::$node<S> {
+                fn key(&self) -> Key {
+                    Key::new(self, Kind::$node)
+                }
+            }
+
+            impl<S> Spanned for ast::$node<S> {}
+        )*
+    };
+}
+
+spanned!(
+    Message,
+    Term,
+    Comment,
+    Attribute,
+    Pattern,
+    PatternElement,
+    Variant,
+    VariantKey,
+    InlineExpression,
+    CallArguments,
+    NamedArgument,
+    Identifier
+);
+
+impl<S> Sealed for ast::Entry<S> {
+    fn key(&self) -> Key {
+        match self {
+            Self::Message(message) => message.key(),
+            Self::Term(term) => term.key(),
+            Self::Comment(comment)
+            | Self::GroupComment(comment)
+            | Self::ResourceComment(comment) => comment.key(),
+            Self::Junk { .. } => Key::new(self, Kind::Junk),
+        }
+    }
+}
+
+impl<S> Spanned for ast::Entry<S> {}
+
+impl<S> Sealed for ast::Expression<S> {
+    fn key(&self) -> Key {
+        match self {
+            Self::Inline(expression) => expression.key(),
+            Self::Select { .. } => Key::new(self, Kind::Expression),
+        }
+    }
+}
+
+impl<S> Spanned for ast::Expression<S> {}
+
+/// Calls `f` with the keys of `entry` and of its nodes, in the order of the source.
+fn visit_entry<S>(entry: &ast::Entry<S>, f: &mut impl FnMut(Key)) {
+    f(entry.key());
+    match entry {
+        ast::Entry::Message(message) => {
+            if let Some(comment) = &message.comment {
+                f(comment.key());
+            }
+            f(message.id.key());
+            if let Some(value) = &message.value {
+                visit_pattern(value, f);
+            }
+            for attribute in &message.attributes {
+                visit_attribute(attribute, f);
+            }
+        }
+        ast::Entry::Term(term) => {
+            if let Some(comment) = &term.comment {
+                f(comment.key());
+            }
+            f(term.id.key());
+            visit_pattern(&term.value, f);
+            for attribute in &term.attributes {
+                visit_attribute(attribute, f);
+            }
+        }
+        ast::Entry::Comment(_)
+        | ast::Entry::GroupComment(_)
+        | ast::Entry::ResourceComment(_)
+        | ast::Entry::Junk { .. } => {}
+    }
+}
+
+fn visit_attribute<S>(attribute: &ast::Attribute<S>, f: &mut impl FnMut(Key)) {
+    f(attribute.key());
+    f(attribute.id.key());
+    visit_pattern(&attribute.value, f);
+}
+
+fn visit_pattern<S>(pattern: &ast::Pattern<S>, f: &mut impl FnMut(Key)) {
+    f(pattern.key());
+    for element in &pattern.elements {
+        f(element.key());
+        if let ast::PatternElement::Placeable { expression } = element {
+            visit_expression(expression, f);
+        }
+    }
+}
+
+fn visit_expression<S>(expression: &ast::Expression<S>, f: &mut impl FnMut(Key)) {
+    match expression {
+        ast::Expression::Inline(expression) => visit_inline_expression(expression, f),
+        ast::Expression::Select { selector, variants } => {
+            f(expression.key());
+            visit_inline_expression(selector, f);
+            for variant in variants {
+                f(variant.key());
+                f(variant.key.key());
+                visit_pattern(&variant.value, f);
+            }
+        }
+    }
+}
+
+fn visit_inline_expression<S>(expression: &ast::InlineExpression<S>, f: &mut impl FnMut(Key)) {
+    f(expression.key());
+    match expression {
+        ast::InlineExpression::StringLiteral { .. }
+        | ast::InlineExpression::NumberLiteral { .. } => {}
+        ast::InlineExpression::FunctionReference { id, arguments } => {
+            f(id.key());
+            visit_call_arguments(arguments, f);
+        }
+        ast::InlineExpression::MessageReference { id, attribute } => {
+            f(id.key());
+            if let Some(attribute) = attribute {
+                f(attribute.key());
+            }
+        }
+        ast::InlineExpression::TermReference {
+            id,
+            attribute,
+            arguments,
+        } => {
+            f(id.key());
+            if let Some(attribute) = attribute {
+                f(attribute.key());
+            }
+            if let Some(arguments) = arguments {
+                visit_call_arguments(arguments, f);
+            }
+        }
+        ast::InlineExpression::VariableReference { id } => f(id.key()),
+        ast::InlineExpression::Placeable { expression } => visit_expression(expression, f),
+    }
+}
+
+fn visit_call_arguments<S>(arguments: &ast::CallArguments<S>, f: &mut impl FnMut(Key)) {
+    f(arguments.key());
+    for argument in &arguments.positional {
+        visit_inline_expression(argument, f);
+    }
+    for argument in &arguments.named {
+        f(argument.key());
+        f(argument.name.key());
+        visit_inline_expression(&argument.value, f);
+    }
+}
+
+impl<'s, S> Parser<S>
+where
+    S: Slice<'s>,
+{
+    /// Records the span of a node of `kind` from `start` to the current position.
+    pub(super) fn record(&mut self, kind: Kind, start: usize) {
+        self.record_span(kind, start..self.ptr);
+    }
+
+    pub(super) fn record_span(&mut self, kind: Kind, span: Range<usize>) {
+        if let Some(spans) = &mut self.spans {
+            spans.push((span, kind));
+        }
+    }
+
+    /// Records the span of a node of `kind` from `start` to the end of the node
+    /// recorded last, which is its last child.
+    pub(super) fn record_to_last(&mut self, kind: Kind, start: usize) {
+        if let Some(spans) = &mut self.spans {
+            let end = spans.last().map_or(self.ptr, |(span, _)| span.end);
+            spans.push((start..end, kind));
+        }
+    }
+
+    /// Extends the span of the message or term recorded last to the start of the
+    /// comment attached to it.
+    pub(super) fn record_attached_comment(&mut self) {
+        if let Some(spans) = &mut self.spans {
+            let comment = spans
+                .iter()
+                .rev()
+                .find(|(_, kind)| *kind == Kind::Comment)
+                .map(|(span, _)| span.start);
+            if let (Some(start), Some((span, _))) = (comment, spans.last_mut()) {
+                span.start = start;
+            }
+        }
+    }
+
+    /// Returns a mark to drop the spans recorded after it with [`Self::rewind_spans`].
+    pub(super) fn spans_mark(&self) -> usize {
+        self.spans.as_ref().map_or(0, Vec::len)
+    }
+
+    pub(super) fn rewind_spans(&mut self, mark: usize) {
+        if let Some(spans) = &mut self.spans {
+            spans.truncate(mark);
+        }
+    }
+}
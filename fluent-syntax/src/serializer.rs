@@ -73,7 +73,7 @@ pub struct Serializer {
 impl Serializer {
     pub fn new(options: Options) -> Self {
         Serializer {
-            writer: TextWriter::default(),
+            writer: TextWriter::new(options.indent),
             options,
             state: State::default(),
         }
@@ -95,6 +95,10 @@ impl Serializer {
             };
 
             self.state.wrote_non_junk_entry = !matches!(entry, Entry::Junk { .. });
+            self.state.wrote_free_comment = matches!(
+                entry,
+                Entry::Comment(_) | Entry::GroupComment(_) | Entry::ResourceComment(_)
+            );
         }
     }
 
@@ -107,11 +111,23 @@ impl Serializer {
     }
 
     pub fn serialize_free_comment<'s, S: Slice<'s>>(&mut self, comment: &Comment<S>, prefix: &str) {
+        let is_group = prefix != "#";
         if self.state.wrote_non_junk_entry {
-            self.writer.newline();
+            let blank_lines = if is_group && !self.state.wrote_free_comment {
+                self.options.group_blank_lines
+            } else {
+                1
+            };
+            for _ in 0..blank_lines {
+                self.writer.newline();
+            }
         }
         self.serialize_comment(comment, prefix);
-        self.writer.newline();
+        // A regular comment directly above a message or a term would be
+        // attached to it.
+        if !is_group || self.options.comment_placement == CommentPlacement::Separated {
+            self.writer.newline();
+        }
     }
 
     fn serialize_comment<'s, S: Slice<'s>>(&mut self, comment: &Comment<S>, prefix: &str) {
@@ -160,24 +176,52 @@ impl Serializer {
     }
 
     fn serialize_pattern<'s, S: Slice<'s>>(&mut self, pattern: &Pattern<S>) {
-        let start_on_newline = pattern.starts_on_new_line();
+        let start_on_newline = self.starts_on_new_line(pattern);
 
         if start_on_newline {
             self.writer.newline();
-            self.writer.indent();
         } else {
             self.writer.write_literal(" ");
         }
+        // Inline patterns with a select expression are indented as well, so
+        // that its variants are indented relative to the closing brace.
+        let indent =
+            start_on_newline || (pattern.is_multiline() && !pattern.has_leading_text_dot());
+        if indent {
+            self.writer.indent();
+        }
 
         for element in &pattern.elements {
             self.serialize_element(element);
         }
 
-        if start_on_newline {
+        if indent {
             self.writer.dedent();
         }
     }
 
+    fn starts_on_new_line<'s, S: Slice<'s>>(&self, pattern: &Pattern<S>) -> bool {
+        if pattern.has_leading_text_dot() {
+            return false;
+        }
+        if pattern.has_multiline_text() {
+            return true;
+        }
+        if pattern.is_multiline() {
+            return self.options.select_layout == SelectLayout::Block;
+        }
+        match self.options.inline_width {
+            Some(width) if !pattern.has_leading_special_char() => {
+                let mut ser = Serializer::new(self.options);
+                for element in &pattern.elements {
+                    ser.serialize_element(element);
+                }
+                ser.into_serialized_text().chars().count() > width
+            }
+            _ => false,
+        }
+    }
+
     fn serialize_attributes<'s, S: Slice<'s>>(&mut self, attrs: &[Attribute<S>]) {
         if attrs.is_empty() {
             return;
@@ -355,14 +399,18 @@ impl Serializer {
 }
 
 impl<'s, S: Slice<'s>> Pattern<S> {
-    fn starts_on_new_line(&self) -> bool {
-        !self.has_leading_text_dot() && self.is_multiline()
+    fn is_multiline(&self) -> bool {
+        self.has_multiline_text()
+            || self.elements.iter().any(|elem| match elem {
+                PatternElement::TextElement { .. } => false,
+                PatternElement::Placeable { expression } => is_select_expr(expression),
+            })
     }
 
-    fn is_multiline(&self) -> bool {
+    fn has_multiline_text(&self) -> bool {
         self.elements.iter().any(|elem| match elem {
             PatternElement::TextElement { value } => value.as_ref().contains('\n'),
-            PatternElement::Placeable { expression } => is_select_expr(expression),
+            PatternElement::Placeable { .. } => false,
         })
     }
 
@@ -373,6 +421,16 @@ impl<'s, S: Slice<'s>> Pattern<S> {
             false
         }
     }
+
+    /// Whether the pattern starts with a character which can't start an
+    /// indented line of a pattern.
+    fn has_leading_special_char(&self) -> bool {
+        if let Some(PatternElement::TextElement { value }) = self.elements.first() {
+            value.as_ref().starts_with(['.', '[', '*', '}'])
+        } else {
+            false
+        }
+    }
 }
 
 fn is_select_expr<'s, S: Slice<'s>>(expr: &Expression<S>) -> bool {
@@ -386,24 +444,177 @@ fn is_select_expr<'s, S: Slice<'s>>(expr: &Expression<S>) -> bool {
 }
 
 /// Options for serializing an abstract syntax tree.
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+///
+/// The default options write the canonical formatting of Fluent.
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::parser;
+/// use fluent_syntax::serializer::{self, Options, SelectLayout};
+///
+/// let ftl = r#"emails = { $count ->
+///     [one] One email
+///    *[other] { $count } emails
+/// }
+/// "#;
+///
+/// let resource = parser::parse(ftl).expect("Failed to parse an FTL resource.");
+///
+/// let options = Options::default()
+///     .indent(2)
+///     .select_layout(SelectLayout::Inline);
+/// let serialized = serializer::serialize_with_options(&resource, options);
+///
+/// let expected = r#"emails = { $count ->
+///     [one] One email
+///    *[other] { $count } emails
+///   }
+/// "#;
+///
+/// assert_eq!(expected, serialized);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub struct Options {
     /// Whether invalid text fragments should be serialized, too.
     pub with_junk: bool,
+    /// The number of spaces in a level of indentation, at least 1. Defaults to 4.
+    pub indent: usize,
+    /// The maximum number of characters of a single-line pattern written on
+    /// the same line as its identifier. Longer patterns start on a new line.
+    /// Defaults to `None`, which only starts multiline patterns on a new line.
+    pub inline_width: Option<usize>,
+    /// The number of blank lines written before group and resource comments
+    /// following a message or a term. Defaults to 1.
+    pub group_blank_lines: usize,
+    /// The placement of group and resource comments relative to the entry
+    /// following them.
+    pub comment_placement: CommentPlacement,
+    /// The layout of patterns with a select expression.
+    pub select_layout: SelectLayout,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            with_junk: false,
+            indent: 4,
+            inline_width: None,
+            group_blank_lines: 1,
+            comment_placement: CommentPlacement::default(),
+            select_layout: SelectLayout::default(),
+        }
+    }
+}
+
+impl Options {
+    /// Sets whether invalid text fragments should be serialized, too.
+    pub fn with_junk(mut self, with_junk: bool) -> Self {
+        self.with_junk = with_junk;
+        self
+    }
+
+    /// Sets the number of spaces in a level of indentation.
+    pub fn indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Sets the maximum number of characters of a single-line pattern written on the
+    /// same line as its identifier.
+    pub fn inline_width(mut self, inline_width: Option<usize>) -> Self {
+        self.inline_width = inline_width;
+        self
+    }
+
+    /// Sets the number of blank lines written before group and resource comments
+    /// following a message or a term.
+    pub fn group_blank_lines(mut self, group_blank_lines: usize) -> Self {
+        self.group_blank_lines = group_blank_lines;
+        self
+    }
+
+    /// Sets the placement of group and resource comments.
+    pub fn comment_placement(mut self, comment_placement: CommentPlacement) -> Self {
+        self.comment_placement = comment_placement;
+        self
+    }
+
+    /// Sets the layout of patterns with a select expression.
+    pub fn select_layout(mut self, select_layout: SelectLayout) -> Self {
+        self.select_layout = select_layout;
+        self
+    }
+}
+
+/// The placement of group and resource comments.
+///
+/// Regular comments which are not attached to a message or a term are always
+/// followed by a blank line.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CommentPlacement {
+    /// Followed by a blank line.
+    #[default]
+    Separated,
+    /// Directly above the entry following them.
+    Attached,
+}
+
+/// The layout of patterns with a select expression.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SelectLayout {
+    /// Start the pattern on a new line:
+    ///
+    /// ```ftl
+    /// emails =
+    ///     { $count ->
+    ///         [one] One email
+    ///        *[other] { $count } emails
+    ///     }
+    /// ```
+    #[default]
+    Block,
+    /// Start the select expression on the line of the identifier, unless
+    /// the pattern has multiline text:
+    ///
+    /// ```ftl
+    /// emails = { $count ->
+    ///         [one] One email
+    ///        *[other] { $count } emails
+    ///     }
+    /// ```
+    Inline,
 }
 
 #[derive(Debug, Default, PartialEq)]
 struct State {
     wrote_non_junk_entry: bool,
+    wrote_free_comment: bool,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 struct TextWriter {
     buffer: String,
     indent_level: usize,
+    indent_width: usize,
+}
+
+impl Default for TextWriter {
+    fn default() -> Self {
+        Self::new(4)
+    }
 }
 
 impl TextWriter {
+    fn new(indent_width: usize) -> Self {
+        Self {
+            buffer: String::new(),
+            indent_level: 0,
+            indent_width: indent_width.max(1),
+        }
+    }
+
     fn indent(&mut self) {
         self.indent_level += 1;
     }
@@ -416,8 +627,8 @@ impl TextWriter {
     }
 
     fn write_indent(&mut self) {
-        for _ in 0..self.indent_level {
-            self.buffer.push_str("    ");
+        for _ in 0..self.indent_level * self.indent_width {
+            self.buffer.push(' ');
         }
     }
 
@@ -652,4 +863,85 @@ mod test {
             .content[0] = "very original";
         assert_eq!("# very original\nfoo = bar\n", serialize(&ast));
     }
+
+    fn serialize_with(ftl: &str, options: Options) -> String {
+        let ast = parse(ftl).expect("failed to parse ftl resource");
+        let serialized = serialize_with_options(&ast, options);
+        let reparsed = parse(serialized.as_str()).expect("failed to parse serialized ftl");
+        assert_eq!(ast, reparsed);
+        serialized
+    }
+
+    #[test]
+    fn indent_option() {
+        let options = Options {
+            indent: 2,
+            ..Options::default()
+        };
+        let ftl = "foo =\n    { $num ->\n        [one] One\n       *[other] Other\n    }\n    .attr = Attr\n";
+        let expected =
+            "foo =\n  { $num ->\n    [one] One\n   *[other] Other\n  }\n  .attr = Attr\n";
+        assert_eq!(serialize_with(ftl, options), expected);
+    }
+
+    #[test]
+    fn inline_width_option() {
+        let options = Options {
+            inline_width: Some(10),
+            ..Options::default()
+        };
+        let ftl = "foo = Short\nbar = A { $var } longer value\nbaz = .Dotted longer value\n";
+        let expected =
+            "foo = Short\nbar =\n    A { $var } longer value\nbaz = .Dotted longer value\n";
+        assert_eq!(serialize_with(ftl, options), expected);
+    }
+
+    #[test]
+    fn group_comment_options() {
+        let ftl = "foo = bar\n## Group\nbaz = qux\n# Comment\n\n## Group 2\nqux = quux\n";
+        let expected =
+            "foo = bar\n\n## Group\n\nbaz = qux\n\n# Comment\n\n\n## Group 2\n\nqux = quux\n";
+        assert_eq!(serialize_with(ftl, Options::default()), expected);
+
+        let options = Options {
+            group_blank_lines: 2,
+            comment_placement: CommentPlacement::Attached,
+            ..Options::default()
+        };
+        let expected =
+            "foo = bar\n\n\n## Group\nbaz = qux\n\n# Comment\n\n\n## Group 2\nqux = quux\n";
+        assert_eq!(serialize_with(ftl, options), expected);
+    }
+
+    #[test]
+    fn select_layout_option() {
+        let options = Options {
+            select_layout: SelectLayout::Inline,
+            ..Options::default()
+        };
+        let ftl = concat!(
+            "foo =\n",
+            "    { $num ->\n",
+            "        [one] One\n",
+            "       *[other] Other\n",
+            "    } items\n",
+            "bar =\n",
+            "    Multiline\n",
+            "    { $num ->\n",
+            "       *[other] Other\n",
+            "    }\n",
+        );
+        let expected = concat!(
+            "foo = { $num ->\n",
+            "        [one] One\n",
+            "       *[other] Other\n",
+            "    } items\n",
+            "bar =\n",
+            "    Multiline\n",
+            "    { $num ->\n",
+            "       *[other] Other\n",
+            "    }\n",
+        );
+        assert_eq!(serialize_with(ftl, options), expected);
+    }
 }
@@ -0,0 +1,120 @@
+//! Walking the expressions of a pattern.
+//!
+//! [`walk_pattern`] calls a function with every expression of a [`Pattern`], including
+//! the ones nested in placeables, select expressions and call arguments, which is how
+//! analyses of the references and variables of messages are built.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_syntax::ast;
+//! use fluent_syntax::parser;
+//! use fluent_syntax::walk::{walk_pattern, Node};
+//!
+//! let ftl = r#"
+//! emails = { $name } has { NUMBER($count) ->
+//!    *[other] { $count } emails
+//! }
+//! "#;
+//! let resource = parser::parse(ftl).expect("Failed to parse an FTL resource.");
+//! let ast::Entry::Message(message) = &resource.body[0] else {
+//!     panic!("Expected a message.");
+//! };
+//!
+//! let mut variables = vec![];
+//! walk_pattern(message.value.as_ref().unwrap(), &mut |node| {
+//!     if let Node::Inline(ast::InlineExpression::VariableReference { id }, _) = node {
+//!         variables.push(id.name);
+//!     }
+//! });
+//! assert_eq!(variables, vec!["name", "count", "count"]);
+//! ```
+use crate::ast::{CallArguments, Expression, Identifier, InlineExpression, Pattern, Variant};
+
+/// An expression visited by [`walk_pattern`].
+#[derive(Debug)]
+pub enum Node<'a, S> {
+    /// An inline expression, and where it's used.
+    Inline(&'a InlineExpression<S>, Position<'a, S>),
+    /// A select expression, visited before its selector and variants.
+    Select(&'a Expression<S>),
+}
+
+/// Where an inline expression is used.
+#[derive(Debug)]
+pub enum Position<'a, S> {
+    /// In a placeable.
+    Placeable,
+    /// As the selector of the variants.
+    Selector(&'a [Variant<S>]),
+    /// As an argument of a call to the function `id`.
+    FunctionArgument(&'a Identifier<S>),
+    /// As an argument of a reference to the term `id`.
+    TermArgument(&'a Identifier<S>),
+}
+
+impl<S> Clone for Position<'_, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for Position<'_, S> {}
+
+/// Calls `f` with every expression of `pattern`, including the nested ones, in the
+/// order of the source.
+pub fn walk_pattern<'a, S>(pattern: &'a Pattern<S>, f: &mut impl FnMut(Node<'a, S>)) {
+    for element in &pattern.elements {
+        if let crate::ast::PatternElement::Placeable { expression } = element {
+            walk_expression(expression, f);
+        }
+    }
+}
+
+fn walk_expression<'a, S>(expression: &'a Expression<S>, f: &mut impl FnMut(Node<'a, S>)) {
+    match expression {
+        Expression::Inline(expression) => {
+            walk_inline_expression(expression, Position::Placeable, f)
+        }
+        Expression::Select { selector, variants } => {
+            f(Node::Select(expression));
+            walk_inline_expression(selector, Position::Selector(variants), f);
+            for variant in variants {
+                walk_pattern(&variant.value, f);
+            }
+        }
+    }
+}
+
+fn walk_inline_expression<'a, S>(
+    expression: &'a InlineExpression<S>,
+    position: Position<'a, S>,
+    f: &mut impl FnMut(Node<'a, S>),
+) {
+    f(Node::Inline(expression, position));
+    match expression {
+        InlineExpression::FunctionReference { id, arguments } => {
+            walk_call_arguments(arguments, Position::FunctionArgument(id), f);
+        }
+        InlineExpression::TermReference {
+            id,
+            arguments: Some(arguments),
+            ..
+        } => walk_call_arguments(arguments, Position::TermArgument(id), f),
+        InlineExpression::Placeable { expression } => walk_expression(expression, f),
+        _ => {}
+    }
+}
+
+fn walk_call_arguments<'a, S>(
+    arguments: &'a CallArguments<S>,
+    position: Position<'a, S>,
+    f: &mut impl FnMut(Node<'a, S>),
+) {
+    for argument in &arguments.positional {
+        walk_inline_expression(argument, position, f);
+    }
+    for argument in &arguments.named {
+        walk_inline_expression(&argument.value, position, f);
+    }
+}
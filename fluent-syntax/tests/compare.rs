@@ -0,0 +1,90 @@
+use fluent_syntax::compare::{compare, Difference};
+use fluent_syntax::parser::parse;
+
+#[test]
+fn compare_resources() {
+    let reference = parse(
+        "\
+-brand = Firefox
+    .gender = masculine
+-other = Other
+about = About { -brand }
+emails = { $count ->
+    [one] One email for { $user }
+   *[other] { $count } emails for { $user }
+}
+    .title = { $count } emails
+    .label = Emails
+value = Value
+attrs =
+    .title = Title
+missing = Missing
+",
+    )
+    .expect("Failed to parse");
+    let translation = parse(
+        "\
+-brand = Firefox
+    .case = nominative
+about = À propos
+emails = { $count ->
+    [one] Un courriel
+   *[other] { $count } courriels
+}
+    .title = { $num } courriels
+    .tooltip = Courriels
+value =
+    .title = Titre
+attrs = Valeur
+    .title = Titre
+obsolete = Obsolète
+",
+    )
+    .expect("Failed to parse");
+
+    let differences: Vec<_> = compare(&reference, &translation)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        differences,
+        [
+            "Missing `-other`",
+            "Missing reference to `-brand` in `about`",
+            "Missing variable `$user` in `emails`",
+            "Missing variable `$count` in `emails.title`",
+            "Extra variable `$num` in `emails.title`",
+            "Missing attribute `emails.label`",
+            "Extra attribute `emails.tooltip`",
+            "Missing value of `value`",
+            "Extra attribute `value.title`",
+            "Extra value of `attrs`",
+            "Missing `missing`",
+            "Obsolete `obsolete`",
+        ]
+    );
+}
+
+#[test]
+fn compare_identical() {
+    let source = "key = { $var } { -term }\n    .attr = { FUNC($other) }\n-term = Term\n";
+    let resource = parse(source).expect("Failed to parse");
+    assert_eq!(compare(&resource, &resource), vec![]);
+
+    let translation =
+        parse("key = { -term } { $var }\n    .attr = { FUNC($other, x: 1) }\n-term = Terme\n")
+            .expect("Failed to parse");
+    assert_eq!(compare(&resource, &translation), vec![]);
+
+    assert_eq!(
+        compare(&resource, &parse("").unwrap()),
+        vec![
+            Difference::MissingEntry {
+                id: "key".to_string()
+            },
+            Difference::MissingEntry {
+                id: "-term".to_string()
+            },
+        ]
+    );
+}
@@ -0,0 +1,119 @@
+use std::fs;
+
+use glob::glob;
+
+use fluent_syntax::ast;
+use fluent_syntax::cst;
+use fluent_syntax::parser;
+
+fn parse(source: &str) -> cst::Document<&str> {
+    match cst::parse(source) {
+        Ok(document) => document,
+        Err((document, _)) => document,
+    }
+}
+
+#[test]
+fn cst_round_trip_fixtures() {
+    for entry in glob("./tests/fixtures/*.ftl").expect("Failed to read glob pattern") {
+        let path = entry.expect("Error while getting an entry");
+        let source = fs::read_to_string(&path).expect("Failed to read file");
+
+        let document = parse(&source);
+        assert_eq!(document.serialize(), source, "{:?}", path);
+
+        let resource = match parser::parse(source.as_str()) {
+            Ok(resource) => resource,
+            Err((resource, _)) => resource,
+        };
+        assert_eq!(document.len(), resource.body.len());
+        assert!(document.entries().eq(resource.body.iter()));
+    }
+}
+
+#[test]
+fn cst_modify_entry() {
+    let source = "\
+# Comment
+key1=Value 1
+key2 =   { $num ->
+  [one] One
+  *[other] Other
+}
+
+
+key3 = Value 3  \n";
+    let mut document = parse(source);
+
+    let Some(ast::Entry::Message(message)) = document.get_mut(1) else {
+        panic!("Expected a message.");
+    };
+    message.attributes.push(ast::Attribute {
+        id: ast::Identifier { name: "attr" },
+        value: ast::Pattern {
+            elements: vec![ast::PatternElement::TextElement { value: "Attribute" }],
+        },
+    });
+
+    assert_eq!(
+        document.serialize(),
+        "\
+# Comment
+key1=Value 1
+key2 =
+    { $num ->
+        [one] One
+       *[other] Other
+    }
+    .attr = Attribute
+
+
+key3 = Value 3  \n"
+    );
+}
+
+#[test]
+fn cst_insert_and_remove() {
+    let source = "key1=Value 1\n\n\nkey2  =  Value 2";
+    let mut document = parse(source);
+
+    let message = |name| {
+        ast::Entry::Message(ast::Message {
+            id: ast::Identifier { name },
+            value: Some(ast::Pattern {
+                elements: vec![ast::PatternElement::TextElement { value: "New" }],
+            }),
+            attributes: vec![],
+            comment: None,
+        })
+    };
+
+    document.insert(1, message("new1"));
+    document.push(message("new2"));
+    assert_eq!(
+        document.serialize(),
+        "key1=Value 1\n\n\nnew1 = New\nkey2  =  Value 2\nnew2 = New\n"
+    );
+
+    let ast::Entry::Message(removed) = document.remove(0) else {
+        panic!("Expected a message.");
+    };
+    assert_eq!(removed.id.name, "key1");
+    assert_eq!(
+        document.serialize(),
+        "\n\nnew1 = New\nkey2  =  Value 2\nnew2 = New\n"
+    );
+
+    document.remove(1);
+    assert_eq!(document.serialize(), "\n\nnew1 = New\nnew2 = New\n");
+    assert_eq!(document.len(), 2);
+}
+
+#[test]
+fn cst_errors() {
+    let source = "key = { Value\nkey2 = Value 2\n";
+    let (document, errors) = cst::parse(source).expect_err("Expected an error");
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(document.get(0), Some(ast::Entry::Junk { .. })));
+    assert_eq!(document.serialize(), source);
+}
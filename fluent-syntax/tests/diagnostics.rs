@@ -0,0 +1,130 @@
+use fluent_syntax::parser::{parse, ErrorKind, LineColumn, ParserError};
+
+fn parse_errors(source: &str) -> Vec<ParserError> {
+    match parse(source) {
+        Ok(_) => vec![],
+        Err((_, errors)) => errors,
+    }
+}
+
+#[test]
+fn line_column() {
+    let source = "key1 = Value\r\n\r\nkey2 = { \"Välue\" }\nkey3 Value\n";
+    let errors = parse_errors(source);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].line_column(source),
+        LineColumn { line: 4, column: 6 }
+    );
+
+    assert_eq!(LineColumn::from_offset(source, 0).to_string(), "1:1");
+    assert_eq!(
+        LineColumn::from_offset(source, source.len() + 10),
+        LineColumn { line: 5, column: 1 }
+    );
+    // "ä" is two bytes long, but a single column.
+    let offset = source.find("lue\"").unwrap();
+    assert_eq!(
+        LineColumn::from_offset(source, offset),
+        LineColumn {
+            line: 3,
+            column: 13
+        }
+    );
+}
+
+#[test]
+fn diagnostic_snippet() {
+    let source = "key1 = Value\n\n-term = { $sel ->\n    [one] One\n}\n";
+    let errors = parse_errors(source);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ErrorKind::MissingDefaultVariant);
+
+    let diagnostic = errors[0].diagnostic(source);
+    assert_eq!(diagnostic.start(), LineColumn { line: 5, column: 1 });
+    assert_eq!(
+        diagnostic.to_string(),
+        concat!(
+            "error[E0010]: The select expression must have a default variant\n",
+            " --> 5:1\n",
+            "  |\n",
+            "5 | }\n",
+            "  | ^\n",
+            "  |\n",
+            "  = help: Mark one of the variants as the default with `*`, like `*[other]`.\n",
+        )
+    );
+}
+
+#[test]
+fn diagnostic_multiline() {
+    let source = "\tkey =\n  .attr\n";
+    let errors = parse_errors(source);
+    assert_eq!(errors.len(), 1);
+
+    assert_eq!(
+        errors[0].diagnostic(source).with_origin("a.ftl").to_string(),
+        concat!(
+            "error[E0004]: Expected one of \"a-zA-Z\"\n",
+            " --> a.ftl:1:1\n",
+            "  |\n",
+            "1 | \tkey =\n",
+            "  | ^\n",
+            "  |\n",
+            "  = help: Identifiers must start with a letter, and may only contain letters, digits, `-` and `_`.\n",
+        )
+    );
+
+    let source = "key =\n    .attr\n\nkey2 = Value\n";
+    let errors = parse_errors(source);
+    let diagnostic = errors[0].diagnostic(source).with_origin("b.ftl");
+    assert_eq!(diagnostic.end(), LineColumn { line: 2, column: 1 });
+    assert_eq!(
+        diagnostic.to_string(),
+        concat!(
+            "error[E0005]: Expected a message field for \"key\"\n",
+            " --> b.ftl:1:1\n",
+            "  |\n",
+            "1 | key =\n",
+            "  | ^^^^^\n",
+            "  |\n",
+            "  = help: Give \"key\" a value or at least one attribute.\n",
+        )
+    );
+}
+
+#[test]
+fn diagnostic_at_end_of_input() {
+    let source = "key = { $var";
+    let errors = parse_errors(source);
+    assert_eq!(
+        errors[0].diagnostic(source).to_string(),
+        concat!(
+            "error[E0003]: Expected a token starting with \"}\"\n",
+            " --> 1:13\n",
+            "  |\n",
+            "1 | key = { $var\n",
+            "  |             ^\n",
+            "  |\n",
+            "  = help: Insert \"}\" here.\n",
+        )
+    );
+}
+
+#[test]
+fn diagnostic_help() {
+    let help = |source: &str| parse_errors(source)[0].kind.help();
+
+    assert_eq!(
+        help("key = { $sel -> *[other] Other }\n"),
+        "The variants of a select expression start on a new line after `->`."
+    );
+    assert_eq!(
+        help("key = { 1. }\n"),
+        "Number literals need digits before and after the `.`, like `-1.5`."
+    );
+    assert_eq!(
+        help("key = { -. }\n"),
+        "Number literals need digits before and after the `.`, like `-1.5`."
+    );
+}
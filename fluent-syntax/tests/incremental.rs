@@ -0,0 +1,291 @@
+use std::fs;
+use std::ops::Range;
+
+use glob::glob;
+
+use fluent_syntax::ast;
+use fluent_syntax::parser::{parse_with_spans, reparse, Result, Spanned, Spans, TextEdit};
+
+/// Collects the spans of the nodes of an entry, in the order of the source.
+struct Collector<'a> {
+    spans: &'a Spans,
+    index: usize,
+    entry: &'a ast::Entry<&'a str>,
+    collected: Vec<Option<Range<usize>>>,
+}
+
+impl Collector<'_> {
+    fn get<N: Spanned>(&self, node: &N) -> Option<Range<usize>> {
+        self.spans.get(self.index, self.entry, node)
+    }
+
+    fn entry(&mut self, entry: &ast::Entry<&str>) {
+        self.collected.push(self.get(entry));
+        match entry {
+            ast::Entry::Message(message) => {
+                if let Some(comment) = &message.comment {
+                    self.collected.push(self.get(comment));
+                }
+                self.collected.push(self.get(&message.id));
+                if let Some(value) = &message.value {
+                    self.pattern(value);
+                }
+                for attribute in &message.attributes {
+                    self.attribute(attribute);
+                }
+            }
+            ast::Entry::Term(term) => {
+                if let Some(comment) = &term.comment {
+                    self.collected.push(self.get(comment));
+                }
+                self.collected.push(self.get(&term.id));
+                self.pattern(&term.value);
+                for attribute in &term.attributes {
+                    self.attribute(attribute);
+                }
+            }
+            ast::Entry::Comment(_)
+            | ast::Entry::GroupComment(_)
+            | ast::Entry::ResourceComment(_)
+            | ast::Entry::Junk { .. } => {}
+        }
+    }
+
+    fn attribute(&mut self, attribute: &ast::Attribute<&str>) {
+        self.collected.push(self.get(attribute));
+        self.collected.push(self.get(&attribute.id));
+        self.pattern(&attribute.value);
+    }
+
+    fn pattern(&mut self, pattern: &ast::Pattern<&str>) {
+        self.collected.push(self.get(pattern));
+        for element in &pattern.elements {
+            self.collected.push(self.get(element));
+            if let ast::PatternElement::Placeable { expression } = element {
+                self.expression(expression);
+            }
+        }
+    }
+
+    fn expression(&mut self, expression: &ast::Expression<&str>) {
+        self.collected.push(self.get(expression));
+        match expression {
+            ast::Expression::Inline(expression) => self.inline_expression(expression),
+            ast::Expression::Select { selector, variants } => {
+                self.inline_expression(selector);
+                for variant in variants {
+                    self.collected.push(self.get(variant));
+                    self.collected.push(self.get(&variant.key));
+                    self.pattern(&variant.value);
+                }
+            }
+        }
+    }
+
+    fn inline_expression(&mut self, expression: &ast::InlineExpression<&str>) {
+        self.collected.push(self.get(expression));
+        match expression {
+            ast::InlineExpression::StringLiteral { .. }
+            | ast::InlineExpression::NumberLiteral { .. } => {}
+            ast::InlineExpression::FunctionReference { id, arguments } => {
+                self.collected.push(self.get(id));
+                self.call_arguments(arguments);
+            }
+            ast::InlineExpression::MessageReference { id, attribute } => {
+                self.collected.push(self.get(id));
+                if let Some(attribute) = attribute {
+                    self.collected.push(self.get(attribute));
+                }
+            }
+            ast::InlineExpression::TermReference {
+                id,
+                attribute,
+                arguments,
+            } => {
+                self.collected.push(self.get(id));
+                if let Some(attribute) = attribute {
+                    self.collected.push(self.get(attribute));
+                }
+                if let Some(arguments) = arguments {
+                    self.call_arguments(arguments);
+                }
+            }
+            ast::InlineExpression::VariableReference { id } => {
+                self.collected.push(self.get(id));
+            }
+            ast::InlineExpression::Placeable { expression } => self.expression(expression),
+        }
+    }
+
+    fn call_arguments(&mut self, arguments: &ast::CallArguments<&str>) {
+        self.collected.push(self.get(arguments));
+        for argument in &arguments.positional {
+            self.inline_expression(argument);
+        }
+        for argument in &arguments.named {
+            self.collected.push(self.get(argument));
+            self.collected.push(self.get(&argument.name));
+            self.inline_expression(&argument.value);
+        }
+    }
+}
+
+fn resource<'s>(result: &'s Result<&str>) -> &'s ast::Resource<&'s str> {
+    match result {
+        Ok(resource) => resource,
+        Err((resource, _)) => resource,
+    }
+}
+
+fn collect_spans(result: &Result<&str>, spans: &Spans) -> Vec<Option<Range<usize>>> {
+    let mut collected = vec![];
+    for (index, entry) in resource(result).body.iter().enumerate() {
+        let mut collector = Collector {
+            spans,
+            index,
+            entry,
+            collected,
+        };
+        collector.entry(entry);
+        collected = collector.collected;
+    }
+    collected
+}
+
+/// Applies `edit` to `source` and asserts that re-parsing the previous AST
+/// gives the same result and spans as parsing the new source from scratch.
+fn check_edit(source: &str, range: Range<usize>, text: &str) {
+    let new_source = format!("{}{}{}", &source[..range.start], text, &source[range.end..]);
+    let (result, spans) = parse_with_spans(source);
+    let resource = match result {
+        Ok(resource) => resource,
+        Err((resource, _)) => resource,
+    };
+    let edit = TextEdit::new(range.clone(), text);
+    let (result, spans) = reparse(resource, &spans, new_source.as_str(), &edit);
+    let (expected, expected_spans) = parse_with_spans(new_source.as_str());
+    assert_eq!(
+        (&result, collect_spans(&result, &spans)),
+        (&expected, collect_spans(&expected, &expected_spans)),
+        "Replacing {:?} with {:?} in:\n{}",
+        range,
+        text,
+        source
+    );
+}
+
+fn boundaries(source: &str) -> Vec<usize> {
+    source
+        .char_indices()
+        .map(|(idx, _)| idx)
+        .chain(Some(source.len()))
+        .collect()
+}
+
+#[test]
+fn reparse_fixtures() {
+    for entry in glob("./tests/fixtures/*.ftl").expect("Failed to read glob pattern") {
+        let path = entry.expect("Error while getting an entry");
+        let source = fs::read_to_string(&path).expect("Failed to read file");
+        let boundaries = boundaries(&source);
+
+        for (idx, &pos) in boundaries.iter().enumerate().step_by(11) {
+            for text in ["x", " ", "\n", "#", "-", "{", "}", "\n\n"] {
+                check_edit(&source, pos..pos, text);
+            }
+            if let Some(&next) = boundaries.get(idx + 1) {
+                check_edit(&source, pos..next, "");
+            }
+            if let Some(&next) = boundaries.get(idx + 12) {
+                check_edit(&source, pos..next, "");
+                check_edit(&source, pos..next, "key = Value\n");
+            }
+        }
+    }
+}
+
+#[test]
+fn reparse_every_position() {
+    let source = "\
+### Resource comment
+
+# Comment
+key1 = Value 1
+    .attr = { -term(arg: 1) }
+
+## Group
+
+-term = { $num ->
+    [one] One
+   *[other] Other
+}
+# Attached comment
+key2 =
+    Multiline
+    value
+key3 = { key1.attr }
+";
+    let boundaries = boundaries(source);
+    for (idx, &pos) in boundaries.iter().enumerate() {
+        for text in [
+            "x", " ", "    ", "\n", "\n\n", "# ", "-", "=", "{", "}", "*[", ".a = B\n",
+        ] {
+            check_edit(source, pos..pos, text);
+        }
+        for &next in &boundaries[idx + 1..] {
+            check_edit(source, pos..next, "");
+        }
+    }
+}
+
+#[test]
+fn reparse_keeps_entries() {
+    let source = "key1 = Value 1\nkey2 = { $var }\n";
+    let (result, spans) = parse_with_spans(source);
+    let resource = result.expect("Failed to parse");
+
+    let new_source = "key1 = Value 1\n\nkey0 = Value 0\nkey2 = { $var }\n";
+    let edit = TextEdit::new(15..15, "\nkey0 = Value 0\n");
+    let (result, spans) = reparse(resource, &spans, new_source, &edit);
+    let resource = result.expect("Failed to parse");
+    assert_eq!(resource, parse_with_spans(new_source).0.unwrap());
+    assert_eq!(resource.body.len(), 3);
+    assert_eq!(
+        spans.get(2, &resource.body[2], &resource.body[2]),
+        Some(31..46)
+    );
+}
+
+#[test]
+fn reparse_errors() {
+    let source = "key1 = Value 1\nkey2 = Value 2\n";
+    let (result, spans) = parse_with_spans(source);
+    let resource = result.expect("Failed to parse");
+
+    let new_source = "key1 = Value 1\nkey2 = { Value 2\n";
+    let edit = TextEdit::new(22..22, "{ ");
+    let (result, spans) = reparse(resource, &spans, new_source, &edit);
+    assert_eq!(result, parse_with_spans(new_source).0);
+    let (resource, errors) = result.expect_err("Expected an error");
+    assert_eq!(errors.len(), 1);
+
+    // Fixing the error drops the junk entry and its error.
+    let edit = TextEdit::new(22..24, "");
+    let (fixed, _) = reparse(resource, &spans, source, &edit);
+    assert_eq!(fixed, parse_with_spans(source).0);
+}
+
+#[test]
+fn reparse_without_spans() {
+    let resource = fluent_syntax::parser::parse("key = Value\n").expect("Failed to parse");
+
+    let new_source = "key = New value\n";
+    let edit = TextEdit::new(6..6, "New ");
+    let (result, spans) = reparse(resource, &Spans::default(), new_source, &edit);
+    let (expected, expected_spans) = parse_with_spans(new_source);
+    assert_eq!(result, expected);
+    assert_eq!(
+        collect_spans(&result, &spans),
+        collect_spans(&expected, &expected_spans)
+    );
+}
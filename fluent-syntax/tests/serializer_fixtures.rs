@@ -58,9 +58,9 @@ fn roundtrip_unnormalized_fixtures() {
         let content = fs::read_to_string(&path).expect("Failed to read file");
         let parsed = parse(content.as_str()).unwrap_or_else(|(res, _)| res);
         let parsed_without_junk = clone_without_junk(&parsed);
-        let reserialized = serialize_with_options(&parsed, Options { with_junk: true });
+        let reserialized = serialize_with_options(&parsed, Options::default().with_junk(true));
         let reserialized_without_junk =
-            serialize_with_options(&parsed, Options { with_junk: false });
+            serialize_with_options(&parsed, Options::default().with_junk(false));
         let reparsed = parse(reserialized.as_str()).unwrap_or_else(|(res, _)| res);
         let reparsed_without_junk =
             parse(reserialized_without_junk.as_str()).unwrap_or_else(|(res, _)| res);
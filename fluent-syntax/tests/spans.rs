@@ -0,0 +1,361 @@
+use std::fs;
+use std::ops::Range;
+
+use glob::glob;
+
+use fluent_syntax::ast;
+use fluent_syntax::parser::{parse, parse_with_spans, Spanned, Spans};
+
+/// The spans of the nodes of one entry.
+struct EntrySpans<'a> {
+    spans: &'a Spans,
+    index: usize,
+    entry: &'a ast::Entry<&'a str>,
+}
+
+impl EntrySpans<'_> {
+    fn get<N: Spanned>(&self, node: &N) -> Option<Range<usize>> {
+        self.spans.get(self.index, self.entry, node)
+    }
+}
+
+/// Asserts that the span of `node` is recorded and lies within `parent`,
+/// returning it.
+fn check_span<N: Spanned>(spans: &EntrySpans, node: &N, parent: &Range<usize>) -> Range<usize> {
+    let span = spans.get(node).expect("Every node has a span.");
+    assert!(
+        parent.start <= span.start && span.end <= parent.end,
+        "{:?} is not within {:?}",
+        span,
+        parent
+    );
+    span
+}
+
+fn check_identifier(
+    source: &str,
+    spans: &EntrySpans,
+    id: &ast::Identifier<&str>,
+    parent: &Range<usize>,
+) {
+    let span = check_span(spans, id, parent);
+    assert_eq!(&source[span], id.name);
+}
+
+fn check_pattern(
+    source: &str,
+    spans: &EntrySpans,
+    pattern: &ast::Pattern<&str>,
+    parent: &Range<usize>,
+) {
+    let span = check_span(spans, pattern, parent);
+    for element in &pattern.elements {
+        let elem = check_span(spans, element, &span);
+        match element {
+            ast::PatternElement::TextElement { value } => {
+                assert_eq!(source[elem].len(), value.len());
+            }
+            ast::PatternElement::Placeable { expression } => {
+                assert!(source[elem.clone()].starts_with('{'));
+                assert!(source[elem.clone()].ends_with('}'));
+                check_expression(source, spans, expression, &elem);
+            }
+        }
+    }
+}
+
+fn check_expression(
+    source: &str,
+    spans: &EntrySpans,
+    expression: &ast::Expression<&str>,
+    parent: &Range<usize>,
+) {
+    match expression {
+        ast::Expression::Inline(inline) => {
+            check_inline_expression(source, spans, inline, parent);
+            assert_eq!(spans.get(expression), spans.get(inline));
+        }
+        ast::Expression::Select { selector, variants } => {
+            let span = check_span(spans, expression, parent);
+            check_inline_expression(source, spans, selector, &span);
+            for variant in variants {
+                let variant_span = check_span(spans, variant, &span);
+                let key = check_span(spans, &variant.key, &variant_span);
+                match &variant.key {
+                    ast::VariantKey::Identifier { name: key_name }
+                    | ast::VariantKey::NumberLiteral { value: key_name } => {
+                        assert_eq!(&source[key], *key_name);
+                    }
+                }
+                check_pattern(source, spans, &variant.value, &variant_span);
+            }
+        }
+    }
+}
+
+fn check_call_arguments(
+    source: &str,
+    spans: &EntrySpans,
+    arguments: &ast::CallArguments<&str>,
+    parent: &Range<usize>,
+) {
+    let span = check_span(spans, arguments, parent);
+    for argument in &arguments.positional {
+        check_inline_expression(source, spans, argument, &span);
+    }
+    for argument in &arguments.named {
+        let argument_span = check_span(spans, argument, &span);
+        check_identifier(source, spans, &argument.name, &argument_span);
+        check_inline_expression(source, spans, &argument.value, &argument_span);
+    }
+}
+
+fn check_inline_expression(
+    source: &str,
+    spans: &EntrySpans,
+    expression: &ast::InlineExpression<&str>,
+    parent: &Range<usize>,
+) {
+    let span = check_span(spans, expression, parent);
+    match expression {
+        ast::InlineExpression::StringLiteral { value } => {
+            assert_eq!(&source[span.start + 1..span.end - 1], *value);
+        }
+        ast::InlineExpression::NumberLiteral { value } => {
+            assert_eq!(&source[span], *value);
+        }
+        ast::InlineExpression::FunctionReference { id, arguments } => {
+            check_identifier(source, spans, id, &span);
+            check_call_arguments(source, spans, arguments, &span);
+        }
+        ast::InlineExpression::MessageReference { id, attribute } => {
+            check_identifier(source, spans, id, &span);
+            if let Some(attribute) = attribute {
+                check_identifier(source, spans, attribute, &span);
+            }
+        }
+        ast::InlineExpression::TermReference {
+            id,
+            attribute,
+            arguments,
+        } => {
+            assert!(source[span.clone()].starts_with('-'));
+            check_identifier(source, spans, id, &span);
+            if let Some(attribute) = attribute {
+                check_identifier(source, spans, attribute, &span);
+            }
+            if let Some(arguments) = arguments {
+                check_call_arguments(source, spans, arguments, &span);
+            }
+        }
+        ast::InlineExpression::VariableReference { id } => {
+            assert!(source[span.clone()].starts_with('$'));
+            check_identifier(source, spans, id, &span);
+        }
+        ast::InlineExpression::Placeable { expression } => {
+            check_expression(source, spans, expression, &span);
+        }
+    }
+}
+
+fn check_entry_fields(
+    source: &str,
+    spans: &EntrySpans,
+    id: &ast::Identifier<&str>,
+    value: Option<&ast::Pattern<&str>>,
+    attributes: &[ast::Attribute<&str>],
+    comment: Option<&ast::Comment<&str>>,
+    span: &Range<usize>,
+) {
+    check_identifier(source, spans, id, span);
+    if let Some(value) = value {
+        check_pattern(source, spans, value, span);
+    }
+    for attribute in attributes {
+        let attribute_span = check_span(spans, attribute, span);
+        assert!(source[attribute_span.clone()].starts_with('.'));
+        check_identifier(source, spans, &attribute.id, &attribute_span);
+        check_pattern(source, spans, &attribute.value, &attribute_span);
+    }
+    if let Some(comment) = comment {
+        let comment_span = check_span(spans, comment, span);
+        assert_eq!(comment_span.start, span.start);
+    }
+}
+
+fn check_resource(source: &str, spans: &Spans, resource: &ast::Resource<&str>) {
+    let span = 0..source.len();
+
+    for (index, entry) in resource.body.iter().enumerate() {
+        let spans = &EntrySpans {
+            spans,
+            index,
+            entry,
+        };
+        let entry_span = check_span(spans, entry, &span);
+        match entry {
+            ast::Entry::Message(message) => {
+                assert_eq!(spans.get(message), Some(entry_span.clone()));
+                check_entry_fields(
+                    source,
+                    spans,
+                    &message.id,
+                    message.value.as_ref(),
+                    &message.attributes,
+                    message.comment.as_ref(),
+                    &entry_span,
+                );
+            }
+            ast::Entry::Term(term) => {
+                assert_eq!(spans.get(term), Some(entry_span.clone()));
+                if term.comment.is_none() {
+                    assert!(source[entry_span.clone()].starts_with('-'));
+                }
+                check_entry_fields(
+                    source,
+                    spans,
+                    &term.id,
+                    Some(&term.value),
+                    &term.attributes,
+                    term.comment.as_ref(),
+                    &entry_span,
+                );
+            }
+            ast::Entry::Comment(comment)
+            | ast::Entry::GroupComment(comment)
+            | ast::Entry::ResourceComment(comment) => {
+                assert_eq!(spans.get(comment), Some(entry_span.clone()));
+                assert!(source[entry_span].starts_with('#'));
+            }
+            ast::Entry::Junk { content } => {
+                assert_eq!(&source[entry_span], *content);
+            }
+        }
+    }
+}
+
+#[test]
+fn parse_with_spans_fixtures() {
+    for entry in glob("./tests/fixtures/*.ftl").expect("Failed to read glob pattern") {
+        let path = entry.expect("Error while getting an entry");
+        let source = fs::read_to_string(&path).expect("Failed to read file");
+
+        let (result, spans) = parse_with_spans(source.as_str());
+        assert_eq!(result, parse(source.as_str()), "{}", path.display());
+        let resource = match result {
+            Ok(resource) => resource,
+            Err((resource, _)) => resource,
+        };
+        check_resource(&source, &spans, &resource);
+    }
+}
+
+#[test]
+fn spans_of_cloned_and_modified_resources() {
+    let source = "key1 = { $var }\nkey2 = Value\n";
+    let (result, spans) = parse_with_spans(source);
+    let resource = result.expect("Failed to parse");
+
+    // The spans are stored by the position of the nodes, so they apply to an
+    // equal resource too.
+    let cloned = parse(source).expect("Failed to parse");
+    assert_eq!(cloned, resource);
+    let entry = &cloned.body[0];
+    let ast::Entry::Message(message) = entry else {
+        panic!("Expected a message.");
+    };
+    assert_eq!(spans.get(0, entry, &message.id), Some(0..4));
+
+    // Nodes of other entries don't have a span in this one.
+    assert_eq!(spans.get(1, &cloned.body[1], &message.id), None);
+
+    // Nor do the nodes of a modified entry.
+    let mut modified = cloned.clone();
+    let ast::Entry::Message(message) = &mut modified.body[0] else {
+        panic!("Expected a message.");
+    };
+    message.value = None;
+    let entry = &modified.body[0];
+    assert_eq!(spans.get(0, entry, entry), None);
+    assert_eq!(spans.get(2, entry, entry), None);
+}
+
+#[test]
+fn message_spans() {
+    let source = "\
+## Group
+
+# Comment
+key = Value
+    .attr = { -term(arg: 1) }
+
+-term = { $num ->
+    [one] One
+   *[other] Other
+}
+";
+    let (result, spans) = parse_with_spans(source);
+    let resource = result.expect("Failed to parse");
+    let text = |span: Option<Range<usize>>| &source[span.expect("The node has a span.")];
+    let entry_spans = |index| EntrySpans {
+        spans: &spans,
+        index,
+        entry: &resource.body[index],
+    };
+
+    let spans = entry_spans(0);
+    let ast::Entry::GroupComment(comment) = &resource.body[0] else {
+        panic!("Expected a group comment.");
+    };
+    assert_eq!(spans.get(comment), Some(0..8));
+
+    let spans = entry_spans(1);
+    let ast::Entry::Message(message) = &resource.body[1] else {
+        panic!("Expected a message.");
+    };
+    assert_eq!(
+        text(spans.get(message)),
+        "# Comment\nkey = Value\n    .attr = { -term(arg: 1) }"
+    );
+    let attr = &message.attributes[0];
+    assert_eq!(text(spans.get(attr)), ".attr = { -term(arg: 1) }");
+    let ast::PatternElement::Placeable {
+        expression: ast::Expression::Inline(reference),
+    } = &attr.value.elements[0]
+    else {
+        panic!("Expected a placeable.");
+    };
+    let ast::InlineExpression::TermReference {
+        arguments: Some(arguments),
+        ..
+    } = reference
+    else {
+        panic!("Expected a term reference.");
+    };
+    assert_eq!(text(spans.get(reference)), "-term(arg: 1)");
+    assert_eq!(text(spans.get(arguments)), "(arg: 1)");
+    assert_eq!(text(spans.get(&arguments.named[0])), "arg: 1");
+    assert_eq!(text(spans.get(&arguments.named[0].name)), "arg");
+
+    let spans = entry_spans(2);
+    let ast::Entry::Term(term) = &resource.body[2] else {
+        panic!("Expected a term.");
+    };
+    assert_eq!(
+        text(spans.get(term)),
+        "-term = { $num ->\n    [one] One\n   *[other] Other\n}"
+    );
+    let ast::PatternElement::Placeable {
+        expression: select @ ast::Expression::Select { variants, .. },
+    } = &term.value.elements[0]
+    else {
+        panic!("Expected a select expression.");
+    };
+    assert_eq!(
+        text(spans.get(select)),
+        "$num ->\n    [one] One\n   *[other] Other"
+    );
+    assert_eq!(text(spans.get(&variants[0])), "[one] One");
+    assert_eq!(text(spans.get(&variants[1])), "*[other] Other");
+    assert_eq!(text(spans.get(&variants[1].key)), "other");
+}
@@ -75,7 +75,10 @@
 //!
 //! [`FluentBundle`]: bundle::FluentBundle
 
+use std::borrow::{Borrow, Cow};
+
 pub use fluent_bundle::*;
+pub use fluent_macros::fluent_message;
 
 /// A helper macro to simplify creation of `FluentArgs`.
 ///
@@ -102,3 +105,78 @@ macro_rules! fluent_args {
         }
     };
 }
+
+/// A reference to a message or an attribute with its arguments, created by
+/// [`fluent_message!`].
+#[derive(Debug)]
+pub struct MessageRef<'a> {
+    pub id: &'static str,
+    pub attribute: Option<&'static str>,
+    pub args: Option<FluentArgs<'a>>,
+}
+
+impl MessageRef<'_> {
+    /// Formats the message or the attribute with `bundle`, or returns `None` if the
+    /// bundle doesn't have it.
+    pub fn format<'b, R, M>(
+        &self,
+        bundle: &'b bundle::FluentBundle<R, M>,
+        errors: &mut Vec<FluentError>,
+    ) -> Option<Cow<'b, str>>
+    where
+        R: Borrow<FluentResource>,
+        M: memoizer::MemoizerKind,
+    {
+        let message = bundle.get_message(self.id)?;
+        let pattern = match self.attribute {
+            Some(attribute) => message.get_attribute(attribute)?.value(),
+            None => message.value()?,
+        };
+        Some(bundle.format_pattern(pattern, self.args.as_ref(), errors))
+    }
+}
+
+/// The compile errors of [`fluent_message!`], checked by the doctests.
+///
+/// A valid reference compiles:
+///
+/// ```
+/// let user = fluent::fluent_message!(
+///     "tests/fixtures/en-US",
+///     "user",
+///     "firstName" => "Jane",
+///     "lastName" => "Doe",
+/// );
+/// ```
+///
+/// An unknown message doesn't:
+///
+/// ```compile_fail
+/// let user = fluent::fluent_message!("tests/fixtures/en-US", "unknown");
+/// ```
+///
+/// Nor a missing argument:
+///
+/// ```compile_fail
+/// let user = fluent::fluent_message!("tests/fixtures/en-US", "user", "firstName" => "Jane");
+/// ```
+///
+/// Nor an argument the message doesn't use:
+///
+/// ```compile_fail
+/// let hello = fluent::fluent_message!("tests/fixtures/en-US", "hello", "name" => "Jane");
+/// ```
+///
+/// Nor a duplicate argument:
+///
+/// ```compile_fail
+/// let user = fluent::fluent_message!(
+///     "tests/fixtures/en-US",
+///     "user",
+///     "firstName" => "Jane",
+///     "firstName" => "John",
+///     "lastName" => "Doe",
+/// );
+/// ```
+#[cfg(doctest)]
+pub struct FluentMessageErrors;
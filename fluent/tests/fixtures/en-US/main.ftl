@@ -0,0 +1,7 @@
+hello = Hello, world!
+user = { $firstName } { $lastName }
+emails = { user } has { $count ->
+        [one] one new email
+       *[other] { $count } new emails
+    }.
+    .title = New emails
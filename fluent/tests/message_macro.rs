@@ -0,0 +1,44 @@
+use fluent::{fluent_message, FluentBundle, FluentResource};
+use unic_langid::langid;
+
+const MAIN: &str = include_str!("fixtures/en-US/main.ftl");
+
+#[test]
+fn test_fluent_message() {
+    let resource =
+        FluentResource::try_new(MAIN.to_string()).expect("Failed to parse an FTL string.");
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(resource)
+        .expect("Failed to add a resource.");
+    let mut errors = vec![];
+
+    let hello = fluent_message!("tests/fixtures/en-US", "hello");
+    assert_eq!(hello.id, "hello");
+    assert!(hello.args.is_none());
+    assert_eq!(
+        hello.format(&bundle, &mut errors).as_deref(),
+        Some("Hello, world!")
+    );
+
+    let emails = fluent_message!(
+        "tests/fixtures/en-US",
+        "emails",
+        "firstName" => "Jane",
+        "lastName" => "Doe",
+        "count" => 3,
+    );
+    assert_eq!(
+        emails.format(&bundle, &mut errors).as_deref(),
+        Some("Jane Doe has 3 new emails.")
+    );
+
+    let title = fluent_message!("tests/fixtures/en-US/main.ftl", "emails.title");
+    assert_eq!(title.attribute, Some("title"));
+    assert_eq!(
+        title.format(&bundle, &mut errors).as_deref(),
+        Some("New emails")
+    );
+    assert!(errors.is_empty());
+}